@@ -0,0 +1,103 @@
+/*
+    Organizes the same pieces with the spatial index and with the linear scan it replaced,
+    the layouts must be the same and the time of each search is printed.
+
+    cargo bench --bench spatial_index_benchmark
+ */
+
+#![allow(dead_code)]
+// checked with every target the modules keep their tests, which a benchmark without harness does not run
+#![cfg_attr(test, allow(unused_imports))]
+
+use std::time::{Duration, Instant};
+
+#[path = "../src/models/mod.rs"]
+mod models;
+#[path = "../src/services/mod.rs"]
+mod services;
+#[cfg(test)]
+#[path = "../src/test_support.rs"]
+mod test_support;
+
+use models::{cut_disposition::{CutDispositionInput, CutDispositionOutput, EdgeMargins, NestingStrategyType, Rectangle}, fabric::FabricGrain, fixed_point::from_whole_millimeters};
+use services::{cut_disposition_service::get_nesting_strategy_with_collision_search, spatial_index_service::CollisionSearch};
+
+const PIECE_COUNT_LIST: [u32; 3] = [100, 500, 2000];
+
+// pieces from 20 mm to 200 mm, the same for every run
+fn get_benchmark_rectangle_list(piece_count: u32) -> Vec<Rectangle> {
+    let mut seed: u32 = 7;
+    let mut next_millimeters = move |max: u32| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        from_whole_millimeters(20 + ((seed >> 16) % max) as i32)
+    };
+
+    (1..=piece_count)
+    .map(
+        |id| Rectangle {
+            id,
+            width: next_millimeters(180),
+            length: next_millimeters(180),
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        }
+    )
+    .collect()
+}
+
+// a fabric of 1.6 m wide and 100 m long, long enough for every piece
+fn get_benchmark_input(nesting_strategy: &NestingStrategyType, piece_count: u32) -> CutDispositionInput {
+    CutDispositionInput {
+        rectangles_list: get_benchmark_rectangle_list(piece_count),
+        prohibited_area_list: Vec::new(),
+        showcase_list: get_benchmark_rectangle_list(5),
+        spacing: Some(from_whole_millimeters(5)),
+        max_length: from_whole_millimeters(100_000),
+        defined_length: None,
+        defined_width: from_whole_millimeters(1600),
+        allow_rotation: true,
+        nesting_strategy: nesting_strategy.clone(),
+        multi_sheet: false,
+        optimization: None,
+        fabric_grain: FabricGrain::RotationAllowed,
+        pattern_repeat: None,
+        edge_margins: EdgeMargins::default(),
+        pinned_piece_list: Vec::new(),
+    }
+}
+
+fn organize(cut_disposition_input: &CutDispositionInput, collision_search: CollisionSearch) -> (CutDispositionOutput, Duration) {
+    let nesting_strategy = get_nesting_strategy_with_collision_search(&cut_disposition_input.nesting_strategy, collision_search);
+
+    let start = Instant::now();
+    let output = nesting_strategy.organize(cut_disposition_input);
+
+    (output, start.elapsed())
+}
+
+fn main() {
+    for nesting_strategy in [NestingStrategyType::BottomLeftVertex, NestingStrategyType::MaxRects, NestingStrategyType::Guillotine] {
+        for piece_count in PIECE_COUNT_LIST {
+            let cut_disposition_input = get_benchmark_input(&nesting_strategy, piece_count);
+
+            let (indexed_output, indexed_duration) = organize(&cut_disposition_input, CollisionSearch::SpatialIndex);
+            let (linear_output, linear_duration) = organize(&cut_disposition_input, CollisionSearch::LinearScan);
+
+            // the index only skips the rectangles too far away, the disposition is the same
+            assert_eq!(indexed_output.positioned_rectangles_list, linear_output.positioned_rectangles_list);
+            assert_eq!(indexed_output.showcase_rectangles_located_list, linear_output.showcase_rectangles_located_list);
+
+            println!(
+                "{:?} {} peças: índice {:?}, varredura linear {:?} ({:.1}x)",
+                nesting_strategy,
+                piece_count,
+                indexed_duration,
+                linear_duration,
+                linear_duration.as_secs_f64() / indexed_duration.as_secs_f64().max(f64::EPSILON)
+            );
+        }
+    }
+}
@@ -1,31 +1,49 @@
-use std::time::Duration;
+use std::{time::{Duration, Instant}, sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}}};
 
 use serde::{Serialize, Deserialize};
-use tauri::State;
+use tauri::{State, Window};
 use tokio::time::sleep;
 
-use crate::{models::{cut_disposition::{CutDispositionInput, Rectangle, ConfigCutDispositionInput, PositionedRectangle, CutDispositionOutput}, app_error::AppError}, CutDispositionInputState, services::cut_disposition_service::organize_disposition};
+use crate::{db::db_connection::DbConnection, models::{fabric::{self, FabricGrain}, cut_disposition::{CutDispositionInput, Rectangle, ConfigCutDispositionInput, PositionedRectangle, CutDispositionOutput, OrganizeProgress, LayoutAdjustment, AdjustedCutDisposition}, app_error::AppError}, CutDispositionInputState, services::{cut_disposition_service::{organize_disposition_sheets, OrganizeObserver, get_max_length, subject_intesect_with_positioned_rectangles_list}, layout_adjustment_service::adjust_disposition_sheet, disposition_validation_service::validate_disposition, length_unit_service::{get_length_unit, InLengthUnit, FromLengthUnit}}};
+
+pub const ORGANIZE_PROGRESS_EVENT: &str = "organize-progress";
+
+// the optimizer reports every iteration, the window only needs a few updates per second
+const ORGANIZE_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
 
 #[tauri::command]
-pub async fn get_cut_disposition_input(state: State<'_, CutDispositionInputState>) -> Result<CutDispositionInput, AppError> {
+pub async fn get_cut_disposition_input(state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<CutDispositionInput>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     let a = state.cut_disposition_state.lock();
     match a {
-        Ok(cut_disposition_status) => {Ok(cut_disposition_status.get_cut_disposition_input())},
+        Ok(cut_disposition_status) => {Ok(InLengthUnit::new(cut_disposition_status.get_cut_disposition_input(), length_unit))},
         Err(_) => Err(AppError::new(1, "Erro ao buscar")),
     }
 }
 
 #[tauri::command]
-pub async fn get_config_cut_disposition_input(state: State<'_, CutDispositionInputState>) -> Result<ConfigCutDispositionInput, AppError> {
+pub async fn get_config_cut_disposition_input(state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<ConfigCutDispositionInput>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     let a = state.cut_disposition_state.lock();
     match a {
-        Ok(cut_disposition_status) => {Ok(cut_disposition_status.get_config_cut_disposition_input())},
+        Ok(cut_disposition_status) => {Ok(InLengthUnit::new(cut_disposition_status.get_config_cut_disposition_input(), length_unit))},
         Err(_) => Err(AppError::new(1, "Erro ao buscar")),
     }
 }
 
 #[tauri::command]
-pub async fn set_config_cut_disposition_input(config: ConfigCutDispositionInput, state: State<'_, CutDispositionInputState>) -> Result<ConfigCutDispositionInput, AppError> {
+pub async fn set_config_cut_disposition_input(config: FromLengthUnit<ConfigCutDispositionInput>, state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<ConfigCutDispositionInput>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
+    let config = config.convert(length_unit)?;
+
+    let (fabric_grain, pattern_repeat) = match config.fabric_id {
+        Some(fabric_id) => fabric::get(fabric_id, &db_state.db)
+            .await
+            .map(|fabric| (fabric.grain, fabric.get_pattern_repeat()))
+            .map_err(|_| AppError::new(1, "Erro ao configurar disposição: tecido não encontrado"))?,
+        None => (FabricGrain::RotationAllowed, None),
+    };
+
     let cut_disposition_state_result = state.cut_disposition_state.lock();
     match cut_disposition_state_result {
         Ok(mut cut_disposition_state) => {
@@ -41,12 +59,29 @@ pub async fn set_config_cut_disposition_input(config: ConfigCutDispositionInput,
             if config.defined_width <= 0 {
                 return Err(AppError::new(1, "Erro ao configurar disposição: largura máxima deve ser maior que zero"));
             }
+            if config.optimization.as_ref().is_some_and(|optimization| optimization.time_limit == 0) {
+                return Err(AppError::new(1, "Erro ao configurar disposição: tempo de otimização deve ser maior que zero ou inexistente"));
+            }
+            if config.optimization.as_ref().is_some_and(|optimization| optimization.max_iterations == Some(0)) {
+                return Err(AppError::new(1, "Erro ao configurar disposição: tentativas da otimização devem ser maiores que zero ou inexistentes"));
+            }
+            if !config.edge_margins.is_valid(config.defined_width, config.max_length) {
+                return Err(AppError::new(1, "Erro ao configurar disposição: margens devem ser positivas e menores que as dimensões do tecido"));
+            }
 
             cut_disposition_state.spacing = config.spacing;
             cut_disposition_state.max_length = config.max_length;
             cut_disposition_state.defined_length = config.defined_length;
             cut_disposition_state.defined_width = config.defined_width;
-            Ok(cut_disposition_state.get_config_cut_disposition_input())
+            cut_disposition_state.allow_rotation = config.allow_rotation;
+            cut_disposition_state.nesting_strategy = config.nesting_strategy;
+            cut_disposition_state.multi_sheet = config.multi_sheet;
+            cut_disposition_state.optimization = config.optimization;
+            cut_disposition_state.fabric_id = config.fabric_id;
+            cut_disposition_state.fabric_grain = fabric_grain;
+            cut_disposition_state.pattern_repeat = pattern_repeat;
+            cut_disposition_state.edge_margins = config.edge_margins;
+            Ok(InLengthUnit::new(cut_disposition_state.get_config_cut_disposition_input(), length_unit))
         },
         Err(_) => Err(AppError::new(1, "Erro ao configurarar cortes")),
     }
@@ -56,24 +91,28 @@ pub async fn set_config_cut_disposition_input(config: ConfigCutDispositionInput,
 pub enum RectangleType {
     Piece(Rectangle),
     Showcase(Rectangle),
-    ProhibitedArea(PositionedRectangle)
+    ProhibitedArea(PositionedRectangle),
+    // cut like a piece, but at the position chosen by the operator
+    PinnedPiece(PositionedRectangle)
 }
 
 #[tauri::command]
-pub async fn create_piece(piece: RectangleType, state: State<'_, CutDispositionInputState>) -> Result<(), AppError> {
+pub async fn create_piece(piece: FromLengthUnit<RectangleType>, state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<(), AppError> {
+    let piece = piece.convert(get_length_unit(&db_state.db).await?)?;
+
     let cut_disposition_state_result = state.cut_disposition_state.lock();
     match cut_disposition_state_result {
         Ok(mut cut_disposition_state) => {
             match piece {
                 RectangleType::Piece(main_piece) => {
                     if !main_piece.is_valid() {
-                        return Err(AppError::new(1, "Erro ao adicionar Peça: dimensões devem ser maiores que zero"));
+                        return Err(AppError::new(1, "Erro ao adicionar Peça: dimensões e quantidade devem ser maiores que zero e margem não pode ser negativa"));
                     }
                     cut_disposition_state.add_piece(&main_piece);
                 },
                 RectangleType::Showcase(showcase) => {
-                    if !showcase.is_valid() {
-                        return Err(AppError::new(1, "Erro ao  adicionar Mostruário: dimensões devem ser maiores que zero"));
+                    if !showcase.is_valid() || showcase.max_quantity.is_some_and(|max_quantity| max_quantity == 0) {
+                        return Err(AppError::new(1, "Erro ao  adicionar Mostruário: dimensões e quantidade máxima devem ser maiores que zero"));
                     }
                     cut_disposition_state.add_showcase(&showcase);
                 },
@@ -83,6 +122,12 @@ pub async fn create_piece(piece: RectangleType, state: State<'_, CutDispositionI
                     }
                     cut_disposition_state.add_prohibited_area(&prohibited_area);
                 },
+                RectangleType::PinnedPiece(pinned_piece) => {
+                    if !pinned_piece.is_valid() || pinned_piece.margin.is_some_and(|margin| margin < 0) {
+                        return Err(AppError::new(1, "Erro ao adicionar Peça Fixa: dimensões devem ser maiores que zero e margem não pode ser negativa"));
+                    }
+                    cut_disposition_state.add_pinned_piece(&pinned_piece);
+                },
             };
             Ok(())
         },
@@ -92,13 +137,14 @@ pub async fn create_piece(piece: RectangleType, state: State<'_, CutDispositionI
 
 
 #[tauri::command]
-pub async fn get_piece(id: u32, state: State<'_, CutDispositionInputState>) -> Result<Rectangle, AppError> {
+pub async fn get_piece(id: u32, state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Rectangle>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     let cut_disposition_state_result = state.cut_disposition_state.lock();
     match cut_disposition_state_result {
         Ok(cut_disposition_state) => {
             let item = cut_disposition_state.get_piece_by_id(id);
             match item {
-                Ok(rectangle) => Ok(rectangle),
+                Ok(rectangle) => Ok(InLengthUnit::new(rectangle, length_unit)),
                 Err(()) => Err(AppError::new(1, "Erro ao buscar Peça")),
             }
         }
@@ -107,25 +153,30 @@ pub async fn get_piece(id: u32, state: State<'_, CutDispositionInputState>) -> R
 }
 
 #[tauri::command]
-pub async fn get_showcase(state: State<'_, CutDispositionInputState>) -> Result<Option<Rectangle>, AppError> {
+pub async fn get_showcase(id: u32, state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Rectangle>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     let cut_disposition_state_result = state.cut_disposition_state.lock();
     match cut_disposition_state_result {
         Ok(cut_disposition_state) => {
-            let item = cut_disposition_state.get_showcase();
-            Ok(item)
+            let item = cut_disposition_state.get_showcase_by_id(id);
+            match item {
+                Ok(rectangle) => Ok(InLengthUnit::new(rectangle, length_unit)),
+                Err(()) => Err(AppError::new(1, "Erro ao buscar Mostruário")),
+            }
         }
         Err(_) => Err(AppError::new(1, "Erro ao buscar Mostruário")),
     }
 }
 
 #[tauri::command]
-pub async fn get_prohibited_area(id: u32, state: State<'_, CutDispositionInputState>) -> Result<PositionedRectangle, AppError> {
+pub async fn get_prohibited_area(id: u32, state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<PositionedRectangle>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     let cut_disposition_state_result = state.cut_disposition_state.lock();
     match cut_disposition_state_result {
         Ok(cut_disposition_state) => {
             let item = cut_disposition_state.get_prohibited_area_by_id(id);
             match item {
-                Ok(rectangle) => Ok(rectangle),
+                Ok(rectangle) => Ok(InLengthUnit::new(rectangle, length_unit)),
                 Err(()) => Err(AppError::new(1, "Erro ao buscar Área Proibida")),
             }
         }
@@ -134,20 +185,38 @@ pub async fn get_prohibited_area(id: u32, state: State<'_, CutDispositionInputSt
 }
 
 #[tauri::command]
-pub async fn edit_piece(piece: RectangleType, state: State<'_, CutDispositionInputState>) -> Result<(), AppError> {
+pub async fn get_pinned_piece(id: u32, state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<PositionedRectangle>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
+    let cut_disposition_state_result = state.cut_disposition_state.lock();
+    match cut_disposition_state_result {
+        Ok(cut_disposition_state) => {
+            let item = cut_disposition_state.get_pinned_piece_by_id(id);
+            match item {
+                Ok(rectangle) => Ok(InLengthUnit::new(rectangle, length_unit)),
+                Err(()) => Err(AppError::new(1, "Erro ao buscar Peça Fixa")),
+            }
+        }
+        Err(_) => Err(AppError::new(1, "Erro ao buscar Peça Fixa")),
+    }
+}
+
+#[tauri::command]
+pub async fn edit_piece(piece: FromLengthUnit<RectangleType>, state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<(), AppError> {
+    let piece = piece.convert(get_length_unit(&db_state.db).await?)?;
+
     let cut_disposition_state_result = state.cut_disposition_state.lock();
     match cut_disposition_state_result {
         Ok(mut cut_disposition_state) => {
             match piece {
                 RectangleType::Piece(main_piece) => {
                     if !main_piece.is_valid() {
-                        return Err(AppError::new(1, "Erro ao adicionar Peça: dimensões devem ser maiores que zero"));
+                        return Err(AppError::new(1, "Erro ao adicionar Peça: dimensões e quantidade devem ser maiores que zero e margem não pode ser negativa"));
                     }
                     cut_disposition_state.edit_piece(main_piece)?;
                 },
                 RectangleType::Showcase(showcase) => {
-                    if !showcase.is_valid() {
-                        return Err(AppError::new(1, "Erro ao  adicionar Mostruário: dimensões devem ser maiores que zero"));
+                    if !showcase.is_valid() || showcase.max_quantity.is_some_and(|max_quantity| max_quantity == 0) {
+                        return Err(AppError::new(1, "Erro ao  adicionar Mostruário: dimensões e quantidade máxima devem ser maiores que zero"));
                     }
                     cut_disposition_state.edit_showcase(showcase)?;
                 },
@@ -157,6 +226,12 @@ pub async fn edit_piece(piece: RectangleType, state: State<'_, CutDispositionInp
                     }
                     cut_disposition_state.edit_prohibited_area(prohibited_area)?;
                 },
+                RectangleType::PinnedPiece(pinned_piece) => {
+                    if !pinned_piece.is_valid() || pinned_piece.margin.is_some_and(|margin| margin < 0) {
+                        return Err(AppError::new(1, "Erro ao adicionar Peça Fixa: dimensões devem ser maiores que zero e margem não pode ser negativa"));
+                    }
+                    cut_disposition_state.edit_pinned_piece(pinned_piece)?;
+                },
             };
             Ok(())
         },
@@ -209,11 +284,96 @@ pub async fn delete_prohibited_area(id: u32, state: State<'_, CutDispositionInpu
     }
 }
 
+#[tauri::command]
+pub async fn delete_pinned_piece(id: u32, state: State<'_, CutDispositionInputState>) -> Result<(), AppError> {
+    let cut_disposition_state_result = state.cut_disposition_state.lock();
+    match cut_disposition_state_result {
+        Ok(mut cut_disposition_state) => {
+            let item = cut_disposition_state.remove_pinned_piece(id);
+            match item {
+                Ok(_rectangle) => Ok(()),
+                Err(_) => Err(AppError::new(1, "Erro ao remover Peça Fixa")),
+            }
+        }
+        Err(_) => Err(AppError::new(1, "Erro ao remover Peça Fixa")),
+    }
+}
 
 #[tauri::command]
-pub async fn organize_cut_disposition(state: State<'_, CutDispositionInputState>) -> Result<CutDispositionOutput, AppError> {
+pub async fn organize_cut_disposition(window: Window, state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Vec<CutDispositionOutput>>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     // Wait for a milisec because it was returning before the table was updated
     sleep(Duration::from_millis(1)).await;
+    let cut_disposition_input = get_validated_cut_disposition_input(&state)?;
+
+    state.organize_cancelled.store(false, Ordering::SeqCst);
+
+    let observer = WindowOrganizeObserver {
+        window,
+        organize_cancelled: state.organize_cancelled.clone(),
+        last_emit: Mutex::new(None),
+        pending_progress: Mutex::new(None),
+    };
+
+    // the state is not locked while organizing, so the cancellation can reach it
+    let (cut_disposition_input, sheet_list) = tokio::task::spawn_blocking(
+        move || {
+            let sheet_list = organize_disposition_sheets(&cut_disposition_input, &observer);
+            observer.emit_pending_progress();
+            (cut_disposition_input, sheet_list)
+        })
+        .await
+        .map_err(|_| AppError::new(1, "Erro ao organizar disposição"))?;
+
+    // a new organization replaces the adjustments made on the previous one
+    state.cut_disposition_state
+        .lock()
+        .map_err(|_| AppError::new(1, "Erro ao organizar disposição"))?
+        .set_layout(cut_disposition_input, sheet_list.clone());
+
+    Ok(InLengthUnit::new(sheet_list, length_unit))
+}
+
+// None when the pieces or the config changed after the last organization
+#[tauri::command]
+pub async fn get_cut_disposition_layout(state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Option<Vec<CutDispositionOutput>>>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
+    let state_result = state.cut_disposition_state.lock();
+    match state_result {
+        Ok(cut_disposition_state) => Ok(InLengthUnit::new(cut_disposition_state.get_layout_sheet_list(), length_unit)),
+        Err(_) => Err(AppError::new(1, "Erro ao buscar disposição")),
+    }
+}
+
+#[tauri::command]
+pub async fn adjust_cut_disposition(sheet_index: usize, adjustment: FromLengthUnit<LayoutAdjustment>, state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<AdjustedCutDisposition>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
+    let adjustment = adjustment.convert(length_unit)?;
+
+    let state_result = state.cut_disposition_state.lock();
+    match state_result {
+        Ok(mut cut_disposition_state) => {
+            let cut_disposition_input = cut_disposition_state.get_cut_disposition_input();
+            let sheet = cut_disposition_state.get_layout_sheet(sheet_index)?;
+
+            let cut_disposition_output = adjust_disposition_sheet(&cut_disposition_input, &sheet, &adjustment)?;
+            let violation_list = validate_disposition(&cut_disposition_output, cut_disposition_input.spacing.unwrap_or(0));
+
+            cut_disposition_state.set_layout_sheet(sheet_index, cut_disposition_output.clone())?;
+
+            Ok(InLengthUnit::new(AdjustedCutDisposition { cut_disposition_output, violation_list }, length_unit))
+        },
+        Err(_) => Err(AppError::new(1, "Erro ao ajustar disposição")),
+    }
+}
+
+#[tauri::command]
+pub async fn cancel_organize_cut_disposition(state: State<'_, CutDispositionInputState>) -> Result<(), AppError> {
+    state.organize_cancelled.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+fn get_validated_cut_disposition_input(state: &State<'_, CutDispositionInputState>) -> Result<CutDispositionInput, AppError> {
     let state_result = state.cut_disposition_state.lock();
     match state_result {
         Ok(cut_disposition_state) => {
@@ -229,16 +389,78 @@ pub async fn organize_cut_disposition(state: State<'_, CutDispositionInputState>
             if cut_disposition_state.defined_width <= 0 {
                 return Err(AppError::new(1, "Erro ao organizar disposição: largura máxima deve ser maior que zero"));
             }
-            Ok(organize_disposition(&CutDispositionInput {
-                rectangles_list: cut_disposition_state.rectangles_list.clone(),
-                prohibited_area_list: cut_disposition_state.prohibited_area_list.clone(),
-                showcase: cut_disposition_state.showcase.clone(),
-                spacing: cut_disposition_state.spacing,
-                max_length: cut_disposition_state.max_length,
-                defined_length: cut_disposition_state.defined_length,
-                defined_width: cut_disposition_state.defined_width
-            }))
+            if !cut_disposition_state.edge_margins.is_valid(cut_disposition_state.defined_width, cut_disposition_state.max_length) {
+                return Err(AppError::new(1, "Erro ao organizar disposição: margens devem ser positivas e menores que as dimensões do tecido"));
+            }
+
+            let cut_disposition_input = cut_disposition_state.get_cut_disposition_input();
+            verify_pinned_piece_list(&cut_disposition_input)?;
+
+            Ok(cut_disposition_input)
         },
         Err(_) => Err(AppError::new(1, "Erro ao organizar disposição")),
     }
-}
\ No newline at end of file
+}
+
+// the pinned pieces are not moved, so they must already be inside the usable fabric and apart from each other
+fn verify_pinned_piece_list(cut_disposition_input: &CutDispositionInput) -> Result<(), AppError> {
+    let edge_margins = &cut_disposition_input.edge_margins;
+    let spacing = cut_disposition_input.spacing.unwrap_or(0);
+    let max_length = get_max_length(cut_disposition_input);
+
+    for (index, pinned_piece) in cut_disposition_input.pinned_piece_list.iter().enumerate() {
+        if 
+        pinned_piece.top_left_vertex.pos_x < edge_margins.left || 
+        pinned_piece.top_left_vertex.pos_y < edge_margins.top ||
+        pinned_piece.top_left_vertex.pos_x + pinned_piece.width > cut_disposition_input.defined_width - edge_margins.right ||
+        pinned_piece.top_left_vertex.pos_y + pinned_piece.length > max_length {
+            return Err(AppError::new(1, format!("Erro ao organizar disposição: peça fixa {} fora da área útil do tecido", pinned_piece.id).as_str()));
+        }
+        if subject_intesect_with_positioned_rectangles_list(pinned_piece, &cut_disposition_input.prohibited_area_list, 0) {
+            return Err(AppError::new(1, format!("Erro ao organizar disposição: peça fixa {} sobre uma área proibida", pinned_piece.id).as_str()));
+        }
+        if subject_intesect_with_positioned_rectangles_list(pinned_piece, &cut_disposition_input.pinned_piece_list[index + 1..], spacing) {
+            return Err(AppError::new(1, format!("Erro ao organizar disposição: peça fixa {} sobre outra peça fixa", pinned_piece.id).as_str()));
+        }
+    }
+
+    Ok(())
+}
+
+struct WindowOrganizeObserver {
+    window: Window,
+    organize_cancelled: Arc<AtomicBool>,
+    last_emit: Mutex<Option<Instant>>,
+    // the last progress held back by the interval, sent when the organization ends so the window reaches the final usage
+    pending_progress: Mutex<Option<OrganizeProgress>>,
+}
+
+impl WindowOrganizeObserver {
+    fn emit_pending_progress(&self) {
+        if let Some(organize_progress) = self.pending_progress.lock().ok().and_then(|mut pending_progress| pending_progress.take()) {
+            let _ = self.window.emit(ORGANIZE_PROGRESS_EVENT, organize_progress);
+        }
+    }
+}
+
+impl OrganizeObserver for WindowOrganizeObserver {
+    fn on_progress(&self, organize_progress: OrganizeProgress) {
+        if let Ok(mut last_emit) = self.last_emit.lock() {
+            if last_emit.is_some_and(|instant| instant.elapsed() < ORGANIZE_PROGRESS_INTERVAL) {
+                if let Ok(mut pending_progress) = self.pending_progress.lock() {
+                    *pending_progress = Some(organize_progress);
+                }
+                return;
+            }
+            *last_emit = Some(Instant::now());
+        }
+        if let Ok(mut pending_progress) = self.pending_progress.lock() {
+            *pending_progress = None;
+        }
+        let _ = self.window.emit(ORGANIZE_PROGRESS_EVENT, organize_progress);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.organize_cancelled.load(Ordering::SeqCst)
+    }
+}
@@ -9,17 +9,18 @@ use crate::{
         app_error::{AppError, DEFAULT_ERROR_CODE},
         cutting_table::{self, CuttingTable, CuttingTableCreate},
     },
-    services::cutting_table_service,
+    services::{cutting_table_service, length_unit_service::{get_length_unit, InLengthUnit, FromLengthUnit}},
 };
 
 #[tauri::command]
 pub async fn get_cutting_table(
     id: i32,
     db_state: State<'_, DbConnection>,
-) -> Result<CuttingTable, AppError> {
+) -> Result<InLengthUnit<CuttingTable>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     let result = cutting_table::get(id, &db_state.db).await;
     match result {
-        Ok(table) => Ok(table),
+        Ok(table) => Ok(InLengthUnit::new(table, length_unit)),
         Err(_error) => Err(AppError::new(
             DEFAULT_ERROR_CODE,
             format!("Falha ao buscar Mesa: {}", id).as_str(),
@@ -30,12 +31,13 @@ pub async fn get_cutting_table(
 #[tauri::command]
 pub async fn get_all_cutting_table(
     db_state: State<'_, DbConnection>,
-) -> Result<Vec<CuttingTable>, AppError> {
+) -> Result<InLengthUnit<Vec<CuttingTable>>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     // Wait for a milisec because it was returning before the table was updated
     sleep(Duration::from_millis(1)).await;
     let result = cutting_table::get_all(&db_state.db).await;
     match result {
-        Ok(table) => Ok(table),
+        Ok(table) => Ok(InLengthUnit::new(table, length_unit)),
         Err(_error) => Err(AppError::new(
             DEFAULT_ERROR_CODE,
             "Falha ao buscar lista de Mesa",
@@ -47,26 +49,31 @@ pub async fn get_all_cutting_table(
 pub async fn delete_cutting_table(
     id: i32,
     db_state: State<'_, DbConnection>,
-) -> Result<CuttingTable, AppError> {
+) -> Result<InLengthUnit<CuttingTable>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     let result = cutting_table::delete(id, &db_state.db).await;
     match result {
-        Ok(table) => Ok(table),
+        Ok(table) => Ok(InLengthUnit::new(table, length_unit)),
         Err(_error) => Err(AppError::new(DEFAULT_ERROR_CODE, "Falha ao remover Mesa")),
     }
 }
 
 #[tauri::command]
 pub async fn create_cutting_table(
-    table: CuttingTableCreate,
+    table: FromLengthUnit<CuttingTableCreate>,
     db_state: State<'_, DbConnection>,
-) -> Result<CuttingTable, AppError> {
-    cutting_table_service::create(table, &db_state.db).await
+) -> Result<InLengthUnit<CuttingTable>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
+    let table = cutting_table_service::create(table.convert(length_unit)?, &db_state.db).await?;
+    Ok(InLengthUnit::new(table, length_unit))
 }
 
 #[tauri::command]
 pub async fn update_cutting_table(
-    table: CuttingTable,
+    table: FromLengthUnit<CuttingTable>,
     db_state: State<'_, DbConnection>,
-) -> Result<CuttingTable, AppError> {
-    cutting_table_service::update(table, &db_state.db).await
+) -> Result<InLengthUnit<CuttingTable>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
+    let table = cutting_table_service::update(table.convert(length_unit)?, &db_state.db).await?;
+    Ok(InLengthUnit::new(table, length_unit))
 }
@@ -3,7 +3,7 @@ use std::{time::Duration, path::{self}};
 use tauri::{State, api::{path::home_dir, dialog::blocking::FileDialogBuilder}};
 use tokio::time::sleep;
 
-use crate::{models::{app_error::{AppError, DEFAULT_ERROR_CODE}, cut_disposition::CutDispositionState}, CutDispositionInputState, services::file_service::{GENERATED_FILES_FOLDER, DISPOSITION_FOLDER, write_to_new_file, get_file_text}};
+use crate::{db::db_connection::DbConnection, models::{app_error::{AppError, DEFAULT_ERROR_CODE}, cut_disposition::{CutDispositionState, DispositionViolation}}, CutDispositionInputState, services::{file_service::{GENERATED_FILES_FOLDER, DISPOSITION_FOLDER, write_to_new_file, get_file_text}, disposition_validation_service::validate_imported_disposition, length_unit_service::{get_length_unit, InLengthUnit}}};
 
 #[tauri::command]
 pub async fn export_disposition(file_name: String, state: State<'_, CutDispositionInputState>) -> Result<String, AppError> {
@@ -33,8 +33,11 @@ pub async fn export_disposition(file_name: String, state: State<'_, CutDispositi
     .map_err(|e| AppError::new(DEFAULT_ERROR_CODE, e.to_string().as_str()))
 }
 
+// The violations of the layout in the file are returned and nothing is imported, an empty list means it was imported
 #[tauri::command]
-pub async fn import_disposition(state: State<'_, CutDispositionInputState>) -> Result<String, AppError> {
+pub async fn import_disposition(state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Vec<DispositionViolation>>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
+
     let mut home_path = "".to_string();
     if let Some(home_path_buf) = home_dir() {
         if let Some(home_str) = home_path_buf.to_str() {
@@ -56,12 +59,18 @@ pub async fn import_disposition(state: State<'_, CutDispositionInputState>) -> R
             let content: CutDispositionState = serde_json::from_str(&content)
                 .map_err(|_| AppError::new(1, "Falha ao carregado o arquivo"))?;
 
+            // a file edited by hand must not reach the machine with a broken layout
+            let violation_list = validate_imported_disposition(&content);
+            if !violation_list.is_empty() {
+                return Ok(InLengthUnit::new(violation_list, length_unit));
+            }
+
             let mut lock = state.cut_disposition_state.lock()
                 .map_err(|_| AppError::new(1, "Falha ao carregado o arquivo"))?;
 
             *lock = content;
 
-            return Ok("Disposição importada".to_string());
+            return Ok(InLengthUnit::new(Vec::new(), length_unit));
         }
     }
     Err(AppError::new(1, "Nenhum arquivo selecionado"))
@@ -3,43 +3,50 @@ use std::time::Duration;
 use tauri::State;
 use tokio::time::sleep;
 
-use crate::{db::db_connection::DbConnection, models::{fabric::{self, Fabric, FabricCreate}, app_error::{AppError, DEFAULT_ERROR_CODE}}, services::fabric_service}; 
+use crate::{db::db_connection::DbConnection, models::{fabric::{self, Fabric, FabricCreate}, app_error::{AppError, DEFAULT_ERROR_CODE}}, services::{fabric_service, length_unit_service::{get_length_unit, InLengthUnit, FromLengthUnit}}}; 
 
 #[tauri::command]
-pub async fn get_fabric(id: i32, db_state: State<'_, DbConnection>) -> Result<Fabric, AppError> {
+pub async fn get_fabric(id: i32, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Fabric>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     let result = fabric::get(id, &db_state.db).await;
     match result {
-        Ok(fab) => Ok(fab),
+        Ok(fab) => Ok(InLengthUnit::new(fab, length_unit)),
         Err(_error) => Err(AppError::new(DEFAULT_ERROR_CODE, format!("Falha ao buscar Tecido: {}", id).as_str()))
     }
 }
 
 #[tauri::command]
-pub async fn get_all_fabric(db_state: State<'_, DbConnection>) -> Result<Vec<Fabric>, AppError> {
+pub async fn get_all_fabric(db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Vec<Fabric>>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     // Wait for a milisec because it was returning before the table was updated
     sleep(Duration::from_millis(1)).await; 
     let result = fabric::get_all(&db_state.db).await;
     match result {
-        Ok(fab) => Ok(fab),
+        Ok(fab) => Ok(InLengthUnit::new(fab, length_unit)),
         Err(_error) => Err(AppError::new(DEFAULT_ERROR_CODE, "Falha ao buscar lista de Tecido")),
     }
 }
 
 #[tauri::command]
-pub async fn delete_fabric(id: i32, db_state: State<'_, DbConnection>) -> Result<Fabric, AppError> {
+pub async fn delete_fabric(id: i32, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Fabric>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
     let result = fabric::delete(id, &db_state.db).await;
     match result {
-        Ok(fab) => Ok(fab),
+        Ok(fab) => Ok(InLengthUnit::new(fab, length_unit)),
         Err(_error) => Err(AppError::new(DEFAULT_ERROR_CODE, "Falha ao remover Tecido")),
     }
 }
 
 #[tauri::command]
-pub async fn create_fabric(fabric: FabricCreate, db_state: State<'_, DbConnection>) -> Result<Fabric, AppError> {
-    fabric_service::create(fabric, &db_state.db).await
+pub async fn create_fabric(fabric: FromLengthUnit<FabricCreate>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Fabric>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
+    let fabric = fabric_service::create(fabric.convert(length_unit)?, &db_state.db).await?;
+    Ok(InLengthUnit::new(fabric, length_unit))
 }
 
 #[tauri::command]
-pub async fn update_fabric(fabric: Fabric, db_state: State<'_, DbConnection>) -> Result<Fabric, AppError> {
-    fabric_service::update(fabric, &db_state.db).await
+pub async fn update_fabric(fabric: FromLengthUnit<Fabric>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Fabric>, AppError> {
+    let length_unit = get_length_unit(&db_state.db).await?;
+    let fabric = fabric_service::update(fabric.convert(length_unit)?, &db_state.db).await?;
+    Ok(InLengthUnit::new(fabric, length_unit))
 }
\ No newline at end of file
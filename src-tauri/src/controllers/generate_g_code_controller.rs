@@ -3,30 +3,94 @@ use std::{time::Duration, path};
 use tauri::{State, api::path::home_dir};
 use tokio::time::sleep;
 
-use crate::{CutDispositionInputState, models::{app_error::AppError, cut_disposition::{CutDispositionInput, CutDispositionOutput}}, services::{cut_disposition_service::organize_disposition, cutting_lines_service::define_cutting_lines, gcode_service::generate_gcode_file, file_service::{FileError, GENERATED_FILES_FOLDER, GCODE_FOLDER}}};
+use crate::{CutDispositionInputState, db::db_connection::DbConnection, models::{app_error::AppError, cut_disposition::{CutDispositionOutput, Vertex}, cutting_lines::{CutStrategy, SheetCuttingLines}, setting}, services::{cut_disposition_service::{organize_disposition_sheets, NoOrganizeObserver}, disposition_validation_service::verify_disposition_sheet_list, cutting_lines_service::{define_cutting_lines, define_selvage_trim_lines, get_textile_separation_line, remove_fabric_border_lines, remove_waste_lines}, cut_sequence_service::sequence_cutting_lines, gcode_service::{generate_gcode_file, get_cut_line}, file_service::{FileError, GENERATED_FILES_FOLDER, GCODE_FOLDER}}};
 
 #[tauri::command]
-pub async fn generate_g_code(file_name: String, pull_textile: bool, state: State<'_, CutDispositionInputState>) -> Result<String, AppError> {
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_g_code(file_name: String, pull_textile: bool, one_file_per_sheet: bool, trim_selvage: bool, skip_fabric_border_lines: bool, skip_waste_lines: bool, cut_strategy: CutStrategy, state: State<'_, CutDispositionInputState>, db_state: State<'_, DbConnection>) -> Result<String, AppError> {
     if file_name.trim() == "" {
         return Err(AppError::new(1, format!("Nome inválido: {}", file_name).as_str()));
     }
 
+    let setting = match setting::get(&db_state.db).await {
+        Ok(setting) => setting,
+        Err(_error) => return Err(AppError::new(1, "Falha ao buscar unidade de medida")),
+    };
+
     sleep(Duration::from_millis(1)).await;
     
-    let cut_disposition_output = get_cut_disposition_output(state)?;
+    let cut_disposition_sheet_output_list = get_cut_disposition_sheet_output_list(state)?;
+
+    let mut sheet_cutting_lines_list = Vec::<SheetCuttingLines>::new();
 
-    let mut rectangle_list = cut_disposition_output.positioned_rectangles_list.clone();
-    rectangle_list.append(&mut cut_disposition_output.showcase_rectangles_located_list.clone());
+    let mut travel_before = 0.0;
+    let mut travel_after = 0.0;
 
-    let cutting_lines = define_cutting_lines(rectangle_list, Some(cut_disposition_output.defined_width));
+    for (index, cut_disposition_output) in cut_disposition_sheet_output_list.iter().enumerate() {
+        let mut rectangle_list = cut_disposition_output.positioned_rectangles_list.clone();
+        rectangle_list.append(&mut cut_disposition_output.showcase_rectangles_located_list.clone());
 
-    let mut textile_length_to_pull = None;
-    if pull_textile {
-        if let Ok(lenght) = u32::try_from(cut_disposition_output.length_used) {
-            textile_length_to_pull = Some(lenght);
+        let trim_line_list = if trim_selvage {
+            define_selvage_trim_lines(
+                &cut_disposition_output.edge_margins, 
+                cut_disposition_output.defined_width, 
+                cut_disposition_output.length_used
+            )
         } else {
-            return Err(AppError::new(2, "Erro ao converter comprimento do tecido"));
-        } 
+            Vec::new()
+        };
+
+        let mut kept_line_list = trim_line_list.clone();
+        kept_line_list.push(get_textile_separation_line(cut_disposition_output.defined_width));
+
+        let mut obstacle_list = rectangle_list.clone();
+        obstacle_list.append(&mut cut_disposition_output.prohibited_area_list.clone());
+
+        let mut cutting_lines = define_cutting_lines(
+            rectangle_list.clone(), 
+            Some(cut_disposition_output.defined_width), 
+            trim_line_list, 
+            &cut_disposition_output.prohibited_area_list
+        );
+        if skip_waste_lines {
+            cutting_lines = remove_waste_lines(cutting_lines, &rectangle_list, &kept_line_list, Some(cut_disposition_output.defined_width));
+        }
+        if skip_fabric_border_lines {
+            cutting_lines = remove_fabric_border_lines(cutting_lines, cut_disposition_output.defined_width, cut_disposition_output.defined_length);
+        }
+
+        // the head starts each sheet at the origin of the fabric, the travel is measured on the lines written in the program
+        let cut_sequence = sequence_cutting_lines(
+            cutting_lines, 
+            &Vertex { pos_x: 0, pos_y: 0 }, 
+            cut_strategy,
+            &|line| {
+                let sheet_limit = if line.is_vertical() { cut_disposition_output.length_used } else { cut_disposition_output.defined_width };
+                get_cut_line(line, &obstacle_list, sheet_limit, &setting)
+            }
+        );
+        travel_before += cut_sequence.travel_before;
+        travel_after += cut_sequence.travel_after;
+
+        // the fabric of the sheets after the first one must always be pulled
+        let mut textile_length_to_pull = None;
+        if pull_textile || index > 0 {
+            if let Ok(lenght) = u32::try_from(cut_disposition_output.length_used) {
+                textile_length_to_pull = Some(lenght);
+            } else {
+                return Err(AppError::new(2, "Erro ao converter comprimento do tecido"));
+            } 
+        }
+
+        sheet_cutting_lines_list.push(
+            SheetCuttingLines { 
+                cut_phase_list: cut_sequence.cut_phase_list, 
+                textile_length_to_pull,
+                obstacle_list,
+                sheet_width: cut_disposition_output.defined_width,
+                sheet_length: cut_disposition_output.length_used
+            }
+        );
     }
 
 
@@ -42,12 +106,40 @@ pub async fn generate_g_code(file_name: String, pull_textile: bool, state: State
         }
     }
 
-    generate_gcode_file(
-        cutting_lines.horizontal_lines, 
-        cutting_lines.vertical_lines, 
-        textile_length_to_pull, 
-        home_path.as_str(), 
-        &file_name).await.map_err(map_file_error_to_app_error)
+    // about a tenth of a millimetre is enough for the travel, one decimal less than the lengths of the unit
+    let length_unit = setting.length_unit;
+    let travel_decimal_places = length_unit.get_decimal_places() as usize - 1;
+    let travel_message = format!(
+        "Deslocamento sem corte: {:.*} {} (sem otimizar a ordem dos cortes: {:.*} {})",
+        travel_decimal_places,
+        travel_after / length_unit.get_millimeters_per_unit(),
+        length_unit.get_symbol(),
+        travel_decimal_places,
+        travel_before / length_unit.get_millimeters_per_unit(),
+        length_unit.get_symbol()
+    );
+
+    if one_file_per_sheet && sheet_cutting_lines_list.len() > 1 {
+        let mut message_list = Vec::<String>::new();
+        for (index, sheet_cutting_lines) in sheet_cutting_lines_list.into_iter().enumerate() {
+            message_list.push(
+                generate_gcode_file(
+                    vec![sheet_cutting_lines], 
+                    home_path.as_str(), 
+                    &format!("{}_folha_{}", file_name, index + 1),
+                    &setting).await.map_err(map_file_error_to_app_error)?
+            );
+        }
+        message_list.push(travel_message);
+        Ok(message_list.join("\n"))
+    } else {
+        let message = generate_gcode_file(
+            sheet_cutting_lines_list, 
+            home_path.as_str(), 
+            &file_name,
+            &setting).await.map_err(map_file_error_to_app_error)?;
+        Ok(format!("{}\n{}", message, travel_message))
+    }
 }
 
 fn map_file_error_to_app_error(error: FileError) -> AppError {
@@ -58,10 +150,10 @@ fn map_file_error_to_app_error(error: FileError) -> AppError {
     }
 }
 
-fn get_cut_disposition_output(state: State<'_, CutDispositionInputState>) -> Result<CutDispositionOutput, AppError>{
+fn get_cut_disposition_sheet_output_list(state: State<'_, CutDispositionInputState>) -> Result<Vec<CutDispositionOutput>, AppError>{
     let state_result = state.cut_disposition_state.lock();
     match state_result {
-        Ok(cut_disposition_state) => {
+        Ok(mut cut_disposition_state) => {
             if cut_disposition_state.spacing.is_some_and(|space| space <= 0) {
                 return Err(AppError::new(1, "Erro ao organizar disposição: espaçamento deve ser maior que zero ou inexistente"));
             }
@@ -75,15 +167,20 @@ fn get_cut_disposition_output(state: State<'_, CutDispositionInputState>) -> Res
                 return Err(AppError::new(1, "Erro ao organizar disposição: largura máxima deve ser maior que zero"));
             }
 
-            Ok(organize_disposition(&CutDispositionInput {
-                rectangles_list: cut_disposition_state.rectangles_list.clone(),
-                prohibited_area_list: cut_disposition_state.prohibited_area_list.clone(),
-                showcase: cut_disposition_state.showcase.clone(),
-                spacing: cut_disposition_state.spacing,
-                max_length: cut_disposition_state.max_length,
-                defined_length: cut_disposition_state.defined_length,
-                defined_width: cut_disposition_state.defined_width
-            }))
+            // the layout shown to the operator, with the adjustments, is the one cut
+            let sheet_list = match cut_disposition_state.get_layout_sheet_list() {
+                Some(sheet_list) => sheet_list,
+                None => {
+                    let cut_disposition_input = cut_disposition_state.get_cut_disposition_input();
+                    let sheet_list = organize_disposition_sheets(&cut_disposition_input, &NoOrganizeObserver);
+                    cut_disposition_state.set_layout(cut_disposition_input, sheet_list.clone());
+                    sheet_list
+                },
+            };
+
+            verify_disposition_sheet_list(&sheet_list, cut_disposition_state.spacing.unwrap_or(0))?;
+
+            Ok(sheet_list)
 
         },
         Err(_) => Err(AppError::new(1, "Erro ao organizar disposição")),
@@ -3,4 +3,5 @@ pub mod cut_disposition_controller;
 pub mod cutting_table_controller;
 pub mod fabric_controller;
 pub mod generate_g_code_controller;
-pub mod export_import_disposition_controller;
\ No newline at end of file
+pub mod export_import_disposition_controller;
+pub mod setting_controller;
\ No newline at end of file
@@ -0,0 +1,37 @@
+use tauri::State;
+
+use crate::{db::db_connection::DbConnection, models::{setting::{self, Setting, LengthUnit, MAX_G_CODE_DECIMAL_PLACES}, app_error::{AppError, DEFAULT_ERROR_CODE}}, services::length_unit_service::{get_length_unit, InLengthUnit, FromLengthUnit}};
+
+#[tauri::command]
+pub async fn get_setting(db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Setting>, AppError> {
+    let result = setting::get(&db_state.db).await;
+    match result {
+        Ok(setting) => Ok(InLengthUnit::new(setting.clone(), setting.length_unit)),
+        Err(_error) => Err(AppError::new(DEFAULT_ERROR_CODE, "Falha ao buscar Configurações")),
+    }
+}
+
+#[tauri::command]
+pub async fn update_setting(setting: FromLengthUnit<Setting>, db_state: State<'_, DbConnection>) -> Result<InLengthUnit<Setting>, AppError> {
+    // the overcut and the lead-in are typed in the unit chosen with them
+    let length_unit = setting.convert(LengthUnit::Millimeter)?.length_unit;
+    let setting = setting.convert(length_unit)?;
+
+    if setting.g_code_decimal_places > MAX_G_CODE_DECIMAL_PLACES {
+        return Err(AppError::new(DEFAULT_ERROR_CODE, format!("Casas decimais do G-code devem ser no máximo {}", MAX_G_CODE_DECIMAL_PLACES).as_str()));
+    }
+    if setting.overcut < 0 || setting.lead_in < 0 {
+        return Err(AppError::new(DEFAULT_ERROR_CODE, "Sobrecorte e entrada da lâmina não podem ser negativos"));
+    }
+    let result = setting::update(setting, &db_state.db).await;
+    match result {
+        Ok(setting) => Ok(InLengthUnit::new(setting.clone(), setting.length_unit)),
+        Err(_error) => Err(AppError::new(DEFAULT_ERROR_CODE, "Falha ao salvar Configurações")),
+    }
+}
+
+// the pages show the unit next to each length
+#[tauri::command]
+pub async fn get_length_unit_symbol(db_state: State<'_, DbConnection>) -> Result<String, AppError> {
+    Ok(get_length_unit(&db_state.db).await?.get_symbol().to_owned())
+}
@@ -8,9 +8,29 @@ const CREATE_FABRIC_SCHEMA_SQL: &str =
     name VARCHAR(250) NOT NULL,
     manufacturer VARCHAR(250),
     width INTEGER NOT NULL,
-    code VARCHAR(250)
+    code VARCHAR(250),
+    grain INTEGER NOT NULL DEFAULT 0,
+    repeat_width INTEGER,
+    repeat_length INTEGER,
+    repeat_offset_x INTEGER NOT NULL DEFAULT 0,
+    repeat_offset_y INTEGER NOT NULL DEFAULT 0,
+    margin_left INTEGER NOT NULL DEFAULT 0,
+    margin_right INTEGER NOT NULL DEFAULT 0,
+    margin_top INTEGER NOT NULL DEFAULT 0
 );";
 
+// columns missing on databases created by older versions
+const FABRIC_ADDED_COLUMN_LIST: [(&str, &str); 8] = [
+    ("grain", "ALTER TABLE fabric ADD COLUMN grain INTEGER NOT NULL DEFAULT 0;"),
+    ("repeat_width", "ALTER TABLE fabric ADD COLUMN repeat_width INTEGER;"),
+    ("repeat_length", "ALTER TABLE fabric ADD COLUMN repeat_length INTEGER;"),
+    ("repeat_offset_x", "ALTER TABLE fabric ADD COLUMN repeat_offset_x INTEGER NOT NULL DEFAULT 0;"),
+    ("repeat_offset_y", "ALTER TABLE fabric ADD COLUMN repeat_offset_y INTEGER NOT NULL DEFAULT 0;"),
+    ("margin_left", "ALTER TABLE fabric ADD COLUMN margin_left INTEGER NOT NULL DEFAULT 0;"),
+    ("margin_right", "ALTER TABLE fabric ADD COLUMN margin_right INTEGER NOT NULL DEFAULT 0;"),
+    ("margin_top", "ALTER TABLE fabric ADD COLUMN margin_top INTEGER NOT NULL DEFAULT 0;"),
+];
+
 const CREATE_CUTTING_TABLE_SCHEMA_SQL: &str = 
 "CREATE TABLE IF NOT EXISTS cutting_table (
     id INTEGER PRIMARY KEY NOT NULL, 
@@ -19,9 +39,29 @@ const CREATE_CUTTING_TABLE_SCHEMA_SQL: &str =
     length INTEGER NOT NULL
 );";
 
+// single row with the preferences of the workstation
+const CREATE_SETTING_SCHEMA_SQL: &str = 
+"CREATE TABLE IF NOT EXISTS setting (
+    id INTEGER PRIMARY KEY NOT NULL, 
+    length_unit INTEGER NOT NULL DEFAULT 0,
+    g_code_decimal_places INTEGER NOT NULL DEFAULT 2,
+    overcut INTEGER NOT NULL DEFAULT 0,
+    lead_in INTEGER NOT NULL DEFAULT 0
+);
+INSERT OR IGNORE INTO setting (id) Values(1);";
+
+// the overcut and the lead-in are in hundredths of a millimetre, like the geometry
+const SETTING_ADDED_COLUMN_LIST: [(&str, &str); 3] = [
+    ("g_code_decimal_places", "ALTER TABLE setting ADD COLUMN g_code_decimal_places INTEGER NOT NULL DEFAULT 2;"),
+    ("overcut", "ALTER TABLE setting ADD COLUMN overcut INTEGER NOT NULL DEFAULT 0;"),
+    ("lead_in", "ALTER TABLE setting ADD COLUMN lead_in INTEGER NOT NULL DEFAULT 0;"),
+];
+
 const DEV_POPULATE_FABRIC_SQL: &str = 
-"INSERT INTO fabric (name, manufacturer, width, code) Values('Tecido Normal', 'Fabricante 1', 4000, '23dfasdv4crgfd');
-INSERT INTO fabric (name, manufacturer, width, code) Values('Tecido Largo', 'Fabricante 1', 5000, 'sdasdasdasd876678');";
+"INSERT INTO fabric (name, manufacturer, width, code, grain) Values('Tecido Normal', 'Fabricante 1', 4000, '23dfasdv4crgfd', 0);
+INSERT INTO fabric (name, manufacturer, width, code, grain) Values('Tecido Largo', 'Fabricante 1', 5000, 'sdasdasdasd876678', 0);
+INSERT INTO fabric (name, manufacturer, width, code, grain) Values('Veludo', 'Fabricante 2', 4000, 'vel001', 1);
+INSERT INTO fabric (name, manufacturer, width, code, grain, repeat_width, repeat_length, repeat_offset_x, repeat_offset_y) Values('Listrado', 'Fabricante 2', 4000, 'lis001', 2, 200, NULL, 50, 0);";
 
 const DEV_POPULATE_CUTTING_TABLE_SQL: &str = 
 "INSERT INTO cutting_table (name, width, length) Values('Mesa 1', 4000, 3000);
@@ -49,6 +89,12 @@ async fn init_db() -> Pool<Sqlite> {
 
     execute_query(CREATE_FABRIC_SCHEMA_SQL, &db_pool).await;
 
+    for (column, add_column_sql) in FABRIC_ADDED_COLUMN_LIST {
+        if !column_exists("fabric", column, &db_pool).await {
+            execute_query(add_column_sql, &db_pool).await;
+        }
+    }
+
     if cfg!(dev) {
         execute_query(DEV_POPULATE_FABRIC_SQL, &db_pool).await;
     }
@@ -59,6 +105,14 @@ async fn init_db() -> Pool<Sqlite> {
         execute_query(DEV_POPULATE_CUTTING_TABLE_SQL, &db_pool).await;
     }
 
+    execute_query(CREATE_SETTING_SCHEMA_SQL, &db_pool).await;
+
+    for (column, add_column_sql) in SETTING_ADDED_COLUMN_LIST {
+        if !column_exists("setting", column, &db_pool).await {
+            execute_query(add_column_sql, &db_pool).await;
+        }
+    }
+
     db_pool
 }
 
@@ -90,6 +144,15 @@ async fn get_poll() -> Pool<Sqlite> {
     SqlitePool::connect(DB_URL).await.expect("Failed to connect to db")
 }
 
+async fn column_exists(table: &str, column: &str, pool: &Pool<Sqlite>) -> bool {
+    sqlx::query_scalar::<Sqlite, i32>("SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?")
+        .bind(table)
+        .bind(column)
+        .fetch_one(pool)
+        .await
+        .unwrap_or_else(|_| panic!("Failed to read columns of: {}", table)) > 0
+}
+
 async fn execute_query(query: &str, pool: &Pool<Sqlite>) {
     let result = sqlx::query(query
     )
@@ -2,7 +2,7 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod db;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, atomic::AtomicBool};
 
 use db::db_connection::DbConnection;
 use models::cut_disposition::CutDispositionState;
@@ -13,23 +13,27 @@ use controllers::{
         get_config_cut_disposition_input, get_cut_disposition_input,
         set_config_cut_disposition_input, create_piece, get_piece, 
         get_showcase, get_prohibited_area, edit_piece, delete_piece, 
-        delete_showcase, delete_prohibited_area
+        delete_showcase, delete_prohibited_area, get_pinned_piece, delete_pinned_piece
     },
     cutting_table_controller::{
         create_cutting_table, delete_cutting_table, get_all_cutting_table, get_cutting_table,
         update_cutting_table,
     },
     fabric_controller::{create_fabric, delete_fabric, get_all_fabric, get_fabric, update_fabric},
+    setting_controller::{get_setting, update_setting, get_length_unit_symbol},
 };
 
-use crate::controllers::{cut_disposition_controller::organize_cut_disposition, generate_g_code_controller::generate_g_code, export_import_disposition_controller::{export_disposition, import_disposition}};
+use crate::controllers::{cut_disposition_controller::{organize_cut_disposition, cancel_organize_cut_disposition, get_cut_disposition_layout, adjust_cut_disposition}, generate_g_code_controller::generate_g_code, export_import_disposition_controller::{export_disposition, import_disposition}};
 
 mod controllers;
 mod models;
 mod services;
+#[cfg(test)]
+mod test_support;
 
 pub struct CutDispositionInputState {
     pub cut_disposition_state: Arc<Mutex<CutDispositionState>>,
+    pub organize_cancelled: Arc<AtomicBool>,
 }
 
 #[tokio::main]
@@ -39,15 +43,17 @@ async fn main() -> Result<(), ()> {
         .manage(db_connection)
         .manage(CutDispositionInputState {
             cut_disposition_state: Arc::new(Mutex::new(CutDispositionState::new())),
+            organize_cancelled: Arc::new(AtomicBool::new(false)),
         })
         .invoke_handler(tauri::generate_handler![
             get_about,
             get_fabric, get_all_fabric, delete_fabric, create_fabric, update_fabric,
             get_cutting_table, get_all_cutting_table, delete_cutting_table, create_cutting_table, update_cutting_table,
+            get_setting, update_setting, get_length_unit_symbol,
             get_cut_disposition_input, set_config_cut_disposition_input, get_config_cut_disposition_input,
             create_piece, get_piece, get_showcase, get_prohibited_area, edit_piece, 
-            delete_piece, delete_showcase, delete_prohibited_area,
-            organize_cut_disposition, generate_g_code, export_disposition, import_disposition])
+            delete_piece, delete_showcase, delete_prohibited_area, get_pinned_piece, delete_pinned_piece,
+            organize_cut_disposition, cancel_organize_cut_disposition, get_cut_disposition_layout, adjust_cut_disposition, generate_g_code, export_disposition, import_disposition])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
     Ok(())
@@ -11,44 +11,90 @@
     Y(length)
  */
 
+use std::fmt::Display;
+
 use serde::{Serialize, Deserialize};
 
-use super::{app_error::AppError, cutting_lines::Line};
+use super::{app_error::AppError, cutting_lines::Line, fabric::FabricGrain, fixed_point::{Fixed, FixedArea, get_area, to_millimeters, millimeters, optional_millimeters, square_millimeters}};
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct CutDispositionState {
-    pub spacing: Option<i32>,
-    pub max_length: i32,
-    pub defined_length: Option<i32>,
-    pub defined_width: i32,
+    #[serde(default, with = "optional_millimeters")]
+    pub spacing: Option<Fixed>,
+    #[serde(with = "millimeters")]
+    pub max_length: Fixed,
+    #[serde(default, with = "optional_millimeters")]
+    pub defined_length: Option<Fixed>,
+    #[serde(with = "millimeters")]
+    pub defined_width: Fixed,
     pub rectangles_list: Vec<Rectangle>,
     pub prohibited_area_list: Vec<PositionedRectangle>,
-    pub showcase: Option<Rectangle>,
-    pub last_id: u32
+    pub showcase_list: Vec<Rectangle>,
+    pub last_id: u32,
+    #[serde(default)]
+    pub allow_rotation: bool,
+    #[serde(default)]
+    pub nesting_strategy: NestingStrategyType,
+    #[serde(default)]
+    pub multi_sheet: bool,
+    #[serde(default)]
+    pub optimization: Option<OptimizationConfig>,
+    #[serde(default)]
+    pub fabric_id: Option<i32>,
+    #[serde(default)]
+    pub fabric_grain: FabricGrain,
+    #[serde(default)]
+    pub pattern_repeat: Option<PatternRepeat>,
+    #[serde(default)]
+    pub edge_margins: EdgeMargins,
+    #[serde(default)]
+    pub pinned_piece_list: Vec<PositionedRectangle>,
+    #[serde(default)]
+    pub layout: Option<CutDispositionLayout>,
 }
 impl CutDispositionState {
     pub fn new() -> CutDispositionState {
         CutDispositionState {
             rectangles_list: Vec::<Rectangle>::new(),
             prohibited_area_list: Vec::<PositionedRectangle>::new(),
-            showcase: None,
+            showcase_list: Vec::<Rectangle>::new(),
             last_id: 0,
             spacing: None,
             max_length: 0,
             defined_length: None,
             defined_width: 0,
+            allow_rotation: false,
+            nesting_strategy: NestingStrategyType::BottomLeftVertex,
+            multi_sheet: false,
+            optimization: None,
+            fabric_id: None,
+            fabric_grain: FabricGrain::RotationAllowed,
+            pattern_repeat: None,
+            edge_margins: EdgeMargins::default(),
+            pinned_piece_list: Vec::<PositionedRectangle>::new(),
+            layout: None,
         }
     }
 
     pub fn reset(&mut self) {
         self.rectangles_list = Vec::<Rectangle>::new();
         self.prohibited_area_list = Vec::<PositionedRectangle>::new();
-        self.showcase = None;
+        self.showcase_list = Vec::<Rectangle>::new();
         self.last_id = 0;
         self.spacing = None;
         self.max_length = 0;
         self.defined_length = None;
         self.defined_width = 0;
+        self.allow_rotation = false;
+        self.nesting_strategy = NestingStrategyType::BottomLeftVertex;
+        self.multi_sheet = false;
+        self.optimization = None;
+        self.fabric_id = None;
+        self.fabric_grain = FabricGrain::RotationAllowed;
+        self.pattern_repeat = None;
+        self.edge_margins = EdgeMargins::default();
+        self.pinned_piece_list = Vec::<PositionedRectangle>::new();
+        self.layout = None;
     }
 
     fn generate_next_id(&mut self) -> u32 {
@@ -73,6 +119,11 @@ impl CutDispositionState {
             id: self.generate_next_id(),
             width: piece.width,
             length: piece.length,
+            allow_rotation: piece.allow_rotation,
+            quantity: piece.quantity,
+            max_quantity: piece.max_quantity,
+            match_pattern: piece.match_pattern,
+            margin: piece.margin,
         };
         self.rectangles_list.push(piece_with_new_id.clone());
         piece_with_new_id
@@ -97,8 +148,16 @@ impl CutDispositionState {
         Ok(piece)
     }
 
-    pub fn get_showcase(&self) -> Option<Rectangle> {
-        self.showcase.clone()
+    pub fn get_showcase_list(&self) -> Vec<Rectangle> {
+        self.showcase_list.clone()
+    }
+
+    pub fn get_showcase_by_id(&self, id: u32) -> Result<Rectangle, ()> {
+        let showcase_option = self.showcase_list.iter().find(|item| item.id == id);
+        match showcase_option {
+            Some(showcase) => Ok(showcase.clone()),
+            None => Err(()),
+        }
     }
 
     pub fn add_showcase(&mut self, showcase: &Rectangle) -> Rectangle{
@@ -106,28 +165,24 @@ impl CutDispositionState {
             id: self.generate_next_id(),
             width: showcase.width,
             length: showcase.length,
+            allow_rotation: showcase.allow_rotation,
+            quantity: showcase.quantity,
+            max_quantity: showcase.max_quantity,
+            match_pattern: showcase.match_pattern,
+            margin: showcase.margin,
         };
-        self.showcase = Some(showcase_with_new_id.clone());
+        self.showcase_list.push(showcase_with_new_id.clone());
         showcase_with_new_id
     }
 
     pub fn remove_showcase(&mut self, id: u32) -> Result<Rectangle, AppError> {
-        if let Some(showcase) = self.showcase.clone()  {
-            if showcase.id != id {
-                return Err(
-                    AppError::new(
-                        1, 
-                        format!("Falha ao encontrar mostruário de id = {}", id).as_str()
-                    )
-                );
-            }
-            self.showcase = None;
-            Ok(showcase)
+        if let Some(index) = self.showcase_list.iter().position(|item| item.id == id) {
+            Ok(self.showcase_list.remove(index))
         } else {
             Err(
                 AppError::new(
                     1, 
-                    "Não existe mostruário a ser deletado"
+                    format!("Falha ao encontrar mostruário de id = {}", id).as_str()
                 )
             )
         }
@@ -135,7 +190,7 @@ impl CutDispositionState {
 
     pub fn edit_showcase(&mut self, showcase: Rectangle) -> Result<Rectangle, AppError> {
         self.remove_showcase(showcase.id)?;
-        self.showcase = Some(showcase.clone());
+        self.showcase_list.push(showcase.clone());
         Ok(showcase)
     }
 
@@ -157,6 +212,8 @@ impl CutDispositionState {
             width: prohibited_area.width,
             length: prohibited_area.length,
             top_left_vertex: prohibited_area.top_left_vertex.clone(),
+            rotated: false,
+            margin: None,
         };
         self.prohibited_area_list.push(prohibited_area_with_new_id.clone());
         prohibited_area_with_new_id
@@ -181,45 +238,259 @@ impl CutDispositionState {
         Ok(prohibited_area)
     }
 
+    pub fn get_pinned_piece_list(&self) -> Vec<PositionedRectangle> {
+        self.pinned_piece_list.clone()
+    }
+
+    pub fn get_pinned_piece_by_id(&self, id: u32) -> Result<PositionedRectangle, ()> {
+        let piece_option = self.pinned_piece_list.iter().find(|item| item.id == id);
+        match piece_option {
+            Some(piece) => Ok(piece.clone()),
+            None => Err(()),
+        }
+    }
+
+    pub fn add_pinned_piece(&mut self, pinned_piece: &PositionedRectangle) -> PositionedRectangle {
+        let pinned_piece_with_new_id = PositionedRectangle {
+            id: self.generate_next_id(),
+            width: pinned_piece.width,
+            length: pinned_piece.length,
+            top_left_vertex: pinned_piece.top_left_vertex.clone(),
+            rotated: false,
+            margin: pinned_piece.margin,
+        };
+        self.pinned_piece_list.push(pinned_piece_with_new_id.clone());
+        pinned_piece_with_new_id
+    }
+
+    pub fn remove_pinned_piece(&mut self, id: u32) -> Result<PositionedRectangle, AppError> {
+        if let Some(index) = self.pinned_piece_list.iter().position(|item| item.id == id) {
+            Ok(self.pinned_piece_list.remove(index))
+        } else {
+            Err(
+                AppError::new(
+                    1, 
+                    format!("Falha ao encontrar peça fixa de id = {}", id).as_str()
+                )
+            )
+        }
+    }
+
+    pub fn edit_pinned_piece(&mut self, pinned_piece: PositionedRectangle) -> Result<PositionedRectangle, AppError> {
+        self.remove_pinned_piece(pinned_piece.id)?;
+        self.pinned_piece_list.push(pinned_piece.clone());
+        Ok(pinned_piece)
+    }
+
     pub fn get_cut_disposition_input(&self) -> CutDispositionInput {
         CutDispositionInput {
             rectangles_list: self.rectangles_list.clone(),
             prohibited_area_list: self.prohibited_area_list.clone(),
-            showcase: self.showcase.clone(),
+            showcase_list: self.showcase_list.clone(),
             spacing: self.spacing,
             max_length: self.max_length,
             defined_length: self.defined_length,
             defined_width: self.defined_width,
+            allow_rotation: self.allow_rotation,
+            nesting_strategy: self.nesting_strategy.clone(),
+            multi_sheet: self.multi_sheet,
+            optimization: self.optimization.clone(),
+            fabric_grain: self.fabric_grain,
+            pattern_repeat: self.pattern_repeat.clone(),
+            edge_margins: self.edge_margins.clone(),
+            pinned_piece_list: self.pinned_piece_list.clone(),
         }
     }
 
+    pub fn set_layout(&mut self, cut_disposition_input: CutDispositionInput, sheet_list: Vec<CutDispositionOutput>) {
+        self.layout = Some(CutDispositionLayout { cut_disposition_input, sheet_list });
+    }
+
+    // a layout organized for other pieces or config is discarded
+    pub fn get_layout_sheet_list(&self) -> Option<Vec<CutDispositionOutput>> {
+        self.layout
+            .as_ref()
+            .filter(|layout| layout.cut_disposition_input == self.get_cut_disposition_input())
+            .map(|layout| layout.sheet_list.clone())
+    }
+
+    pub fn get_layout_sheet(&self, sheet_index: usize) -> Result<CutDispositionOutput, AppError> {
+        self.get_layout_sheet_list()
+            .and_then(|sheet_list| sheet_list.get(sheet_index).cloned())
+            .ok_or_else(|| AppError::new(1, format!("Falha ao encontrar folha {} da disposição", sheet_index + 1).as_str()))
+    }
+
+    pub fn set_layout_sheet(&mut self, sheet_index: usize, sheet: CutDispositionOutput) -> Result<(), AppError> {
+        let mut sheet_list = self.get_layout_sheet_list().unwrap_or_default();
+        match sheet_list.get_mut(sheet_index) {
+            Some(layout_sheet) => *layout_sheet = sheet,
+            None => return Err(AppError::new(1, format!("Falha ao encontrar folha {} da disposição", sheet_index + 1).as_str())),
+        }
+        self.set_layout(self.get_cut_disposition_input(), sheet_list);
+        Ok(())
+    }
+
     pub fn get_config_cut_disposition_input(&self) -> ConfigCutDispositionInput {
         ConfigCutDispositionInput {
             spacing: self.spacing,
             max_length: self.max_length,
             defined_length: self.defined_length,
             defined_width: self.defined_width,
+            allow_rotation: self.allow_rotation,
+            nesting_strategy: self.nesting_strategy.clone(),
+            multi_sheet: self.multi_sheet,
+            optimization: self.optimization.clone(),
+            fabric_id: self.fabric_id,
+            edge_margins: self.edge_margins.clone(),
         }
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct ConfigCutDispositionInput {
-    pub spacing: Option<i32>,
-    pub max_length: i32,
-    pub defined_length: Option<i32>,
-    pub defined_width: i32,
+    #[serde(default, with = "optional_millimeters")]
+    pub spacing: Option<Fixed>,
+    #[serde(with = "millimeters")]
+    pub max_length: Fixed,
+    #[serde(default, with = "optional_millimeters")]
+    pub defined_length: Option<Fixed>,
+    #[serde(with = "millimeters")]
+    pub defined_width: Fixed,
+    pub allow_rotation: bool,
+    #[serde(default)]
+    pub nesting_strategy: NestingStrategyType,
+    // pieces that do not fit are nested on new sheets of the same width
+    #[serde(default)]
+    pub multi_sheet: bool,
+    // None nests the pieces only in the order of the strategy
+    #[serde(default)]
+    pub optimization: Option<OptimizationConfig>,
+    // the grain of the fabric limits the rotation of every piece
+    #[serde(default)]
+    pub fabric_id: Option<i32>,
+    #[serde(default)]
+    pub edge_margins: EdgeMargins,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct CutDispositionInput {
     pub rectangles_list: Vec<Rectangle>,
     pub prohibited_area_list: Vec<PositionedRectangle>,
-    pub showcase: Option<Rectangle>,
-    pub spacing: Option<i32>,
-    pub max_length: i32,
-    pub defined_length: Option<i32>,
-    pub defined_width: i32,
+    pub showcase_list: Vec<Rectangle>,
+    #[serde(default, with = "optional_millimeters")]
+    pub spacing: Option<Fixed>,
+    #[serde(with = "millimeters")]
+    pub max_length: Fixed,
+    #[serde(default, with = "optional_millimeters")]
+    pub defined_length: Option<Fixed>,
+    #[serde(with = "millimeters")]
+    pub defined_width: Fixed,
+    pub allow_rotation: bool,
+    #[serde(default)]
+    pub nesting_strategy: NestingStrategyType,
+    #[serde(default)]
+    pub multi_sheet: bool,
+    #[serde(default)]
+    pub optimization: Option<OptimizationConfig>,
+    #[serde(default)]
+    pub fabric_grain: FabricGrain,
+    #[serde(default)]
+    pub pattern_repeat: Option<PatternRepeat>,
+    #[serde(default)]
+    pub edge_margins: EdgeMargins,
+    // pieces cut at the position chosen by the operator, the other pieces are nested around them
+    #[serde(default)]
+    pub pinned_piece_list: Vec<PositionedRectangle>,
+}
+
+// Unusable edges of the fabric, the selvage on both sides and the uneven start of the fabric
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct EdgeMargins {
+    #[serde(with = "millimeters")]
+    pub left: Fixed,
+    #[serde(with = "millimeters")]
+    pub right: Fixed,
+    #[serde(with = "millimeters")]
+    pub top: Fixed,
+}
+impl EdgeMargins {
+    pub fn is_empty(&self) -> bool {
+        self.left == 0 && self.right == 0 && self.top == 0
+    }
+
+    pub fn is_valid(&self, defined_width: Fixed, max_length: Fixed) -> bool {
+        self.left >= 0 && self.right >= 0 && self.top >= 0 &&
+        self.left + self.right < defined_width && self.top < max_length
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OptimizationConfig {
+    // milliseconds spent trying other orders of the pieces, each sheet has its own limit
+    pub time_limit: u32,
+    // the same seed tries the same orders
+    pub seed: u32,
+    // orders tried on each sheet, rounded up to whole batches; when set it replaces the time limit,
+    // so the same seed gives the same layout whatever the load of the machine
+    #[serde(default)]
+    pub max_iterations: Option<u32>,
+}
+
+// Print of the fabric, the pieces that match the pattern start on a repeat
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PatternRepeat {
+    // None when the print does not repeat along the axis
+    #[serde(default, with = "optional_millimeters")]
+    pub repeat_width: Option<Fixed>,
+    #[serde(default, with = "optional_millimeters")]
+    pub repeat_length: Option<Fixed>,
+    // position of the first repeat on the fabric
+    #[serde(with = "millimeters")]
+    pub offset_x: Fixed,
+    #[serde(with = "millimeters")]
+    pub offset_y: Fixed,
+}
+impl PatternRepeat {
+    // the first repeat at the right and below the vertex
+    pub fn snap_vertex(&self, vertex: &Vertex) -> Vertex {
+        Vertex {
+            pos_x: snap_to_repeat(vertex.pos_x, self.repeat_width, self.offset_x),
+            pos_y: snap_to_repeat(vertex.pos_y, self.repeat_length, self.offset_y),
+        }
+    }
+}
+
+fn snap_to_repeat(position: Fixed, repeat: Option<Fixed>, offset: Fixed) -> Fixed {
+    match repeat {
+        Some(repeat) if repeat > 0 => {
+            let distance_from_repeat = (position - offset).rem_euclid(repeat);
+            if distance_from_repeat == 0 {
+                position
+            } else {
+                position + repeat - distance_from_repeat
+            }
+        },
+        _ => position,
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub enum NestingStrategyType {
+    // places each piece at the free vertex closest to the top and left
+    #[default]
+    BottomLeftVertex,
+    // places each piece at the free rectangle that leaves the shortest side left over
+    MaxRects,
+    // same as MaxRects, but every free rectangle comes from a cut from edge to edge of its panel
+    Guillotine,
+}
+
+// Sent to the window while a disposition is being organized
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OrganizeProgress {
+    pub sheet: u32,
+    pub iteration: u32,
+    pub best_usage: f64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -228,39 +499,172 @@ pub struct CutDispositionOutput {
     pub showcase_rectangles_located_list: Vec<PositionedRectangle>,
     pub unused_rectangles_list: Vec<Rectangle>,
     pub prohibited_area_list: Vec<PositionedRectangle>,
-    pub length_used: i32,
-    pub total_area: i32,
-    pub used_area: i32,
+    #[serde(with = "millimeters")]
+    pub length_used: Fixed,
+    #[serde(with = "square_millimeters")]
+    pub total_area: FixedArea,
+    #[serde(with = "square_millimeters")]
+    pub used_area: FixedArea,
     pub usage: f64,
-    pub max_length: i32,
-    pub defined_length: Option<i32>,
-    pub defined_width: i32,
+    pub piece_count_list: Vec<PieceCount>,
+    pub showcase_count_list: Vec<ShowcaseCount>,
+    pub is_guillotine: bool,
+    pub edge_margins: EdgeMargins,
+    #[serde(with = "millimeters")]
+    pub max_length: Fixed,
+    #[serde(default, with = "optional_millimeters")]
+    pub defined_length: Option<Fixed>,
+    #[serde(with = "millimeters")]
+    pub defined_width: Fixed,
+    #[serde(default)]
+    pub unplaced_piece_list: Vec<UnplacedPiece>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum UnplacedReason {
+    WiderThanFabric,
+    LongerThanFabric,
+    BlockedByProhibitedAreas,
+    NoRoomLeft,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct NearMiss {
+    pub positioned_rectangle: PositionedRectangle,
+    // area of the piece over the prohibited areas and over the other pieces with their spacing
+    #[serde(with = "square_millimeters")]
+    pub overlap_area: FixedArea,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UnplacedPiece {
+    pub id: u32,
+    pub quantity: u32,
+    pub reason: UnplacedReason,
+    // None when the piece does not fit even on the empty fabric
+    pub near_miss: Option<NearMiss>,
+}
+
+// The sheets organized for an input, kept with the adjustments made by the operator until the input changes
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CutDispositionLayout {
+    pub cut_disposition_input: CutDispositionInput,
+    pub sheet_list: Vec<CutDispositionOutput>,
+}
+
+// piece_index is the position of the piece in the positioned_rectangles_list of the sheet
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum LayoutAdjustment {
+    Move { piece_index: usize, top_left_vertex: Vertex },
+    Swap { piece_index: usize, other_piece_index: usize },
+    Rotate { piece_index: usize },
+}
+
+// Something on a sheet that the machine must not cut, the ids are of pieces, showcases and prohibited areas
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum DispositionViolation {
+    Overlap { id: u32, other_id: u32 },
+    SpacingBreach { id: u32, other_id: u32, #[serde(with = "millimeters")] spacing: Fixed },
+    OutOfBounds { id: u32 },
+    ProhibitedAreaIntersection { id: u32, prohibited_area_id: u32 },
+    DuplicatedId { id: u32 },
+}
+impl Display for DispositionViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DispositionViolation::Overlap { id, other_id } => write!(f, "Peça {} sobreposta à peça {}", id, other_id),
+            DispositionViolation::SpacingBreach { id, other_id, spacing } => write!(f, "Peça {} a menos de {}mm da peça {}", id, to_millimeters(*spacing), other_id),
+            DispositionViolation::OutOfBounds { id } => write!(f, "Peça {} fora da área útil do tecido", id),
+            DispositionViolation::ProhibitedAreaIntersection { id, prohibited_area_id } => write!(f, "Peça {} sobre a área proibida {}", id, prohibited_area_id),
+            DispositionViolation::DuplicatedId { id } => write!(f, "Id {} repetido na disposição", id),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AdjustedCutDisposition {
+    pub cut_disposition_output: CutDispositionOutput,
+    // the adjustment is kept even with violations, so the operator can solve them with the next ones
+    pub violation_list: Vec<DispositionViolation>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Rectangle {
     pub id: u32,
-    pub width: i32,
-    pub length: i32,
+    #[serde(with = "millimeters")]
+    pub width: Fixed,
+    #[serde(with = "millimeters")]
+    pub length: Fixed,
+    // None follows the allow_rotation of the disposition
+    #[serde(default)]
+    pub allow_rotation: Option<bool>,
+    #[serde(default = "default_quantity")]
+    pub quantity: u32,
+    // only used by showcases, None fills every hole left
+    #[serde(default)]
+    pub max_quantity: Option<u32>,
+    // the piece must start on a repeat of the print, so the print matches between pieces
+    #[serde(default)]
+    pub match_pattern: bool,
+    // gap kept around the piece instead of the spacing of the disposition, Some(0) lets it butt up
+    #[serde(default, with = "optional_millimeters")]
+    pub margin: Option<Fixed>,
 }
 impl Rectangle {
     pub fn equals(&self, rectangle: &Rectangle) -> bool {
         self.id == rectangle.id &&
         self.width == rectangle.width &&
-        self.length == rectangle.length
+        self.length == rectangle.length &&
+        self.quantity == rectangle.quantity
     }
 
     pub fn is_valid(&self) -> bool {
-        self.width > 0 && self.length > 0
+        self.width > 0 && self.length > 0 && self.quantity > 0 && self.margin.unwrap_or(0) >= 0
+    }
+
+    pub fn get_spacing(&self, spacing: Fixed) -> Fixed {
+        self.margin.unwrap_or(spacing)
     }
+
+    // a rotated piece would not follow the print, even starting on a repeat
+    pub fn can_rotate(&self, allow_rotation_default: bool) -> bool {
+        !self.match_pattern && self.allow_rotation.unwrap_or(allow_rotation_default) && self.width != self.length
+    }
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PieceCount {
+    pub id: u32,
+    pub quantity: u32,
+    pub placed_quantity: u32,
+    pub unplaced_quantity: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ShowcaseCount {
+    pub id: u32,
+    pub placed_quantity: u32,
+    #[serde(with = "square_millimeters")]
+    pub used_area: FixedArea,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct PositionedRectangle {
     pub id: u32,
-    pub width: i32,
-    pub length: i32,
+    #[serde(with = "millimeters")]
+    pub width: Fixed,
+    #[serde(with = "millimeters")]
+    pub length: Fixed,
     pub top_left_vertex: Vertex,
+    // width and length are already swapped when the rectangle was rotated 90°
+    #[serde(default)]
+    pub rotated: bool,
+    #[serde(default, with = "optional_millimeters")]
+    pub margin: Option<Fixed>,
 }
 
 impl PositionedRectangle {
@@ -270,6 +674,19 @@ impl PositionedRectangle {
             width: rectangle.width,
             length: rectangle.length,
             top_left_vertex: top_left_vertex.clone(),
+            rotated: false,
+            margin: rectangle.margin,
+        }
+    }
+
+    pub fn new_from_rotated_rectangle_and_vertex(rectangle: &Rectangle, top_left_vertex: &Vertex) -> PositionedRectangle {
+        PositionedRectangle {
+            id: rectangle.id,
+            width: rectangle.length,
+            length: rectangle.width,
+            top_left_vertex: top_left_vertex.clone(),
+            rotated: true,
+            margin: rectangle.margin,
         }
     }
 
@@ -341,10 +758,15 @@ impl PositionedRectangle {
         self.id == positioned_rectangle.id &&
         self.width == positioned_rectangle.width &&
         self.length == positioned_rectangle.length &&
-        self.top_left_vertex == positioned_rectangle.top_left_vertex
+        self.top_left_vertex == positioned_rectangle.top_left_vertex &&
+        self.rotated == positioned_rectangle.rotated
     }
-    pub fn get_area(&self) -> i32 {
-        self.width * self.length
+    pub fn get_area(&self) -> FixedArea {
+        get_area(self.width, self.length)
+    }
+
+    pub fn get_spacing(&self, spacing: Fixed) -> Fixed {
+        self.margin.unwrap_or(spacing)
     }
 
     pub fn is_valid(&self) -> bool {
@@ -363,8 +785,10 @@ pub struct PositionedRectangleVertices {
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Vertex {
-    pub pos_x: i32,
-    pub pos_y: i32,
+    #[serde(with = "millimeters")]
+    pub pos_x: Fixed,
+    #[serde(with = "millimeters")]
+    pub pos_y: Fixed,
 }
 
 impl Vertex {
@@ -410,6 +834,8 @@ mod tests {
             width: 5, 
             length: 7, 
             top_left_vertex: top_left_vertex.clone(),
+            rotated: false,
+            margin: None,
         };
 
         // expect
@@ -433,6 +859,20 @@ mod tests {
         assert_eq!(vertices, rectangle.get_vertices());
     }
 
+    #[test]
+    fn snap_vertex_test() {
+        let pattern_repeat = PatternRepeat {
+            repeat_width: Some(30),
+            repeat_length: None,
+            offset_x: 5,
+            offset_y: 0,
+        };
+
+        assert_eq!(pattern_repeat.snap_vertex(&Vertex { pos_x: 0, pos_y: 12 }), Vertex { pos_x: 5, pos_y: 12 });
+        assert_eq!(pattern_repeat.snap_vertex(&Vertex { pos_x: 35, pos_y: 0 }), Vertex { pos_x: 35, pos_y: 0 });
+        assert_eq!(pattern_repeat.snap_vertex(&Vertex { pos_x: 36, pos_y: 7 }), Vertex { pos_x: 65, pos_y: 7 });
+    }
+
     #[test]
     fn get_top_line_test() {
         // input
@@ -447,6 +887,8 @@ mod tests {
             width: 5, 
             length: 7, 
             top_left_vertex: top_left_vertex.clone(),
+            rotated: false,
+            margin: None,
         };
 
         // expect
@@ -479,6 +921,8 @@ mod tests {
             width: 5, 
             length: 7, 
             top_left_vertex: top_left_vertex.clone(),
+            rotated: false,
+            margin: None,
         };
 
         // expect
@@ -511,6 +955,8 @@ mod tests {
             width: 5, 
             length: 7, 
             top_left_vertex: top_left_vertex.clone(),
+            rotated: false,
+            margin: None,
         };
 
         // expect
@@ -543,6 +989,8 @@ mod tests {
             width: 5, 
             length: 7, 
             top_left_vertex: top_left_vertex.clone(),
+            rotated: false,
+            margin: None,
         };
 
         // expect
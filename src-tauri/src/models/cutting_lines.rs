@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 
 use serde::{Deserialize, Serialize};
 
-use super::cut_disposition::Vertex;
+use super::{cut_disposition::{PositionedRectangle, Vertex}, fixed_point::Fixed};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Line {
@@ -47,6 +47,12 @@ impl Line {
         false
     }
 
+    pub fn contains(&self, line: &Line) -> bool {
+        self.is_same_level(line) &&
+        self.start.pos_x <= line.start.pos_x && self.start.pos_y <= line.start.pos_y &&
+        self.end.pos_x >= line.end.pos_x && self.end.pos_y >= line.end.pos_y
+    }
+
     pub fn cross_perpend(&self, line: &Line) -> bool {
         (
                 self.start.pos_y < line.start.pos_y && self.end.pos_y > line.start.pos_y && line.start.pos_x < self.start.pos_x && line.end.pos_x > self.start.pos_x
@@ -80,6 +86,31 @@ impl Line {
             end
         }
     }
+
+    // the same cut made from the other end
+    pub fn get_reversed(&self) -> Line {
+        Line {
+            start: self.end.clone(),
+            end: self.start.clone()
+        }
+    }
+}
+
+// Order in which the lines of a sheet are cut
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CutStrategy {
+    #[default]
+    VerticalFirst,
+    HorizontalFirst,
+    // the sheet is cut in strips from the top, each strip fully before the next one
+    ByBand,
+}
+
+// lines of the same direction cut one after the other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CutPhase {
+    pub is_vertical: bool,
+    pub line_list: Vec<Line>
 }
 
 pub struct CuttingLines {
@@ -87,6 +118,16 @@ pub struct CuttingLines {
     pub horizontal_lines: Vec<Line>
 }
 
+pub struct SheetCuttingLines {
+    pub cut_phase_list: Vec<CutPhase>,
+    // the fabric is pulled before cutting the sheet, in hundredths of a millimetre
+    pub textile_length_to_pull: Option<u32>,
+    // the pieces and the prohibited areas, the overcut and the lead-in stop at them and at the sides of the sheet
+    pub obstacle_list: Vec<PositionedRectangle>,
+    pub sheet_width: Fixed,
+    pub sheet_length: Fixed
+}
+
 #[cfg(test)]
 mod tests {
     use crate::models::{cut_disposition::Vertex, cutting_lines::Line};
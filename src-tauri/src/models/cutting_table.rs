@@ -1,11 +1,15 @@
 use serde::{Deserialize, Serialize};
 use sqlx::{Error, Pool, Sqlite};
 
+use super::fixed_point::whole_millimeters;
+
 #[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow)]
 pub struct CuttingTable {
     pub id: i32,
     pub name: String,
+    #[serde(with = "whole_millimeters")]
     pub width: i32,
+    #[serde(with = "whole_millimeters")]
     pub length: i32,
 }
 
@@ -61,6 +65,8 @@ pub async fn update(
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CuttingTableCreate {
     pub name: String,
+    #[serde(with = "whole_millimeters")]
     pub width: i32,
+    #[serde(with = "whole_millimeters")]
     pub length: i32,
 }
@@ -1,13 +1,48 @@
 use serde::{Serialize, Deserialize};
 use sqlx::{Error, Sqlite, Pool};
 
+use super::{cut_disposition::PatternRepeat, fixed_point::{from_whole_millimeters, whole_millimeters, optional_whole_millimeters}};
+
 #[derive(Serialize, Deserialize, Debug, Clone, sqlx::FromRow)]
 pub struct Fabric {
     pub id: i32,
     pub name: String,
     pub manufacturer: String,
+    #[serde(with = "whole_millimeters")]
     pub width: i32,
     pub code: String,
+    pub grain: FabricGrain,
+    // distance between the repeats of the print, None for plain fabrics
+    #[serde(default, with = "optional_whole_millimeters")]
+    pub repeat_width: Option<i32>,
+    #[serde(default, with = "optional_whole_millimeters")]
+    pub repeat_length: Option<i32>,
+    #[serde(with = "whole_millimeters")]
+    pub repeat_offset_x: i32,
+    #[serde(with = "whole_millimeters")]
+    pub repeat_offset_y: i32,
+    // unusable selvage on the sides and start of the fabric
+    #[serde(with = "whole_millimeters")]
+    pub margin_left: i32,
+    #[serde(with = "whole_millimeters")]
+    pub margin_right: i32,
+    #[serde(with = "whole_millimeters")]
+    pub margin_top: i32,
+}
+
+impl Fabric {
+    pub fn get_pattern_repeat(&self) -> Option<PatternRepeat> {
+        if self.repeat_width.is_none() && self.repeat_length.is_none() {
+            return None;
+        }
+        // the fabric is registered in whole millimetres
+        Some(PatternRepeat {
+            repeat_width: self.repeat_width.map(from_whole_millimeters),
+            repeat_length: self.repeat_length.map(from_whole_millimeters),
+            offset_x: from_whole_millimeters(self.repeat_offset_x),
+            offset_y: from_whole_millimeters(self.repeat_offset_y),
+        })
+    }
 }
 
 pub async fn get(id: i32, poll: &Pool<Sqlite>) -> Result<Fabric, Error> {
@@ -31,21 +66,37 @@ pub async fn delete(id: i32, poll: &Pool<Sqlite>) -> Result<Fabric, Error> {
 }
 
 pub async fn create(fabric: FabricCreate, poll: &Pool<Sqlite>) -> Result<Fabric, Error> {
-    sqlx::query_as::<Sqlite, Fabric>("INSERT INTO fabric (name, manufacturer, width, code) Values(?, ?, ?, ?) RETURNING *;")
+    sqlx::query_as::<Sqlite, Fabric>("INSERT INTO fabric (name, manufacturer, width, code, grain, repeat_width, repeat_length, repeat_offset_x, repeat_offset_y, margin_left, margin_right, margin_top) Values(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING *;")
         .bind(fabric.name)
         .bind(fabric.manufacturer)
         .bind(fabric.width)
         .bind(fabric.code)
+        .bind(fabric.grain)
+        .bind(fabric.repeat_width)
+        .bind(fabric.repeat_length)
+        .bind(fabric.repeat_offset_x)
+        .bind(fabric.repeat_offset_y)
+        .bind(fabric.margin_left)
+        .bind(fabric.margin_right)
+        .bind(fabric.margin_top)
         .fetch_one(poll)
         .await
 }
 
 pub async fn update(fabric: Fabric, poll: &Pool<Sqlite>) -> Result<Fabric, Error> {
-    sqlx::query_as::<Sqlite, Fabric>("UPDATE fabric SET name = ?, manufacturer = ?, width = ?, code = ? WHERE id = ? RETURNING *;")
+    sqlx::query_as::<Sqlite, Fabric>("UPDATE fabric SET name = ?, manufacturer = ?, width = ?, code = ?, grain = ?, repeat_width = ?, repeat_length = ?, repeat_offset_x = ?, repeat_offset_y = ?, margin_left = ?, margin_right = ?, margin_top = ? WHERE id = ? RETURNING *;")
         .bind(fabric.name)
         .bind(fabric.manufacturer)
         .bind(fabric.width)
         .bind(fabric.code)
+        .bind(fabric.grain)
+        .bind(fabric.repeat_width)
+        .bind(fabric.repeat_length)
+        .bind(fabric.repeat_offset_x)
+        .bind(fabric.repeat_offset_y)
+        .bind(fabric.margin_left)
+        .bind(fabric.margin_right)
+        .bind(fabric.margin_top)
         .bind(fabric.id)
         .fetch_one(poll)
         .await
@@ -55,6 +106,38 @@ pub async fn update(fabric: Fabric, poll: &Pool<Sqlite>) -> Result<Fabric, Error
 pub struct FabricCreate {
     pub name: String,
     pub manufacturer: String,
+    #[serde(with = "whole_millimeters")]
     pub width: i32,
     pub code: String,
-}
\ No newline at end of file
+    pub grain: FabricGrain,
+    #[serde(default, with = "optional_whole_millimeters")]
+    pub repeat_width: Option<i32>,
+    #[serde(default, with = "optional_whole_millimeters")]
+    pub repeat_length: Option<i32>,
+    #[serde(with = "whole_millimeters")]
+    pub repeat_offset_x: i32,
+    #[serde(with = "whole_millimeters")]
+    pub repeat_offset_y: i32,
+    #[serde(with = "whole_millimeters")]
+    pub margin_left: i32,
+    #[serde(with = "whole_millimeters")]
+    pub margin_right: i32,
+    #[serde(with = "whole_millimeters")]
+    pub margin_top: i32,
+}
+
+// How the pieces may be turned on the fabric, velvet and stripes must keep the direction of the fabric
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, sqlx::Type)]
+#[repr(i32)]
+pub enum FabricGrain {
+    #[default]
+    RotationAllowed = 0,
+    RotationForbidden = 1,
+    HalfTurnOnly = 2,
+}
+impl FabricGrain {
+    // a rectangle turned 180° covers the same place, so the half turn only grain nests like the rotation forbidden one
+    pub fn allows_quarter_turn(&self) -> bool {
+        *self == FabricGrain::RotationAllowed
+    }
+}
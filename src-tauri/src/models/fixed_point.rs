@@ -0,0 +1,197 @@
+/*
+    The geometry is kept in hundredths of a millimetre, as integers, so the comparisons stay exact
+    and half a millimetre or the fractions of an inch are not rounded.
+    Outside of the backend the values are decimal millimetres (exported files),
+    or decimals in the unit of the setting while the interface reads or writes them (with_length_unit).
+ */
+
+use std::cell::Cell;
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+use super::setting::LengthUnit;
+
+pub const FIXED_POINT_SCALE: i32 = 100;
+
+// hundredths of a millimetre
+pub type Fixed = i32;
+
+// squared hundredths of a millimetre, the product of two lengths does not fit in 32 bits
+pub type FixedArea = i64;
+
+pub fn from_millimeters(millimeters: f64) -> Fixed {
+    (millimeters * FIXED_POINT_SCALE as f64).round() as Fixed
+}
+
+pub fn from_whole_millimeters(millimeters: i32) -> Fixed {
+    millimeters * FIXED_POINT_SCALE
+}
+
+pub fn to_millimeters(value: Fixed) -> f64 {
+    value as f64 / FIXED_POINT_SCALE as f64
+}
+
+pub fn to_square_millimeters(area: FixedArea) -> f64 {
+    area as f64 / (FIXED_POINT_SCALE as f64 * FIXED_POINT_SCALE as f64)
+}
+
+pub fn get_area(width: Fixed, length: Fixed) -> FixedArea {
+    width as FixedArea * length as FixedArea
+}
+
+thread_local! {
+    static LENGTH_UNIT: Cell<LengthUnit> = const { Cell::new(LengthUnit::Millimeter) };
+}
+
+// puts the millimetre back even when the serialization panics, the thread goes on serving other commands
+struct LengthUnitGuard {
+    previous_length_unit: LengthUnit,
+}
+impl Drop for LengthUnitGuard {
+    fn drop(&mut self) {
+        LENGTH_UNIT.with(|length_unit| length_unit.set(self.previous_length_unit));
+    }
+}
+
+// The values serialized or deserialized inside the closure are in the unit given, instead of millimetres
+pub fn with_length_unit<R>(length_unit: LengthUnit, f: impl FnOnce() -> R) -> R {
+    let _guard = LengthUnitGuard {
+        previous_length_unit: LENGTH_UNIT.with(|current_length_unit| current_length_unit.replace(length_unit)),
+    };
+    f()
+}
+
+fn get_length_unit() -> LengthUnit {
+    LENGTH_UNIT.with(|length_unit| length_unit.get())
+}
+
+// #[serde(with = "millimeters")]
+pub mod millimeters {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Fixed, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(get_length_unit().convert_to_unit(*value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Fixed, D::Error> {
+        f64::deserialize(deserializer).map(|value| get_length_unit().convert_from_unit(value))
+    }
+}
+
+// #[serde(default, with = "optional_millimeters")]
+pub mod optional_millimeters {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<Fixed>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_some(&get_length_unit().convert_to_unit(*value)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Fixed>, D::Error> {
+        Option::<f64>::deserialize(deserializer).map(|value| value.map(|value| get_length_unit().convert_from_unit(value)))
+    }
+}
+
+// #[serde(with = "square_millimeters")]
+pub mod square_millimeters {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(area: &FixedArea, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(get_length_unit().convert_area_to_unit(*area))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FixedArea, D::Error> {
+        f64::deserialize(deserializer).map(|area| get_length_unit().convert_area_from_unit(area))
+    }
+}
+
+fn to_whole_millimeters(value: Fixed) -> i32 {
+    to_millimeters(value).round() as i32
+}
+
+// the fabrics and the cutting tables are registered in whole millimetres
+// #[serde(with = "whole_millimeters")]
+pub mod whole_millimeters {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(millimeters: &i32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(get_length_unit().convert_to_unit(from_whole_millimeters(*millimeters)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+        f64::deserialize(deserializer).map(|value| to_whole_millimeters(get_length_unit().convert_from_unit(value)))
+    }
+}
+
+// #[serde(default, with = "optional_whole_millimeters")]
+pub mod optional_whole_millimeters {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(millimeters: &Option<i32>, serializer: S) -> Result<S::Ok, S::Error> {
+        match millimeters {
+            Some(millimeters) => serializer.serialize_some(&get_length_unit().convert_to_unit(from_whole_millimeters(*millimeters))),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i32>, D::Error> {
+        Option::<f64>::deserialize(deserializer).map(|value| value.map(|value| to_whole_millimeters(get_length_unit().convert_from_unit(value))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Subject {
+        #[serde(with = "millimeters")]
+        width: Fixed,
+        #[serde(default, with = "optional_millimeters")]
+        margin: Option<Fixed>,
+        #[serde(with = "square_millimeters")]
+        area: FixedArea,
+    }
+
+    #[test]
+    fn millimeters_serde_test() {
+        let subject: Subject = serde_json::from_str(r#"{"width": 10.5, "area": 2}"#).unwrap();
+        assert_eq!(subject, Subject { width: 1050, margin: None, area: 20_000 });
+
+        // the old files only had whole millimetres
+        let subject: Subject = serde_json::from_str(r#"{"width": 3, "margin": 0.125, "area": 0.5}"#).unwrap();
+        assert_eq!(subject, Subject { width: 300, margin: Some(13), area: 5_000 });
+
+        assert_eq!(
+            serde_json::to_string(&Subject { width: 1050, margin: Some(5), area: 12_345 }).unwrap(),
+            r#"{"width":10.5,"margin":0.05,"area":1.2345}"#
+        );
+    }
+
+    #[test]
+    fn with_length_unit_serde_test() {
+        let subject = Subject { width: 12_700, margin: Some(1_000), area: 1_000_000 };
+
+        let json = with_length_unit(LengthUnit::Centimeter, || serde_json::to_string(&subject).unwrap());
+        assert_eq!(json, r#"{"width":12.7,"margin":1.0,"area":1.0}"#);
+        assert_eq!(with_length_unit(LengthUnit::Centimeter, || serde_json::from_str::<Subject>(&json).unwrap()), subject);
+
+        let json = with_length_unit(LengthUnit::Inch, || serde_json::to_string(&subject).unwrap());
+        assert_eq!(json, r#"{"width":5.0,"margin":0.3937,"area":0.15500031}"#);
+        assert_eq!(with_length_unit(LengthUnit::Inch, || serde_json::from_str::<Subject>(&json).unwrap()), subject);
+
+        // outside of the closure the values are millimetres again
+        assert_eq!(serde_json::to_string(&subject).unwrap(), r#"{"width":127.0,"margin":10.0,"area":100.0}"#);
+    }
+
+    #[test]
+    fn get_area_test() {
+        // 2000 mm x 100 m would overflow 32 bits
+        assert_eq!(get_area(from_whole_millimeters(2_000), from_whole_millimeters(100_000)), 2_000_000_000_000);
+        assert_eq!(to_square_millimeters(get_area(from_millimeters(0.5), from_millimeters(0.5))), 0.25);
+    }
+}
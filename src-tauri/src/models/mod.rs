@@ -3,3 +3,5 @@ pub mod cut_disposition;
 pub mod cutting_table;
 pub mod fabric;
 pub mod cutting_lines;
+pub mod fixed_point;
+pub mod setting;
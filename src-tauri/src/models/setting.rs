@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, Pool, Sqlite};
+
+use super::fixed_point::{Fixed, FixedArea, from_millimeters, to_millimeters, to_square_millimeters, millimeters, FIXED_POINT_SCALE};
+
+// a ten-thousandth of an inch is already finer than the hundredth of a millimetre kept for the geometry
+pub const MAX_G_CODE_DECIMAL_PLACES: u32 = 4;
+
+// Preferences of the workstation, kept on a single row
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct Setting {
+    pub length_unit: LengthUnit,
+    // decimal places of the coordinates written on the G-code
+    pub g_code_decimal_places: u32,
+    // the cut goes on past both ends of each line so the fabric separates cleanly
+    #[serde(with = "millimeters")]
+    pub overcut: Fixed,
+    // the blade goes down this far before the start of each cut
+    #[serde(with = "millimeters")]
+    pub lead_in: Fixed,
+}
+impl Setting {
+    pub fn format_g_code_value(&self, value: Fixed) -> String {
+        self.length_unit.format_g_code_value(value, self.g_code_decimal_places)
+    }
+}
+
+pub async fn get(poll: &Pool<Sqlite>) -> Result<Setting, Error> {
+    sqlx::query_as::<Sqlite, Setting>("SELECT length_unit, g_code_decimal_places, overcut, lead_in FROM setting WHERE id = 1")
+        .fetch_one(poll)
+        .await
+}
+
+pub async fn update(setting: Setting, poll: &Pool<Sqlite>) -> Result<Setting, Error> {
+    sqlx::query_as::<Sqlite, Setting>("UPDATE setting SET length_unit = ?, g_code_decimal_places = ?, overcut = ?, lead_in = ? WHERE id = 1 RETURNING length_unit, g_code_decimal_places, overcut, lead_in;")
+        .bind(setting.length_unit)
+        .bind(setting.g_code_decimal_places)
+        .bind(setting.overcut)
+        .bind(setting.lead_in)
+        .fetch_one(poll)
+        .await
+}
+
+// Unit shown to the operator and used by the G-code, the lengths are always stored in millimetres
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, sqlx::Type)]
+#[repr(i32)]
+pub enum LengthUnit {
+    #[default]
+    Millimeter = 0,
+    Centimeter = 1,
+    Inch = 2,
+}
+impl LengthUnit {
+    pub fn get_millimeters_per_unit(&self) -> f64 {
+        match self {
+            LengthUnit::Millimeter => 1.0,
+            LengthUnit::Centimeter => 10.0,
+            LengthUnit::Inch => 25.4,
+        }
+    }
+
+    pub fn convert_from_fixed(&self, value: Fixed) -> f64 {
+        to_millimeters(value) / self.get_millimeters_per_unit()
+    }
+
+    pub fn get_symbol(&self) -> &'static str {
+        match self {
+            LengthUnit::Millimeter => "mm",
+            LengthUnit::Centimeter => "cm",
+            LengthUnit::Inch => "pol",
+        }
+    }
+
+    // a hundredth of a millimetre is the smallest step, so each unit only keeps the decimals needed for it
+    pub fn get_decimal_places(&self) -> i32 {
+        match self {
+            LengthUnit::Millimeter => 2,
+            LengthUnit::Centimeter => 3,
+            LengthUnit::Inch => 4,
+        }
+    }
+
+    // the value shown to the operator, read back by convert_from_unit without losing the hundredth of a millimetre
+    pub fn convert_to_unit(&self, value: Fixed) -> f64 {
+        let decimal_scale = 10f64.powi(self.get_decimal_places());
+        (value as f64 * decimal_scale / (FIXED_POINT_SCALE as f64 * self.get_millimeters_per_unit())).round() / decimal_scale
+    }
+
+    pub fn convert_from_unit(&self, value: f64) -> Fixed {
+        from_millimeters(value * self.get_millimeters_per_unit())
+    }
+
+    pub fn convert_area_to_unit(&self, area: FixedArea) -> f64 {
+        let decimal_scale = 10f64.powi(self.get_decimal_places() * 2);
+        (to_square_millimeters(area) * decimal_scale / self.get_millimeters_per_unit().powi(2)).round() / decimal_scale
+    }
+
+    pub fn convert_area_from_unit(&self, area: f64) -> FixedArea {
+        (area * self.get_millimeters_per_unit().powi(2) * (FIXED_POINT_SCALE as f64).powi(2)).round() as FixedArea
+    }
+
+    // the G-code only knows millimetres (G21) and inches (G20)
+    pub fn get_g_code_unit(&self) -> LengthUnit {
+        match self {
+            LengthUnit::Inch => LengthUnit::Inch,
+            _ => LengthUnit::Millimeter,
+        }
+    }
+
+    pub fn format_g_code_value(&self, value: Fixed, decimal_places: u32) -> String {
+        format!("{:.*}", decimal_places as usize, self.get_g_code_unit().convert_from_fixed(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn length_unit_conversion_test() {
+        assert_eq!(LengthUnit::Millimeter.convert_from_fixed(12_550), 125.5);
+        assert_eq!(LengthUnit::Centimeter.convert_from_fixed(12_500), 12.5);
+        assert_eq!(LengthUnit::Inch.convert_from_fixed(25_400), 10.0);
+    }
+
+    #[test]
+    fn convert_to_unit_test() {
+        assert_eq!(LengthUnit::Millimeter.convert_to_unit(12_555), 125.55);
+        assert_eq!(LengthUnit::Centimeter.convert_to_unit(12_555), 12.555);
+        assert_eq!(LengthUnit::Inch.convert_to_unit(1_000), 0.3937);
+        assert_eq!(LengthUnit::Inch.convert_to_unit(25_400), 10.0);
+
+        // every hundredth of a millimetre comes back the same from the unit shown
+        for length_unit in [LengthUnit::Millimeter, LengthUnit::Centimeter, LengthUnit::Inch] {
+            for value in [0, 1, 7, 99, 1_000, 12_555, 254_001, 5_000_000] {
+                assert_eq!(length_unit.convert_from_unit(length_unit.convert_to_unit(value)), value, "{:?} {}", length_unit, value);
+            }
+        }
+
+        assert_eq!(LengthUnit::Centimeter.convert_area_to_unit(1_000_000), 1.0);
+        assert_eq!(LengthUnit::Centimeter.convert_area_from_unit(1.0), 1_000_000);
+        assert_eq!(LengthUnit::Inch.convert_area_from_unit(1.0), 6_451_600);
+    }
+
+    #[test]
+    fn format_g_code_value_test() {
+        assert_eq!(LengthUnit::Millimeter.format_g_code_value(320_050, 2), "3200.50");
+        assert_eq!(LengthUnit::Millimeter.format_g_code_value(320_060, 0), "3201");
+        assert_eq!(LengthUnit::Centimeter.format_g_code_value(320_000, 1), "3200.0");
+        assert_eq!(LengthUnit::Inch.format_g_code_value(320_000, 4), "125.9843");
+        assert_eq!(LengthUnit::Inch.format_g_code_value(1_270, 3), "0.500");
+    }
+}
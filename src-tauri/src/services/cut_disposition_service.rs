@@ -1,56 +1,315 @@
 use std::cmp::Ordering;
 
-use crate::models::cut_disposition::{CutDispositionInput, Vertex, PositionedRectangle, PositionedRectangleVertices, Rectangle, CutDispositionOutput};
+use crate::models::{cut_disposition::{CutDispositionInput, Vertex, PositionedRectangle, PositionedRectangleVertices, Rectangle, CutDispositionOutput, PieceCount, ShowcaseCount, NestingStrategyType, OrganizeProgress, PatternRepeat, EdgeMargins}, fixed_point::get_area};
 
+use super::{max_rects_service::MaxRectsStrategy, guillotine_service::GuillotineStrategy, cutting_lines_service::is_guillotine_cuttable, optimizer_service::optimize_disposition, unplaced_piece_service::explain_unplaced_pieces, spatial_index_service::{SpatialIndex, CollisionSearch, get_cell_size}};
 
-pub fn organize_disposition(cut_disposition_input: &CutDispositionInput) -> CutDispositionOutput {
-    let max_length = match cut_disposition_input.defined_length {
-        Some(defined_length) => defined_length,
-        None => cut_disposition_input.max_length,
+
+pub trait NestingStrategy {
+    fn organize(&self, cut_disposition_input: &CutDispositionInput) -> CutDispositionOutput {
+        self.organize_in_order(
+            cut_disposition_input, 
+            self.get_rectangle_instance_list(&cut_disposition_input.rectangles_list)
+        )
+    }
+
+    // one copy of each rectangle for each unit of quantity, in the order the strategy nests them
+    fn get_rectangle_instance_list(&self, rectangles_list: &[Rectangle]) -> Vec<Rectangle>;
+
+    fn organize_in_order(&self, cut_disposition_input: &CutDispositionInput, rectangle_instance_list: Vec<Rectangle>) -> CutDispositionOutput;
+}
+
+pub fn get_nesting_strategy(nesting_strategy_type: &NestingStrategyType) -> Box<dyn NestingStrategy> {
+    get_nesting_strategy_with_collision_search(nesting_strategy_type, CollisionSearch::SpatialIndex)
+}
+
+pub fn get_nesting_strategy_with_collision_search(nesting_strategy_type: &NestingStrategyType, collision_search: CollisionSearch) -> Box<dyn NestingStrategy> {
+    match nesting_strategy_type {
+        NestingStrategyType::BottomLeftVertex => Box::new(BottomLeftVertexStrategy { collision_search }),
+        NestingStrategyType::MaxRects => Box::new(MaxRectsStrategy { collision_search }),
+        NestingStrategyType::Guillotine => Box::new(GuillotineStrategy { collision_search }),
+    }
+}
+
+// Follows a long organization, a cancelled organization keeps the best layout found so far,
+// the cancellation is checked by the threads of the optimization
+pub trait OrganizeObserver: Sync {
+    fn on_progress(&self, organize_progress: OrganizeProgress);
+
+    fn is_cancelled(&self) -> bool;
+}
+
+pub struct NoOrganizeObserver;
+
+impl OrganizeObserver for NoOrganizeObserver {
+    fn on_progress(&self, _organize_progress: OrganizeProgress) {}
+
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+pub fn organize_disposition(cut_disposition_input: &CutDispositionInput, sheet: u32, observer: &dyn OrganizeObserver) -> CutDispositionOutput {
+    let cut_disposition_input = &restrict_rotation_to_fabric_grain(cut_disposition_input);
+
+    let usable_band_input = &get_usable_band_input(cut_disposition_input);
+
+    let usable_band_output = match &usable_band_input.optimization {
+        Some(optimization) => optimize_disposition(usable_band_input, optimization, sheet, observer),
+        None => {
+            let output = get_nesting_strategy(&usable_band_input.nesting_strategy).organize(usable_band_input);
+            observer.on_progress(OrganizeProgress { sheet, iteration: 0, best_usage: output.usage });
+            output
+        },
     };
 
-    let spacing = cut_disposition_input.spacing.unwrap_or(0);
+    let output = move_usable_band_output_to_fabric(usable_band_output, cut_disposition_input);
 
-    let max_width = cut_disposition_input.defined_width;
+    CutDispositionOutput {
+        unplaced_piece_list: explain_unplaced_pieces(cut_disposition_input, &output),
+        ..output
+    }
+}
 
-    let rectangles_list = cut_disposition_input.rectangles_list.clone();
+// The pieces are nested in the band left between the edge margins, as if it were the whole fabric,
+// so the usage is computed against the usable area only
+fn get_usable_band_input(cut_disposition_input: &CutDispositionInput) -> CutDispositionInput {
+    let edge_margins = &cut_disposition_input.edge_margins;
 
-    let prohibited_area_list = cut_disposition_input.prohibited_area_list.clone();
+    if edge_margins.is_empty() {
+        return cut_disposition_input.clone();
+    }
+
+    let band_width = cut_disposition_input.defined_width - edge_margins.left - edge_margins.right;
+    let band_length = cut_disposition_input.max_length - edge_margins.top;
+
+    let prohibited_area_list = cut_disposition_input.prohibited_area_list
+        .iter()
+        .filter_map(
+            |prohibited_area| {
+                // only the part of the prohibited area inside the band matters
+                let left = (prohibited_area.top_left_vertex.pos_x - edge_margins.left).max(0);
+                let top = (prohibited_area.top_left_vertex.pos_y - edge_margins.top).max(0);
+                let right = (prohibited_area.top_left_vertex.pos_x + prohibited_area.width - edge_margins.left).min(band_width);
+                let bottom = (prohibited_area.top_left_vertex.pos_y + prohibited_area.length - edge_margins.top).min(band_length);
+
+                if right <= left || bottom <= top {
+                    return None;
+                }
+
+                Some(PositionedRectangle {
+                    width: right - left,
+                    length: bottom - top,
+                    top_left_vertex: Vertex { pos_x: left, pos_y: top },
+                    ..prohibited_area.clone()
+                })
+            }
+        )
+        .collect();
+
+    let pinned_piece_list = cut_disposition_input.pinned_piece_list
+        .iter()
+        .map(
+            |pinned_piece| PositionedRectangle {
+                top_left_vertex: Vertex {
+                    pos_x: pinned_piece.top_left_vertex.pos_x - edge_margins.left,
+                    pos_y: pinned_piece.top_left_vertex.pos_y - edge_margins.top,
+                },
+                ..pinned_piece.clone()
+            }
+        )
+        .collect();
+
+    CutDispositionInput {
+        prohibited_area_list,
+        pinned_piece_list,
+        max_length: band_length,
+        defined_length: cut_disposition_input.defined_length.map(|defined_length| defined_length - edge_margins.top),
+        defined_width: band_width,
+        pattern_repeat: cut_disposition_input.pattern_repeat.as_ref().map(
+            |pattern_repeat| PatternRepeat {
+                offset_x: pattern_repeat.offset_x - edge_margins.left,
+                offset_y: pattern_repeat.offset_y - edge_margins.top,
+                ..pattern_repeat.clone()
+            }
+        ),
+        edge_margins: EdgeMargins::default(),
+        ..cut_disposition_input.clone()
+    }
+}
+
+fn move_usable_band_output_to_fabric(usable_band_output: CutDispositionOutput, cut_disposition_input: &CutDispositionInput) -> CutDispositionOutput {
+    let edge_margins = &cut_disposition_input.edge_margins;
+
+    if edge_margins.is_empty() {
+        return usable_band_output;
+    }
+
+    let move_to_fabric = |positioned_rectangle: &PositionedRectangle| PositionedRectangle {
+        top_left_vertex: Vertex {
+            pos_x: positioned_rectangle.top_left_vertex.pos_x + edge_margins.left,
+            pos_y: positioned_rectangle.top_left_vertex.pos_y + edge_margins.top,
+        },
+        ..positioned_rectangle.clone()
+    };
+
+    CutDispositionOutput {
+        positioned_rectangles_list: usable_band_output.positioned_rectangles_list.iter().map(move_to_fabric).collect(),
+        showcase_rectangles_located_list: usable_band_output.showcase_rectangles_located_list.iter().map(move_to_fabric).collect(),
+        prohibited_area_list: cut_disposition_input.prohibited_area_list.clone(),
+        // the top edge is pulled with the fabric even if it is not used
+        length_used: if usable_band_output.length_used > 0 { usable_band_output.length_used + edge_margins.top } else { 0 },
+        edge_margins: edge_margins.clone(),
+        max_length: cut_disposition_input.max_length,
+        defined_length: cut_disposition_input.defined_length,
+        defined_width: cut_disposition_input.defined_width,
+        ..usable_band_output
+    }
+}
+
+// The grain belongs to the fabric, so it overrides the rotation chosen for the disposition and for each piece
+fn restrict_rotation_to_fabric_grain(cut_disposition_input: &CutDispositionInput) -> CutDispositionInput {
+    let mut restricted_input = cut_disposition_input.clone();
+
+    if !restricted_input.fabric_grain.allows_quarter_turn() {
+        restricted_input.allow_rotation = false;
+        for rectangle in restricted_input.rectangles_list.iter_mut() {
+            rectangle.allow_rotation = Some(false);
+        }
+    }
+
+    restricted_input
+}
 
+// Each new sheet receives the pieces left by the previous one, the prohibited areas only exist on the first sheet
+pub fn organize_disposition_sheets(cut_disposition_input: &CutDispositionInput, observer: &dyn OrganizeObserver) -> Vec<CutDispositionOutput> {
+    let mut sheet_output_list = Vec::<CutDispositionOutput>::new();
+
+    let mut sheet_input = cut_disposition_input.clone();
+
+    loop {
+        let sheet_output = organize_disposition(&sheet_input, sheet_output_list.len() as u32 + 1, observer);
+
+        // nothing fits on an empty sheet, so the pieces left are bigger than the fabric
+        if !sheet_output_list.is_empty() && sheet_output.positioned_rectangles_list.is_empty() {
+            break;
+        }
+
+        let unused_rectangles_list = sheet_output.unused_rectangles_list.clone();
+
+        sheet_input.showcase_list = get_showcases_left(&sheet_input.showcase_list, &sheet_output.showcase_count_list);
+
+        sheet_output_list.push(sheet_output);
+
+        if !cut_disposition_input.multi_sheet || unused_rectangles_list.is_empty() || observer.is_cancelled() {
+            break;
+        }
+
+        sheet_input.rectangles_list = unused_rectangles_list;
+        sheet_input.prohibited_area_list = Vec::new();
+        sheet_input.pinned_piece_list = Vec::new();
+    }
+
+    sheet_output_list
+}
+
+// the max quantity of a showcase is for the whole job, not for each sheet
+fn get_showcases_left(showcase_list: &[Rectangle], showcase_count_list: &[ShowcaseCount]) -> Vec<Rectangle> {
+    showcase_list
+    .iter()
+    .filter_map(
+        |showcase| {
+            let placed_quantity = showcase_count_list
+                .iter()
+                .find(|showcase_count| showcase_count.id == showcase.id)
+                .map_or(0, |showcase_count| showcase_count.placed_quantity);
+
+            match showcase.max_quantity {
+                Some(max_quantity) if max_quantity <= placed_quantity => None,
+                Some(max_quantity) => Some(Rectangle { max_quantity: Some(max_quantity - placed_quantity), ..showcase.clone() }),
+                None => Some(showcase.clone()),
+            }
+        }
+    )
+    .collect()
+}
+
+// Places each rectangle, from the widest to the narrowest, at the free vertex closest to the top and left
+pub struct BottomLeftVertexStrategy {
+    pub collision_search: CollisionSearch,
+}
+
+impl NestingStrategy for BottomLeftVertexStrategy {
+    fn get_rectangle_instance_list(&self, rectangles_list: &[Rectangle]) -> Vec<Rectangle> {
+        get_rectangle_instance_list(rectangles_list)
+    }
+
+    fn organize_in_order(&self, cut_disposition_input: &CutDispositionInput, rectangle_instance_list: Vec<Rectangle>) -> CutDispositionOutput {
+        let main_rectangle_organized = organize_main_rectangles( 
+            get_max_length(cut_disposition_input), 
+            cut_disposition_input.spacing.unwrap_or(0), 
+            cut_disposition_input.defined_width, 
+            cut_disposition_input.allow_rotation,
+            rectangle_instance_list, 
+            &cut_disposition_input.prohibited_area_list,
+            &cut_disposition_input.pinned_piece_list,
+            cut_disposition_input.pattern_repeat.as_ref(),
+            self.collision_search
+        );
+
+        complete_cut_disposition_output(cut_disposition_input, main_rectangle_organized, self.collision_search)
+    }
+}
+
+pub fn get_max_length(cut_disposition_input: &CutDispositionInput) -> i32 {
+    match cut_disposition_input.defined_length {
+        Some(defined_length) => defined_length,
+        None => cut_disposition_input.max_length,
+    }
+}
+
+// Fills the showcases and computes the usage for the main rectangles organized by any strategy
+pub fn complete_cut_disposition_output(
+    cut_disposition_input: &CutDispositionInput,
+    main_rectangle_organized: MainRectangleOrganized,
+    collision_search: CollisionSearch
+) -> CutDispositionOutput {
     let MainRectangleOrganized { 
         possible_vertex_for_rectangle_list, 
         unused_rectangles_list, 
         positioned_rectangles_list, 
+        positioned_showcase_list,
         mut length_used 
-    } = organize_main_rectangles( 
-        max_length, 
-        spacing, 
-        max_width, 
-        &rectangles_list, 
-        &prohibited_area_list
-    );
+    } = main_rectangle_organized;
+
+    let spacing = cut_disposition_input.spacing.unwrap_or(0);
+
+    let max_width = cut_disposition_input.defined_width;
+
+    let rectangles_list = cut_disposition_input.rectangles_list.clone();
+
+    let prohibited_area_list = cut_disposition_input.prohibited_area_list.clone();
 
     if let Some(defined_length) = cut_disposition_input.defined_length {
         length_used = defined_length;
     }
 
-    let mut positioned_showcase_list = Vec::<PositionedRectangle>::new();
-    
-    if let Some(showcase) = &cut_disposition_input.showcase {
-        positioned_showcase_list.append(
-            &mut organize_showcase_rectangles(
-                showcase.clone(), 
-                spacing, 
-                max_width, 
-                &prohibited_area_list, 
-                &possible_vertex_for_rectangle_list, 
-                &positioned_rectangles_list,
-                length_used
-            )
+    let positioned_showcase_list = positioned_showcase_list.unwrap_or_else(
+        || organize_showcase_rectangles(
+            &cut_disposition_input.showcase_list, 
+            spacing, 
+            max_width, 
+            &prohibited_area_list, 
+            &possible_vertex_for_rectangle_list, 
+            &positioned_rectangles_list,
+            length_used,
+            collision_search
         )
-    }
+    );
+
+    let showcase_count_list = count_showcases(&cut_disposition_input.showcase_list, &positioned_showcase_list);
     
-    let total_area = max_width * length_used;
+    let total_area = get_area(max_width, length_used);
 
     let used_area = positioned_rectangles_list.iter().fold(0, |sum, value| sum + value.get_area());
 
@@ -59,6 +318,13 @@ pub fn organize_disposition(cut_disposition_input: &CutDispositionInput) -> CutD
         usage = (used_area as f64) / (total_area as f64);
     }
 
+    let piece_count_list = count_pieces(&rectangles_list, &positioned_rectangles_list);
+
+    let mut cut_rectangles_list = positioned_rectangles_list.clone();
+    cut_rectangles_list.append(&mut positioned_showcase_list.clone());
+
+    let is_guillotine = is_guillotine_cuttable(&cut_rectangles_list);
+
     CutDispositionOutput { 
         positioned_rectangles_list, 
         showcase_rectangles_located_list: positioned_showcase_list, 
@@ -68,31 +334,59 @@ pub fn organize_disposition(cut_disposition_input: &CutDispositionInput) -> CutD
         total_area,
         used_area,
         usage,
+        piece_count_list,
+        showcase_count_list,
+        is_guillotine,
+        edge_margins: cut_disposition_input.edge_margins.clone(),
         max_length: cut_disposition_input.max_length,
         defined_length: cut_disposition_input.defined_length,
-        defined_width: max_width
+        defined_width: max_width,
+        // explained once the sheet is back on the whole fabric
+        unplaced_piece_list: Vec::new(),
     }
 }
 
-struct MainRectangleOrganized{
+pub struct MainRectangleOrganized{
     pub possible_vertex_for_rectangle_list : Vec<Vertex>,
     pub unused_rectangles_list: Vec<Rectangle>,
     pub positioned_rectangles_list: Vec<PositionedRectangle>,
+    // None lets the showcases fill the vertices left by the main rectangles
+    pub positioned_showcase_list: Option<Vec<PositionedRectangle>>,
     pub length_used: i32,
 }
 
+fn get_rectangle_instance_list(rectangles_list: &[Rectangle]) -> Vec<Rectangle> {
+    let mut rectangle_instance_list = expand_rectangles_by_quantity(rectangles_list);
+
+    rectangle_instance_list.sort_by(rectangle_wider_and_longer_comparator);
+
+    rectangle_instance_list
+}
+
+#[allow(clippy::too_many_arguments)]
 fn organize_main_rectangles(
     max_length: i32, 
     spacing: i32, 
     max_width: i32, 
-    rectangles_list: &[Rectangle], 
-    prohibited_area_list: &[PositionedRectangle]
+    allow_rotation: bool,
+    rectangle_instance_list: Vec<Rectangle>, 
+    prohibited_area_list: &[PositionedRectangle],
+    pinned_piece_list: &[PositionedRectangle],
+    pattern_repeat: Option<&PatternRepeat>,
+    collision_search: CollisionSearch
 ) -> MainRectangleOrganized {
     let mut possible_vertex_for_rectangle_list = Vec::<Vertex>::new();
 
-    let mut unused_rectangles_list = Vec::<Rectangle>::new();
+    let mut unused_instances_list = Vec::<Rectangle>::new();
 
-    let mut positioned_rectangles_list = Vec::<PositionedRectangle>::new();
+    // the pinned pieces are already in place, the other pieces are nested around them
+    let mut positioned_rectangles_list = Vec::from(pinned_piece_list);
+
+    let spacing_list = get_spacing_list(&rectangle_instance_list, spacing);
+
+    let cell_size = get_cell_size(&rectangle_instance_list, max_width, collision_search);
+    let mut positioned_rectangle_index = SpatialIndex::new_from_rectangle_list(pinned_piece_list, spacing, cell_size);
+    let prohibited_area_index = SpatialIndex::new_from_rectangle_list(prohibited_area_list, 0, cell_size);
 
     // Creates a vertex at the origin
     possible_vertex_for_rectangle_list.push(Vertex { pos_x: 0, pos_y: 0 });
@@ -111,44 +405,49 @@ fn organize_main_rectangles(
         )
     );
 
+    for pinned_piece in pinned_piece_list {
+        possible_vertex_for_rectangle_list.append(&mut create_available_vertices_for_prohibited_area(pinned_piece));
+        possible_vertex_for_rectangle_list.append(&mut get_vertices_for_positioning(pinned_piece.clone(), spacing, &spacing_list));
+    }
+
     possible_vertex_for_rectangle_list.sort_by(vertex_closest_to_top_and_left_comparator);
 
     possible_vertex_for_rectangle_list.dedup();
 
-    let mut rectangles_list_sorted = Vec::from(rectangles_list);
-
-    rectangles_list_sorted.sort_by(rectangle_wider_and_longer_comparator);
-    
-    for rectangle in rectangles_list_sorted {
+    for rectangle in rectangle_instance_list {
         possible_vertex_for_rectangle_list.sort_by(vertex_closest_to_top_and_left_comparator);
 
         let positioned_rectangle_option = find_position_for_rectangle(
             &rectangle, 
             max_width, 
             max_length, 
-            spacing, 
-            &positioned_rectangles_list, 
+            allow_rotation,
+            &positioned_rectangle_index, 
             &possible_vertex_for_rectangle_list, 
-            prohibited_area_list
+            &prohibited_area_index,
+            pattern_repeat
         );
 
         match positioned_rectangle_option {
             Some(positioned_rectangle) => {
                 positioned_rectangles_list.push(positioned_rectangle.clone());
+                positioned_rectangle_index.insert(positioned_rectangle.clone());
                 if let Some(used_vertex_index) = get_vertex_index_in_list(
                     &positioned_rectangle.top_left_vertex,
                     &possible_vertex_for_rectangle_list
                 ) {
                     possible_vertex_for_rectangle_list.remove(used_vertex_index);
                 }
-                possible_vertex_for_rectangle_list.append(&mut get_vertices_for_positioning(positioned_rectangle, spacing));
+                possible_vertex_for_rectangle_list.append(&mut get_vertices_for_positioning(positioned_rectangle, spacing, &spacing_list));
             },
             None => {
-                unused_rectangles_list.push(rectangle.clone());
+                unused_instances_list.push(rectangle.clone());
             },
         };
     }
 
+    let unused_rectangles_list = group_rectangles_by_id(&unused_instances_list);
+
     let length_used = 
     match positioned_rectangles_list.iter().max_by(
         |first, second| rectangle_maximum_y_comparator(first, second)
@@ -161,79 +460,161 @@ fn organize_main_rectangles(
         possible_vertex_for_rectangle_list,
         unused_rectangles_list,
         positioned_rectangles_list,
+        positioned_showcase_list: None,
         length_used
     }
 }
 
+// each copy of a piece is nested on its own, sharing the id of the piece
+pub fn expand_rectangles_by_quantity(rectangles_list: &[Rectangle]) -> Vec<Rectangle> {
+    rectangles_list
+    .iter()
+    .flat_map(
+        |rectangle| 
+        (0..rectangle.quantity).map(
+            |_| Rectangle {
+                quantity: 1,
+                ..rectangle.clone()
+            }
+        )
+    )
+    .collect()
+}
+
+pub fn group_rectangles_by_id(rectangles_list: &[Rectangle]) -> Vec<Rectangle> {
+    let mut grouped_list = Vec::<Rectangle>::new();
+
+    for rectangle in rectangles_list {
+        match grouped_list.iter_mut().find(|item| item.id == rectangle.id) {
+            Some(grouped) => grouped.quantity += rectangle.quantity,
+            None => grouped_list.push(rectangle.clone()),
+        }
+    }
+
+    grouped_list
+}
+
+fn count_pieces(
+    rectangles_list: &[Rectangle],
+    positioned_rectangles_list: &[PositionedRectangle]
+) -> Vec<PieceCount> {
+    rectangles_list
+    .iter()
+    .map(
+        |rectangle| {
+            let placed_quantity = positioned_rectangles_list
+                .iter()
+                .filter(|positioned| positioned.id == rectangle.id)
+                .count() as u32;
+
+            PieceCount {
+                id: rectangle.id,
+                quantity: rectangle.quantity,
+                placed_quantity,
+                unplaced_quantity: rectangle.quantity.saturating_sub(placed_quantity),
+            }
+        }
+    )
+    .collect()
+}
+
+fn count_showcases(
+    showcase_list: &[Rectangle],
+    positioned_showcase_list: &[PositionedRectangle]
+) -> Vec<ShowcaseCount> {
+    showcase_list
+    .iter()
+    .map(
+        |showcase| {
+            let placed_showcase_list: Vec<&PositionedRectangle> = positioned_showcase_list
+                .iter()
+                .filter(|positioned| positioned.id == showcase.id)
+                .collect();
+
+            ShowcaseCount {
+                id: showcase.id,
+                placed_quantity: placed_showcase_list.len() as u32,
+                used_area: placed_showcase_list.iter().fold(0, |sum, value| sum + value.get_area()),
+            }
+        }
+    )
+    .collect()
+}
+
+// showcases are placed from the biggest to the smallest, so the smaller ones fill the holes left by the bigger ones
+#[allow(clippy::too_many_arguments)]
 fn organize_showcase_rectangles(
-    showcase: Rectangle,
+    showcase_list: &[Rectangle],
     spacing: i32, 
     max_width: i32, 
     prohibited_area_list: &[PositionedRectangle],
     possible_vertex_for_rectangle_list: &[Vertex], 
     positioned_rectangles_list: &[PositionedRectangle], 
-    length_used: i32
+    length_used: i32,
+    collision_search: CollisionSearch
 ) -> Vec<PositionedRectangle> {
-    let mut possible_vertex_for_rectangle_list_sorted: Vec<Vertex> = Vec::from(possible_vertex_for_rectangle_list);
+    let mut showcase_list_sorted = Vec::from(showcase_list);
 
-    possible_vertex_for_rectangle_list_sorted.sort_by(vertex_closest_to_top_and_left_comparator);
+    showcase_list_sorted.sort_by(rectangle_bigger_area_comparator);
 
-    possible_vertex_for_rectangle_list_sorted.dedup();
+    let mut available_vertex_list: Vec<Vertex> = Vec::from(possible_vertex_for_rectangle_list);
 
     let mut positioned_showcase_list = Vec::<PositionedRectangle>::new();
 
-    while !&possible_vertex_for_rectangle_list_sorted.is_empty() {
+    // showcases keep the same spacing from the pieces and from each other
+    let cell_size = get_cell_size(showcase_list, max_width, collision_search);
+    let mut cut_rectangle_index = SpatialIndex::new_from_rectangle_list(positioned_rectangles_list, spacing, cell_size);
+    let prohibited_area_index = SpatialIndex::new_from_rectangle_list(prohibited_area_list, 0, cell_size);
+
+    for showcase in showcase_list_sorted {
+        let mut possible_vertex_for_rectangle_list_sorted = available_vertex_list.clone();
+
         possible_vertex_for_rectangle_list_sorted.sort_by(vertex_closest_to_top_and_left_comparator);
 
-        if let Some(vertex) = possible_vertex_for_rectangle_list_sorted.get(0) {
-            let subject = PositionedRectangle {
-                id: showcase.id,
-                width: showcase.width,
-                length: showcase.length,
-                top_left_vertex: vertex.clone(),
-            };
+        possible_vertex_for_rectangle_list_sorted.dedup();
 
-            if 
-            !is_within_boundaries(
-                &subject, 
-                max_width, 
-                length_used)
-            ||
-            subject_intesect_with_positioned_rectangles_list(
-                &subject, 
-                positioned_rectangles_list, 
-                spacing
-            ) 
-            ||
-            subject_intesect_with_positioned_rectangles_list(
-                &subject, 
-                prohibited_area_list,
-                0
-            ) 
-            ||
-            subject_intesect_with_positioned_rectangles_list(
-                &subject, 
-                &positioned_showcase_list, 
-                spacing
-            ) {
-                possible_vertex_for_rectangle_list_sorted.remove(0);
-                continue;
+        let mut placed_quantity = 0;
+
+        while !&possible_vertex_for_rectangle_list_sorted.is_empty() {
+            if showcase.max_quantity.is_some_and(|max_quantity| placed_quantity >= max_quantity) {
+                break;
             }
 
-            possible_vertex_for_rectangle_list_sorted.remove(0);
-            possible_vertex_for_rectangle_list_sorted.append(
-                &mut create_available_vertices_for_positioning(&subject, spacing)
-            );
-            positioned_showcase_list.push(subject);
-        } else {
-            break;
-        }
-    };
+            possible_vertex_for_rectangle_list_sorted.sort_by(vertex_closest_to_top_and_left_comparator);
+
+            if let Some(vertex) = possible_vertex_for_rectangle_list_sorted.first() {
+                let subject = PositionedRectangle::new_from_rectangle_and_vertex(&showcase, vertex);
+
+                if 
+                !is_within_boundaries(
+                    &subject, 
+                    max_width, 
+                    length_used)
+                ||
+                cut_rectangle_index.intersect(&subject)
+                ||
+                prohibited_area_index.intersect(&subject) {
+                    possible_vertex_for_rectangle_list_sorted.remove(0);
+                    continue;
+                }
+
+                possible_vertex_for_rectangle_list_sorted.remove(0);
+                let mut new_vertex_list = create_available_vertices_for_positioning(&subject, spacing);
+                possible_vertex_for_rectangle_list_sorted.append(&mut new_vertex_list.clone());
+                available_vertex_list.append(&mut new_vertex_list);
+                cut_rectangle_index.insert(subject.clone());
+                positioned_showcase_list.push(subject);
+                placed_quantity += 1;
+            } else {
+                break;
+            }
+        };
+    }
 
     positioned_showcase_list
 }
 
-fn get_vertex_index_in_list(vertex: &Vertex, possible_vertex_for_rectangle_list: &Vec<Vertex>) -> Option<usize> {
+fn get_vertex_index_in_list(vertex: &Vertex, possible_vertex_for_rectangle_list: &[Vertex]) -> Option<usize> {
     possible_vertex_for_rectangle_list
     .iter()
     .position(
@@ -242,71 +623,113 @@ fn get_vertex_index_in_list(vertex: &Vertex, possible_vertex_for_rectangle_list:
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn find_position_for_rectangle(
     rectangle: &Rectangle,
     max_width: i32, 
     max_length: i32,
-    spacing: i32, 
-    positioned_rectangles_list: &[PositionedRectangle], 
+    allow_rotation: bool,
+    positioned_rectangle_index: &SpatialIndex, 
     possible_vertex_for_rectangle_list: &[Vertex],
-    prohibited_area_list: &[PositionedRectangle]
+    prohibited_area_index: &SpatialIndex,
+    pattern_repeat: Option<&PatternRepeat>
 ) -> Option<PositionedRectangle> {
     let positioned_rectangle_option = 
     possible_vertex_for_rectangle_list
     .iter()
     .find_map(
         |vertex| {
-            let subject = PositionedRectangle {
-                id: rectangle.id,
-                width: rectangle.width,
-                length: rectangle.length,
-                top_left_vertex: vertex.clone(),
-            };
-
-            if 
-            !is_within_boundaries(
-                &subject, 
-                max_width, 
-                max_length
-            )
-            ||
-            subject_intesect_with_positioned_rectangles_list(
-                &subject, 
-                positioned_rectangles_list, 
-                spacing
-            ) 
-            ||
-            subject_intesect_with_positioned_rectangles_list(
-                &subject, 
-                prohibited_area_list,
-                0
-                ) {
-                return Option::None;
+            let vertex = &snap_vertex_to_pattern(rectangle, vertex, pattern_repeat);
+
+            // the original orientation is preferred, the rotated one is only tried on the same vertex afterwards
+            let mut subject_list = vec![
+                PositionedRectangle::new_from_rectangle_and_vertex(rectangle, vertex)
+            ];
+            if rectangle.can_rotate(allow_rotation) {
+                subject_list.push(
+                    PositionedRectangle::new_from_rotated_rectangle_and_vertex(rectangle, vertex)
+                );
             }
 
-            Option::Some(subject)
+            subject_list
+            .into_iter()
+            .find(
+                |subject| 
+                is_within_boundaries(
+                    subject, 
+                    max_width, 
+                    max_length
+                )
+                &&
+                !positioned_rectangle_index.intersect(subject)
+                &&
+                !prohibited_area_index.intersect(subject)
+            )
         }
     );
     positioned_rectangle_option
 }
 
-fn get_vertices_for_positioning(positioned_rectangle: PositionedRectangle, spacing: i32) -> Vec<Vertex> {
+// the pieces that match the pattern are moved to the next repeat of the print
+pub fn snap_vertex_to_pattern(rectangle: &Rectangle, vertex: &Vertex, pattern_repeat: Option<&PatternRepeat>) -> Vertex {
+    match pattern_repeat {
+        Some(pattern_repeat) if rectangle.match_pattern => pattern_repeat.snap_vertex(vertex),
+        _ => vertex.clone(),
+    }
+}
+
+// the gap to the next piece depends on its margin too, so there is a pair of vertices for each margin still to be placed
+fn get_vertices_for_positioning(positioned_rectangle: PositionedRectangle, spacing: i32, spacing_list: &[i32]) -> Vec<Vertex> {
     let positioned_rectangle_vertices = positioned_rectangle.get_vertices();
-    let vertices_to_be_added = 
-        &mut vec![
+
+    let mut gap_list: Vec<i32> = spacing_list
+        .iter()
+        .map(|next_spacing| positioned_rectangle.get_spacing(spacing).max(*next_spacing))
+        .collect();
+    gap_list.sort();
+    gap_list.dedup();
+
+    gap_list
+    .into_iter()
+    .flat_map(
+        |gap| [
             Vertex { 
                 pos_x: positioned_rectangle_vertices.bottom_left_vertex.pos_x, 
-                pos_y:  positioned_rectangle_vertices.bottom_left_vertex.pos_y + spacing 
+                pos_y:  positioned_rectangle_vertices.bottom_left_vertex.pos_y + gap 
             },
             Vertex { 
-                pos_x: positioned_rectangle_vertices.top_rigth_vertex.pos_x + spacing, 
+                pos_x: positioned_rectangle_vertices.top_rigth_vertex.pos_x + gap, 
                 pos_y:  positioned_rectangle_vertices.top_rigth_vertex.pos_y 
             },
-        ];
-    vertices_to_be_added.to_vec()
+        ]
+    )
+    .collect()
+}
+
+// the spacing of every piece still to be placed, and the spacing of the disposition kept for the showcases
+pub fn get_spacing_list(rectangle_list: &[Rectangle], spacing: i32) -> Vec<i32> {
+    let mut spacing_list: Vec<i32> = rectangle_list
+        .iter()
+        .map(|rectangle| rectangle.get_spacing(spacing))
+        .collect();
+    spacing_list.push(spacing);
+    spacing_list.sort();
+    spacing_list.dedup();
+    spacing_list
+}
+
+// the biggest margin of the pieces still to be placed, the pieces without margin butt up to the prohibited areas
+pub fn get_max_margin(rectangle_list: &[Rectangle]) -> i32 {
+    rectangle_list
+        .iter()
+        .filter_map(|rectangle| rectangle.margin)
+        .max()
+        .unwrap_or(0)
+        .max(0)
 }
 
-fn subject_intesect_with_positioned_rectangles_list(
+// two neighbours are kept apart by the biggest of their margins
+pub fn subject_intesect_with_positioned_rectangles_list(
     subject: &PositionedRectangle,
     positioned_rectangles_list: &[PositionedRectangle], 
     spacing: i32
@@ -315,13 +738,13 @@ fn subject_intesect_with_positioned_rectangles_list(
     .iter()
     .any(
         |rect| {
-            intersect(subject, rect, spacing)
+            intersect(subject, rect, subject.get_spacing(spacing).max(rect.get_spacing(spacing)))
         }
     )
 }
 
 
-fn create_available_vertices_for_prohibited_area(
+pub fn create_available_vertices_for_prohibited_area(
     prohibited_area: &PositionedRectangle,
 ) -> Vec<Vertex> {
         let mut vertex_list: Vec<Vertex> = Vec::<Vertex>::new();
@@ -355,7 +778,7 @@ fn create_available_vertices_for_prohibited_area(
         vertex_list
 }
 
-fn create_available_vertices_for_positioning(
+pub fn create_available_vertices_for_positioning(
     positioned_rectangle: &PositionedRectangle,
     spacing: i32
 ) -> Vec<Vertex> {
@@ -363,6 +786,9 @@ fn create_available_vertices_for_positioning(
     
     let PositionedRectangleVertices {top_rigth_vertex, bottom_left_vertex, ..} = positioned_rectangle.get_vertices();
 
+    // the vertices are used by the showcases, which keep at least the spacing of the disposition
+    let spacing = positioned_rectangle.get_spacing(spacing).max(spacing);
+
     vertex_list.push(
         Vertex { 
             pos_x: top_rigth_vertex.pos_x + spacing, 
@@ -388,7 +814,11 @@ fn rectangle_wider_and_longer_comparator(first: &Rectangle, second: &Rectangle)
     }
 }
 
-fn vertex_closest_to_top_and_left_comparator(first: &Vertex, second: &Vertex) -> Ordering {
+pub fn rectangle_bigger_area_comparator(first: &Rectangle, second: &Rectangle) -> Ordering {
+    get_area(second.width, second.length).cmp(&get_area(first.width, first.length))
+}
+
+pub fn vertex_closest_to_top_and_left_comparator(first: &Vertex, second: &Vertex) -> Ordering {
     if first.pos_y < second.pos_y || (first.pos_y == second.pos_y && first.pos_x < second.pos_x) {
         Ordering::Less
     } else if first.pos_y == second.pos_y && first.pos_x == second.pos_x {
@@ -398,7 +828,7 @@ fn vertex_closest_to_top_and_left_comparator(first: &Vertex, second: &Vertex) ->
     }
 }
 
-fn is_within_boundaries(
+pub fn is_within_boundaries(
     subject: &PositionedRectangle,
     max_width: i32,
     max_length: i32
@@ -411,7 +841,7 @@ fn is_within_boundaries(
         subject_vertices.top_left_vertex.pos_y >= 0
 }
 
-fn intersect(
+pub fn intersect(
     first: &PositionedRectangle,
     second: &PositionedRectangle,
     spacing: i32
@@ -432,12 +862,14 @@ fn intersect(
         second_vertices.top_left_vertex.pos_x < first_vertices.bottom_rigth_vertex.pos_x + spacing
 }
 
-fn rectangle_maximum_y_comparator(first: &PositionedRectangle, second: &PositionedRectangle) -> Ordering {
+pub fn rectangle_maximum_y_comparator(first: &PositionedRectangle, second: &PositionedRectangle) -> Ordering {
     (first.top_left_vertex.pos_y + first.length).cmp(&(second.top_left_vertex.pos_y + second.length))
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{models::fabric::FabricGrain, services::cutting_lines_service::define_cutting_lines, test_support::get_cut_disposition_input};
+
     use super::*;
 
 /*
@@ -480,27 +912,49 @@ mod tests {
                 Vertex { 
                     pos_x: 0, 
                     pos_y: 0 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
         let rect1 = Rectangle {
             id: 2,
             width: 120,
-            length: 40
+            length: 40,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
         };
         let rect2 = Rectangle {
             id: 3,
             width: 40,
-            length: 70
+            length: 70,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
         };
         let rect3 = Rectangle {
             id: 4,
             width: 20,
-            length: 40
+            length: 40,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
         };
         let rect_no_fit = Rectangle {
             id: 5,
             width: 20,
-            length: 40
+            length: 40,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
         };
 
         let rectangles_list = vec![
@@ -523,8 +977,12 @@ mod tests {
             max_length,
             spacing, 
             max_width, 
-            &rectangles_list, 
-            &prohibited_area_list
+            false,
+            get_rectangle_instance_list(&rectangles_list), 
+            &prohibited_area_list,
+            &[],
+            None,
+            CollisionSearch::SpatialIndex
         );
 
         // assertion
@@ -552,12 +1010,134 @@ mod tests {
 
     }
 
+    /*
+    fabric 100x50, the 30x80 piece only fits lying down
+    <svg width="100" height="50">
+        <rect x="0" y="0" width="80" height="30" style="fill:green;stroke:black;" />
+        <rect x="80" y="0" width="20" height="40" style="fill:green;stroke:black;" />
+    </svg>
+    */
+    #[test]
+    fn organize_main_rectangles_with_rotation_test() {
+        let rect_rotate = Rectangle {
+            id: 1,
+            width: 30,
+            length: 80,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let rect_fixed = Rectangle {
+            id: 2,
+            width: 20,
+            length: 40,
+            allow_rotation: Some(false),
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let rect_fixed_no_fit = Rectangle {
+            id: 3,
+            width: 30,
+            length: 80,
+            allow_rotation: Some(false),
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+
+        let rectangles_list = vec![
+            rect_rotate.clone(),
+            rect_fixed.clone(),
+            rect_fixed_no_fit.clone(),
+        ];
+
+        // action
+        let main_rectangle_organized = organize_main_rectangles(
+            50,
+            0,
+            100,
+            true,
+            get_rectangle_instance_list(&rectangles_list),
+            &[],
+            &[],
+            None,
+            CollisionSearch::SpatialIndex
+        );
+
+        // assertion
+        let p_rect_rotate = PositionedRectangle::new_from_rotated_rectangle_and_vertex(&rect_rotate, &Vertex { pos_x: 0, pos_y: 0 });
+        let p_rect_fixed = PositionedRectangle::new_from_rectangle_and_vertex(&rect_fixed, &Vertex { pos_x: 80, pos_y: 0 });
+
+        assert_eq!(80, p_rect_rotate.width);
+        assert_eq!(30, p_rect_rotate.length);
+        assert!(main_rectangle_organized.positioned_rectangles_list.iter().any(|item| item.equals(&p_rect_rotate)));
+        assert!(main_rectangle_organized.positioned_rectangles_list.iter().any(|item| item.equals(&p_rect_fixed)));
+        assert!(main_rectangle_organized.unused_rectangles_list.iter().any(|item| item.equals(&rect_fixed_no_fit)));
+        assert_eq!(40, main_rectangle_organized.length_used);
+    }
+
+    #[test]
+    fn organize_main_rectangles_with_quantity_test() {
+        let rect = Rectangle {
+            id: 1,
+            width: 40,
+            length: 20,
+            allow_rotation: None,
+            quantity: 7,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+
+        let rectangles_list = vec![
+            rect.clone()
+        ];
+
+        // action
+        let main_rectangle_organized = organize_main_rectangles(
+            50,
+            0,
+            100,
+            false,
+            get_rectangle_instance_list(&rectangles_list),
+            &[],
+            &[],
+            None,
+            CollisionSearch::SpatialIndex
+        );
+
+        // assertion
+        // 2 columns and 2 rows of 40x20 fit in 100x50
+        assert_eq!(4, main_rectangle_organized.positioned_rectangles_list.len());
+        assert!(main_rectangle_organized.positioned_rectangles_list.iter().all(|item| item.id == 1));
+        assert_eq!(
+            vec![Rectangle { quantity: 3, ..rect.clone() }], 
+            main_rectangle_organized.unused_rectangles_list
+        );
+
+        let piece_count_list = count_pieces(&rectangles_list, &main_rectangle_organized.positioned_rectangles_list);
+        assert_eq!(
+            vec![PieceCount { id: 1, quantity: 7, placed_quantity: 4, unplaced_quantity: 3 }],
+            piece_count_list
+        );
+    }
+
     #[test]
     fn organize_showcase_test() {
         let showcase = Rectangle {
             id: 1,
             width: 10,
-            length: 10
+            length: 10,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
         };
 
         let positioned_rectangles_list = vec![
@@ -568,7 +1148,9 @@ mod tests {
                 top_left_vertex: 
                     Vertex { 
                         pos_x: 0, 
-                        pos_y: 60 }
+                        pos_y: 60 },
+                rotated: false,
+                margin: None,
             },
             PositionedRectangle {
                 id: 3,
@@ -577,7 +1159,9 @@ mod tests {
                 top_left_vertex: Vertex { 
                     pos_x: 160, 
                     pos_y: 0 
-                }
+                },
+                rotated: false,
+                margin: None,
             },
             PositionedRectangle {
                 id: 4,
@@ -586,7 +1170,9 @@ mod tests {
                 top_left_vertex: Vertex { 
                     pos_x: 130, 
                     pos_y: 60 
-                }
+                },
+                rotated: false,
+                margin: None,
             }
         ];
 
@@ -599,7 +1185,9 @@ mod tests {
                     Vertex { 
                         pos_x: 0, 
                         pos_y: 0 
-                    }
+                    },
+                rotated: false,
+                margin: None,
             }
         ];
 
@@ -622,13 +1210,14 @@ mod tests {
 
         // action
         let showcase_organized = organize_showcase_rectangles(
-            showcase, 
+            &[showcase], 
             spacing, 
             max_width, 
             &prohibited_area_list, 
             &possible_vertices, 
             &positioned_rectangles_list, 
-            length_used
+            length_used,
+            CollisionSearch::SpatialIndex
         );
 
         //assertion
@@ -641,7 +1230,9 @@ mod tests {
                     Vertex {
                         pos_x: 160,
                         pos_y: 80
-                    }
+                    },
+                rotated: false,
+                margin: None,
             },
             PositionedRectangle {
                 id: 1,
@@ -651,7 +1242,9 @@ mod tests {
                     Vertex {
                         pos_x: 180,
                         pos_y: 80
-                    }
+                    },
+                rotated: false,
+                margin: None,
             }
         };
 
@@ -659,6 +1252,552 @@ mod tests {
 
     }
 
+    /*
+    fabric 100x50 with a 60x50 piece, leaves a 40x50 hole on the rigth,
+    the small showcase is limited to 3
+    <svg width="100" height="50">
+        <rect x="0" y="0" width="60" height="50" style="fill:green;stroke:black;" />
+        <rect x="60" y="0" width="30" height="30" style="fill:yellow;stroke:black;" />
+        <rect x="90" y="0" width="10" height="10" style="fill:yellow;stroke:black;" />
+        <rect x="90" y="10" width="10" height="10" style="fill:yellow;stroke:black;" />
+        <rect x="90" y="20" width="10" height="10" style="fill:yellow;stroke:black;" />
+    </svg>
+    */
+    #[test]
+    fn organize_multiple_showcase_test() {
+        let small_showcase = Rectangle {
+            id: 2,
+            width: 10,
+            length: 10,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: Some(3),
+            match_pattern: false,
+            margin: None,
+        };
+
+        let big_showcase = Rectangle {
+            id: 3,
+            width: 30,
+            length: 30,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+
+        let positioned_rectangles_list = vec![
+            PositionedRectangle {
+                id: 1,
+                width: 60,
+                length: 50,
+                top_left_vertex: Vertex { pos_x: 0, pos_y: 0 },
+                rotated: false,
+                margin: None,
+            }
+        ];
+
+        let possible_vertices = vec![
+            Vertex { pos_x: 60, pos_y: 0 },
+            Vertex { pos_x: 0, pos_y: 50 },
+        ];
+
+        // action
+        let showcase_organized = organize_showcase_rectangles(
+            &[small_showcase.clone(), big_showcase.clone()], 
+            0, 
+            100, 
+            &[], 
+            &possible_vertices, 
+            &positioned_rectangles_list, 
+            50,
+            CollisionSearch::SpatialIndex
+        );
+
+        // assertion
+        let showcase_organized_expect = vec![
+            PositionedRectangle::new_from_rectangle_and_vertex(&big_showcase, &Vertex { pos_x: 60, pos_y: 0 }),
+            PositionedRectangle::new_from_rectangle_and_vertex(&small_showcase, &Vertex { pos_x: 90, pos_y: 0 }),
+            PositionedRectangle::new_from_rectangle_and_vertex(&small_showcase, &Vertex { pos_x: 90, pos_y: 10 }),
+            PositionedRectangle::new_from_rectangle_and_vertex(&small_showcase, &Vertex { pos_x: 90, pos_y: 20 }),
+        ];
+
+        assert_eq!(showcase_organized_expect, showcase_organized);
+
+        let showcase_count_list = count_showcases(&[small_showcase, big_showcase], &showcase_organized);
+        assert_eq!(
+            vec![
+                ShowcaseCount { id: 2, placed_quantity: 3, used_area: 300 },
+                ShowcaseCount { id: 3, placed_quantity: 1, used_area: 900 },
+            ],
+            showcase_count_list
+        );
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- first sheet -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+        <rect x="0" y="0" width="100" height="50" style="fill:black;stroke:black;" />
+        <rect x="0" y="50" width="100" height="40" style="fill:green;stroke:black;" />
+
+        <!-- second sheet -->
+        <rect x="200" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+        <rect x="200" y="0" width="100" height="40" style="fill:green;stroke:black;" />
+        <rect x="200" y="40" width="100" height="40" style="fill:green;stroke:black;" />
+    </svg>
+
+ */
+    #[test]
+    fn organize_disposition_sheets_test() {
+        let rect = Rectangle {
+            id: 2,
+            width: 100,
+            length: 40,
+            allow_rotation: None,
+            quantity: 3,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let too_big_rect = Rectangle {
+            id: 3,
+            width: 110,
+            length: 10,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let showcase = Rectangle {
+            id: 4,
+            width: 100,
+            length: 10,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: Some(1),
+            match_pattern: false,
+            margin: None,
+        };
+
+        let mut cut_disposition_input = CutDispositionInput {
+            prohibited_area_list: vec![
+                PositionedRectangle {
+                    id: 1,
+                    width: 100,
+                    length: 50,
+                    top_left_vertex: Vertex { pos_x: 0, pos_y: 0 },
+                    rotated: false,
+                    margin: None,
+                }
+            ],
+            showcase_list: vec![showcase.clone()],
+            multi_sheet: true,
+            ..get_cut_disposition_input(vec![rect.clone(), too_big_rect.clone()])
+        };
+
+        let sheet_output_list = organize_disposition_sheets(&cut_disposition_input, &NoOrganizeObserver);
+
+        assert_eq!(sheet_output_list.len(), 2);
+
+        assert_eq!(
+            sheet_output_list[0].positioned_rectangles_list,
+            vec![PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 0, pos_y: 50 })]
+        );
+        assert!(sheet_output_list[0].showcase_rectangles_located_list.is_empty());
+
+        assert_eq!(
+            sheet_output_list[1].positioned_rectangles_list,
+            vec![
+                PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 0, pos_y: 0 }),
+                PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 0, pos_y: 40 }),
+            ]
+        );
+        assert!(sheet_output_list[1].prohibited_area_list.is_empty());
+        assert_eq!(sheet_output_list[1].unused_rectangles_list, vec![too_big_rect.clone()]);
+
+        cut_disposition_input.multi_sheet = false;
+
+        let sheet_output_list = organize_disposition_sheets(&cut_disposition_input, &NoOrganizeObserver);
+
+        assert_eq!(sheet_output_list.len(), 1);
+        assert_eq!(
+            sheet_output_list[0].unused_rectangles_list,
+            vec![too_big_rect, Rectangle { quantity: 2, ..rect }]
+        );
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- the piece only fits the fabric with a quarter turn -->
+        <rect x="0" y="0" width="50" height="150" style="fill:grey;stroke:black;" />
+        <rect x="0" y="0" width="40" height="100" style="fill:green;stroke:black;" />
+
+        <!-- velvet, the piece is not placed -->
+        <rect x="200" y="0" width="50" height="150" style="fill:grey;stroke:black;" />
+    </svg>
+
+ */
+    #[test]
+    fn organize_disposition_fabric_grain_test() {
+        let rect = Rectangle {
+            id: 1,
+            width: 100,
+            length: 40,
+            allow_rotation: Some(true),
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+
+        let mut cut_disposition_input = CutDispositionInput {
+            max_length: 150,
+            defined_width: 50,
+            allow_rotation: true,
+            ..get_cut_disposition_input(vec![rect.clone()])
+        };
+
+        let output = organize_disposition(&cut_disposition_input, 1, &NoOrganizeObserver);
+
+        assert_eq!(output.positioned_rectangles_list.len(), 1);
+        assert!(output.positioned_rectangles_list[0].rotated);
+        assert_eq!(output.positioned_rectangles_list[0].width, 40);
+        assert_eq!(output.positioned_rectangles_list[0].length, 100);
+
+        for fabric_grain in [FabricGrain::RotationForbidden, FabricGrain::HalfTurnOnly] {
+            cut_disposition_input.fabric_grain = fabric_grain;
+
+            let output = organize_disposition(&cut_disposition_input, 1, &NoOrganizeObserver);
+
+            assert!(output.positioned_rectangles_list.is_empty());
+            assert_eq!(output.unused_rectangles_list.len(), 1);
+            assert_eq!(output.unused_rectangles_list[0].id, rect.id);
+        }
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="200" height="180">
+        <!-- repeats of the print every 30 along the width, starting at 5, and every 25 along the length -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+        <line x1="5" y1="0" x2="5" y2="100" style="stroke:white;" />
+        <line x1="35" y1="0" x2="35" y2="100" style="stroke:white;" />
+        <line x1="65" y1="0" x2="65" y2="100" style="stroke:white;" />
+        <line x1="95" y1="0" x2="95" y2="100" style="stroke:white;" />
+
+        <!-- with the bottom left vertex strategy, the plain piece takes the origin
+            and the pieces that match the pattern start on the repeats -->
+        <rect x="0" y="0" width="30" height="20" style="fill:blue;stroke:black;" />
+        <rect x="35" y="0" width="20" height="20" style="fill:green;stroke:black;" />
+        <rect x="65" y="0" width="20" height="20" style="fill:green;stroke:black;" />
+    </svg>
+
+ */
+    #[test]
+    fn organize_disposition_pattern_repeat_test() {
+        let plain_rect = Rectangle {
+            id: 1,
+            width: 30,
+            length: 20,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let pattern_rect = Rectangle {
+            id: 2,
+            width: 20,
+            length: 20,
+            allow_rotation: Some(true),
+            quantity: 2,
+            max_quantity: None,
+            match_pattern: true,
+            margin: None,
+        };
+
+        let pattern_repeat = PatternRepeat {
+            repeat_width: Some(30),
+            repeat_length: Some(25),
+            offset_x: 5,
+            offset_y: 0,
+        };
+
+        for nesting_strategy in [NestingStrategyType::BottomLeftVertex, NestingStrategyType::MaxRects, NestingStrategyType::Guillotine] {
+            let cut_disposition_input = CutDispositionInput {
+                allow_rotation: true,
+                nesting_strategy: nesting_strategy.clone(),
+                pattern_repeat: Some(pattern_repeat.clone()),
+                ..get_cut_disposition_input(vec![plain_rect.clone(), pattern_rect.clone()])
+            };
+
+            let output = organize_disposition(&cut_disposition_input, 1, &NoOrganizeObserver);
+
+            assert!(output.unused_rectangles_list.is_empty(), "{:?}", nesting_strategy);
+
+            let pattern_positioned_list: Vec<&PositionedRectangle> = output.positioned_rectangles_list
+                .iter()
+                .filter(|positioned| positioned.id == pattern_rect.id)
+                .collect();
+
+            assert_eq!(pattern_positioned_list.len(), 2);
+            assert!(
+                pattern_positioned_list.iter().all(
+                    |positioned|
+                    !positioned.rotated &&
+                    (positioned.top_left_vertex.pos_x - 5) % 30 == 0 &&
+                    positioned.top_left_vertex.pos_y % 25 == 0
+                ),
+                "{:?}",
+                nesting_strategy
+            );
+
+            for (index, positioned) in output.positioned_rectangles_list.iter().enumerate() {
+                assert!(
+                    !output.positioned_rectangles_list[index + 1..].iter().any(|other| intersect(positioned, other, 0)),
+                    "{:?}",
+                    nesting_strategy
+                );
+            }
+
+            if nesting_strategy == NestingStrategyType::BottomLeftVertex {
+                assert_eq!(
+                    output.positioned_rectangles_list,
+                    vec![
+                        PositionedRectangle::new_from_rectangle_and_vertex(&plain_rect, &Vertex { pos_x: 0, pos_y: 0 }),
+                        PositionedRectangle::new_from_rectangle_and_vertex(&pattern_rect, &Vertex { pos_x: 35, pos_y: 0 }),
+                        PositionedRectangle::new_from_rectangle_and_vertex(&pattern_rect, &Vertex { pos_x: 65, pos_y: 0 }),
+                    ]
+                );
+            }
+        }
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="200" height="180">
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+        <!-- selvage and top edge -->
+        <rect x="0" y="0" width="10" height="100" style="fill:darkgrey;stroke:black;" />
+        <rect x="80" y="0" width="20" height="100" style="fill:darkgrey;stroke:black;" />
+        <rect x="0" y="0" width="100" height="5" style="fill:darkgrey;stroke:black;" />
+        <rect x="0" y="0" width="50" height="10" style="fill:black;stroke:black;" />
+
+        <rect x="10" y="10" width="70" height="40" style="fill:green;stroke:black;" />
+        <rect x="10" y="50" width="70" height="40" style="fill:green;stroke:black;" />
+    </svg>
+
+ */
+    #[test]
+    fn organize_disposition_edge_margins_test() {
+        let rect = Rectangle {
+            id: 1,
+            width: 70,
+            length: 40,
+            allow_rotation: None,
+            quantity: 2,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let wide_rect = Rectangle {
+            id: 2,
+            width: 80,
+            length: 10,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let prohibited_area = PositionedRectangle {
+            id: 3,
+            width: 50,
+            length: 10,
+            top_left_vertex: Vertex { pos_x: 0, pos_y: 0 },
+            rotated: false,
+            margin: None,
+        };
+
+        let cut_disposition_input = CutDispositionInput {
+            prohibited_area_list: vec![prohibited_area.clone()],
+            edge_margins: EdgeMargins { left: 10, right: 20, top: 5 },
+            ..get_cut_disposition_input(vec![rect.clone(), wide_rect.clone()])
+        };
+
+        let output = organize_disposition(&cut_disposition_input, 1, &NoOrganizeObserver);
+
+        assert_eq!(
+            output.positioned_rectangles_list,
+            vec![
+                PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 10, pos_y: 10 }),
+                PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 10, pos_y: 50 }),
+            ]
+        );
+        assert_eq!(output.unused_rectangles_list, vec![wide_rect]);
+        assert_eq!(output.prohibited_area_list, vec![prohibited_area]);
+        assert_eq!(output.length_used, 90);
+        // the usable band is 70 wide and 85 long
+        assert_eq!(output.total_area, 5950);
+        assert_eq!(output.used_area, 5600);
+        assert_eq!(output.defined_width, 100);
+        assert_eq!(output.edge_margins, EdgeMargins { left: 10, right: 20, top: 5 });
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="200" height="180">
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <!-- the pieces without margin butt up and share the cut at x = 20,
+            the piece with the margin of 15 keeps it from the neighbour even with the spacing of 10 -->
+        <rect x="0" y="0" width="20" height="20" style="fill:green;stroke:black;" />
+        <rect x="20" y="0" width="20" height="20" style="fill:green;stroke:black;" />
+        <rect x="55" y="0" width="20" height="20" style="fill:blue;stroke:black;" />
+    </svg>
+
+ */
+    #[test]
+    fn organize_disposition_piece_margin_test() {
+        let butt_rect = Rectangle {
+            id: 1,
+            width: 20,
+            length: 20,
+            allow_rotation: None,
+            quantity: 2,
+            max_quantity: None,
+            match_pattern: false,
+            margin: Some(0),
+        };
+        let thick_rect = Rectangle {
+            id: 2,
+            width: 20,
+            length: 20,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: Some(15),
+        };
+
+        for nesting_strategy in [NestingStrategyType::BottomLeftVertex, NestingStrategyType::MaxRects, NestingStrategyType::Guillotine] {
+            let cut_disposition_input = CutDispositionInput {
+                spacing: Some(10),
+                nesting_strategy: nesting_strategy.clone(),
+                ..get_cut_disposition_input(vec![butt_rect.clone(), thick_rect.clone()])
+            };
+
+            let output = organize_disposition(&cut_disposition_input, 1, &NoOrganizeObserver);
+
+            assert!(output.unused_rectangles_list.is_empty(), "{:?}", nesting_strategy);
+            assert_eq!(output.positioned_rectangles_list.len(), 3);
+
+            for (index, positioned) in output.positioned_rectangles_list.iter().enumerate() {
+                assert!(
+                    !output.positioned_rectangles_list[index + 1..].iter().any(
+                        |other| intersect(positioned, other, positioned.get_spacing(10).max(other.get_spacing(10)))
+                    ),
+                    "{:?}",
+                    nesting_strategy
+                );
+            }
+
+            if nesting_strategy == NestingStrategyType::BottomLeftVertex {
+                assert_eq!(
+                    output.positioned_rectangles_list,
+                    vec![
+                        PositionedRectangle::new_from_rectangle_and_vertex(&butt_rect, &Vertex { pos_x: 0, pos_y: 0 }),
+                        PositionedRectangle::new_from_rectangle_and_vertex(&butt_rect, &Vertex { pos_x: 20, pos_y: 0 }),
+                        PositionedRectangle::new_from_rectangle_and_vertex(&thick_rect, &Vertex { pos_x: 55, pos_y: 0 }),
+                    ]
+                );
+
+                let cutting_lines = define_cutting_lines(output.positioned_rectangles_list.clone(), None, Vec::new(), &[]);
+
+                // the thick piece is cut half of its margin away from its sides
+                assert_eq!(
+                    cutting_lines.vertical_lines.iter().map(|line| line.start.pos_x).collect::<Vec<i32>>(),
+                    vec![82, 48, 40, 20, 0]
+                );
+            }
+        }
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="200" height="180">
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <!-- the pinned piece stays where the operator put it and the other pieces keep the spacing of 5 from it -->
+        <rect x="35" y="0" width="30" height="30" style="fill:orange;stroke:black;" />
+        <rect x="0" y="0" width="30" height="30" style="fill:green;stroke:black;" />
+        <rect x="70" y="0" width="30" height="30" style="fill:green;stroke:black;" />
+        <rect x="0" y="35" width="30" height="30" style="fill:green;stroke:black;" />
+    </svg>
+
+ */
+    #[test]
+    fn organize_disposition_pinned_piece_test() {
+        let rect = Rectangle {
+            id: 1,
+            width: 30,
+            length: 30,
+            allow_rotation: None,
+            quantity: 3,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let pinned_piece = PositionedRectangle {
+            id: 2,
+            width: 30,
+            length: 30,
+            top_left_vertex: Vertex { pos_x: 35, pos_y: 0 },
+            rotated: false,
+            margin: None,
+        };
+
+        for nesting_strategy in [NestingStrategyType::BottomLeftVertex, NestingStrategyType::MaxRects, NestingStrategyType::Guillotine] {
+            let cut_disposition_input = CutDispositionInput {
+                spacing: Some(5),
+                nesting_strategy: nesting_strategy.clone(),
+                pinned_piece_list: vec![pinned_piece.clone()],
+                ..get_cut_disposition_input(vec![rect.clone()])
+            };
+
+            let output = organize_disposition(&cut_disposition_input, 1, &NoOrganizeObserver);
+
+            assert!(output.unused_rectangles_list.is_empty(), "{:?}", nesting_strategy);
+            assert_eq!(output.positioned_rectangles_list.len(), 4);
+            assert!(output.positioned_rectangles_list.contains(&pinned_piece));
+
+            for (index, positioned) in output.positioned_rectangles_list.iter().enumerate() {
+                assert!(
+                    !output.positioned_rectangles_list[index + 1..].iter().any(|other| intersect(positioned, other, 5)),
+                    "{:?}",
+                    nesting_strategy
+                );
+            }
+
+            if nesting_strategy == NestingStrategyType::BottomLeftVertex {
+                assert_eq!(
+                    output.positioned_rectangles_list,
+                    vec![
+                        pinned_piece.clone(),
+                        PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 0, pos_y: 0 }),
+                        PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 70, pos_y: 0 }),
+                        PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 0, pos_y: 35 }),
+                    ]
+                );
+            }
+        }
+    }
+
     /* 
     Assert that vertices are generated correctly
     for spacing = 0
@@ -690,6 +1829,8 @@ mod tests {
             width: 5, 
             length: 7, 
             top_left_vertex,
+            rotated: false,
+            margin: None,
         };
 
         // expect
@@ -755,6 +1896,8 @@ mod tests {
             width: 5, 
             length: 7, 
             top_left_vertex,
+            rotated: false,
+            margin: None,
         };
 
         let spacing = 3;
@@ -782,27 +1925,52 @@ mod tests {
             Rectangle {
                 id: 1,
                 width: 10,
-                length: 2
+                length: 2,
+                allow_rotation: None,
+                quantity: 1,
+                max_quantity: None,
+                match_pattern: false,
+                margin: None,
             },
             Rectangle {
                 id: 2,
                 width: 10,
-                length: 5
+                length: 5,
+                allow_rotation: None,
+                quantity: 1,
+                max_quantity: None,
+                match_pattern: false,
+                margin: None,
             },
             Rectangle {
                 id: 3,
                 width: 3,
-                length: 2
+                length: 2,
+                allow_rotation: None,
+                quantity: 1,
+                max_quantity: None,
+                match_pattern: false,
+                margin: None,
             },
             Rectangle {
                 id: 3,
                 width: 5,
-                length: 10
+                length: 10,
+                allow_rotation: None,
+                quantity: 1,
+                max_quantity: None,
+                match_pattern: false,
+                margin: None,
             },
             Rectangle {
                 id: 4,
                 width: 10,
-                length: 2
+                length: 2,
+                allow_rotation: None,
+                quantity: 1,
+                max_quantity: None,
+                match_pattern: false,
+                margin: None,
             }
         ];
 
@@ -811,27 +1979,52 @@ mod tests {
             Rectangle {
                 id: 2,
                 width: 10,
-                length: 5
+                length: 5,
+                allow_rotation: None,
+                quantity: 1,
+                max_quantity: None,
+                match_pattern: false,
+                margin: None,
             },
             Rectangle {
                 id: 1,
                 width: 10,
-                length: 2
+                length: 2,
+                allow_rotation: None,
+                quantity: 1,
+                max_quantity: None,
+                match_pattern: false,
+                margin: None,
             },
             Rectangle {
                 id: 4,
                 width: 10,
-                length: 2
+                length: 2,
+                allow_rotation: None,
+                quantity: 1,
+                max_quantity: None,
+                match_pattern: false,
+                margin: None,
             },
             Rectangle {
                 id: 3,
                 width: 5,
-                length: 10
+                length: 10,
+                allow_rotation: None,
+                quantity: 1,
+                max_quantity: None,
+                match_pattern: false,
+                margin: None,
             },
             Rectangle {
                 id: 3,
                 width: 3,
-                length: 2
+                length: 2,
+                allow_rotation: None,
+                quantity: 1,
+                max_quantity: None,
+                match_pattern: false,
+                margin: None,
             }
         ];
 
@@ -924,7 +2117,9 @@ mod tests {
                 Vertex { 
                     pos_x: -1, 
                     pos_y: 0 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
         
         let partially_outside_top = 
@@ -936,7 +2131,9 @@ mod tests {
                 Vertex { 
                     pos_x: 0, 
                     pos_y: -1 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let partially_outside_rigth = 
@@ -948,7 +2145,9 @@ mod tests {
                 Vertex { 
                     pos_x: 4, 
                     pos_y: 0 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let partially_outside_bottom = 
@@ -960,7 +2159,9 @@ mod tests {
                 Vertex { 
                     pos_x: 0, 
                     pos_y: 9
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         
@@ -973,7 +2174,9 @@ mod tests {
                 Vertex { 
                     pos_x: 4, 
                     pos_y: 9 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         
@@ -986,7 +2189,9 @@ mod tests {
                 Vertex { 
                     pos_x: 1, 
                     pos_y: 4 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let inside_left_top_corner = 
@@ -998,7 +2203,9 @@ mod tests {
                 Vertex { 
                     pos_x: 0, 
                     pos_y: 0 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let inside_rigth_bottom_corner = 
@@ -1010,7 +2217,9 @@ mod tests {
                 Vertex { 
                     pos_x: 3, 
                     pos_y: 8 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         // assert
@@ -1043,7 +2252,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 50 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let intersect_left = 
@@ -1055,7 +2266,9 @@ mod tests {
                 Vertex { 
                     pos_x: 20, 
                     pos_y: 50
-                }
+                },
+            rotated: false,
+            margin: None,
         };
         
         let intersect_top = 
@@ -1067,7 +2280,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 30 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let intersect_rigth = 
@@ -1079,7 +2294,9 @@ mod tests {
                 Vertex { 
                     pos_x: 50, 
                     pos_y: 50 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let intersect_bottom = 
@@ -1091,7 +2308,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 90
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         // inside
@@ -1105,7 +2324,9 @@ mod tests {
                 Vertex { 
                     pos_x: 35, 
                     pos_y: 60
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let intersect_inside_top_left = 
@@ -1117,7 +2338,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 50
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let intersect_inside_bottom_rigth = 
@@ -1129,7 +2352,9 @@ mod tests {
                 Vertex { 
                     pos_x: 40, 
                     pos_y: 70
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         // no intersect
@@ -1145,7 +2370,9 @@ mod tests {
                 Vertex { 
                     pos_x: 0, 
                     pos_y: 50
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let top = 
@@ -1157,7 +2384,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 10 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let rigth = 
@@ -1169,7 +2398,9 @@ mod tests {
                 Vertex { 
                     pos_x: 70, 
                     pos_y: 50 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let bottom = 
@@ -1181,7 +2412,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 110
-                }
+                },
+            rotated: false,
+            margin: None,
         };
         
 
@@ -1196,7 +2429,9 @@ mod tests {
                 Vertex { 
                     pos_x: 10, 
                     pos_y: 50
-                }
+                },
+            rotated: false,
+            margin: None,
         };
         
         let top_touch = 
@@ -1208,7 +2443,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 20 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let rigth_touch = 
@@ -1220,7 +2457,9 @@ mod tests {
                 Vertex { 
                     pos_x: 60, 
                     pos_y: 50 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let bottom_touch = 
@@ -1232,7 +2471,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 100
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         // assertion
@@ -1274,7 +2515,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 50 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let intersect_left = 
@@ -1286,7 +2529,9 @@ mod tests {
                 Vertex { 
                     pos_x: 15, 
                     pos_y: 50
-                }
+                },
+            rotated: false,
+            margin: None,
         };
         
         let intersect_top = 
@@ -1298,7 +2543,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 25
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let intersect_rigth = 
@@ -1310,7 +2557,9 @@ mod tests {
                 Vertex { 
                     pos_x: 55, 
                     pos_y: 50 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let intersect_bottom = 
@@ -1322,7 +2571,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 95
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         // inside
@@ -1336,7 +2587,9 @@ mod tests {
                 Vertex { 
                     pos_x: 35, 
                     pos_y: 60
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let intersect_inside_top_left = 
@@ -1348,7 +2601,9 @@ mod tests {
                 Vertex { 
                     pos_x: 25, 
                     pos_y: 45
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let intersect_inside_bottom_rigth = 
@@ -1360,7 +2615,9 @@ mod tests {
                 Vertex { 
                     pos_x: 45, 
                     pos_y: 75
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
 
@@ -1377,7 +2634,9 @@ mod tests {
                 Vertex { 
                     pos_x: 0, 
                     pos_y: 50
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let top = 
@@ -1389,7 +2648,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 10 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let rigth = 
@@ -1401,7 +2662,9 @@ mod tests {
                 Vertex { 
                     pos_x: 70, 
                     pos_y: 50 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let bottom = 
@@ -1413,7 +2676,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 110
-                }
+                },
+            rotated: false,
+            margin: None,
         };
         
 
@@ -1428,7 +2693,9 @@ mod tests {
                 Vertex { 
                     pos_x: 5, 
                     pos_y: 50
-                }
+                },
+            rotated: false,
+            margin: None,
         };
         
         let top_touch = 
@@ -1440,7 +2707,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 15 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let rigth_touch = 
@@ -1452,7 +2721,9 @@ mod tests {
                 Vertex { 
                     pos_x: 65, 
                     pos_y: 50 
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         let bottom_touch = 
@@ -1464,7 +2735,9 @@ mod tests {
                 Vertex { 
                     pos_x: 30, 
                     pos_y: 105
-                }
+                },
+            rotated: false,
+            margin: None,
         };
 
         // assertion
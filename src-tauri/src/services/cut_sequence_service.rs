@@ -0,0 +1,463 @@
+use std::cmp::Ordering;
+
+use crate::models::{cut_disposition::Vertex, cutting_lines::{CutPhase, CutStrategy, CuttingLines, Line}, fixed_point::{Fixed, FIXED_POINT_SCALE}};
+
+// each pass tries every segment of the sequence, it stops earlier when a pass finds nothing shorter
+const MAX_TWO_OPT_PASS_COUNT: u32 = 50;
+
+pub struct CutSequence {
+    pub cut_phase_list: Vec<CutPhase>,
+    // millimetres travelled by the head without cutting (G0), in the order the lines were defined and in the order chosen,
+    // measured from the end of each cut line to the start of the next one
+    pub travel_before: f64,
+    pub travel_after: f64,
+}
+
+// Splits the lines in phases following the cut strategy, then chooses the order and the direction of the cuts of each phase
+// so the head travels as little as possible between the end of a cut and the start of the next one.
+// get_cut_line gives the line the head really cuts, with the extensions added when the program is written
+pub fn sequence_cutting_lines(cutting_lines: CuttingLines, start_position: &Vertex, cut_strategy: CutStrategy, get_cut_line: &dyn Fn(&Line) -> Line) -> CutSequence {
+    let cut_phase_list = get_cut_phase_list(cutting_lines, cut_strategy);
+
+    let travel_before = get_phase_list_travel(&cut_phase_list, start_position, get_cut_line);
+
+    let mut position = start_position.clone();
+    let cut_phase_list: Vec<CutPhase> = cut_phase_list
+        .into_iter()
+        .map(
+            |cut_phase| {
+                let line_list = sequence_line_list(&cut_phase.line_list, &position);
+                if let Some(line) = line_list.last() {
+                    position = line.end.clone();
+                }
+                CutPhase { is_vertical: cut_phase.is_vertical, line_list }
+            }
+        )
+        .collect();
+
+    let travel_after = get_phase_list_travel(&cut_phase_list, start_position, get_cut_line);
+
+    CutSequence {
+        cut_phase_list,
+        travel_before: travel_before / FIXED_POINT_SCALE as f64,
+        travel_after: travel_after / FIXED_POINT_SCALE as f64,
+    }
+}
+
+fn get_cut_phase_list(cutting_lines: CuttingLines, cut_strategy: CutStrategy) -> Vec<CutPhase> {
+    let vertical_phase = CutPhase { is_vertical: true, line_list: cutting_lines.vertical_lines };
+    let horizontal_phase = CutPhase { is_vertical: false, line_list: cutting_lines.horizontal_lines };
+
+    match cut_strategy {
+        CutStrategy::VerticalFirst => vec![vertical_phase, horizontal_phase],
+        CutStrategy::HorizontalFirst => vec![horizontal_phase, vertical_phase],
+        CutStrategy::ByBand => get_band_phase_list(vertical_phase.line_list, horizontal_phase.line_list),
+    }
+}
+
+// Each band goes from one level of the horizontal lines to the next one, its vertical lines are cut and then the
+// horizontal lines under it. The vertical lines that go through a level are split there, a part for each band.
+// is_none_or would need Rust 1.82
+#[allow(clippy::unnecessary_map_or)]
+fn get_band_phase_list(vertical_lines: Vec<Line>, horizontal_lines: Vec<Line>) -> Vec<CutPhase> {
+    let mut level_list: Vec<Fixed> = horizontal_lines.iter().map(|line| line.start.pos_y).collect();
+    level_list.sort();
+    level_list.dedup();
+
+    let vertical_section_list: Vec<Line> = vertical_lines
+        .iter()
+        .flat_map(|line| split_at_level_list(line, &level_list))
+        .collect();
+
+    let mut phase_list = Vec::<CutPhase>::new();
+    let mut push_phase = |is_vertical: bool, line_list: Vec<Line>| {
+        if !line_list.is_empty() {
+            phase_list.push(CutPhase { is_vertical, line_list });
+        }
+    };
+
+    // the vertical lines under the last level are the last band
+    let mut previous_level: Option<Fixed> = None;
+    for level in level_list.iter().copied().map(Some).chain([None]) {
+        push_phase(
+            true,
+            vertical_section_list
+                .iter()
+                .filter(
+                    |line| {
+                        let lower_end = line.start.pos_y.max(line.end.pos_y);
+                        previous_level.map_or(true, |previous_level| lower_end > previous_level) && level.map_or(true, |level| lower_end <= level)
+                    }
+                )
+                .cloned()
+                .collect()
+        );
+
+        if let Some(level) = level {
+            push_phase(false, horizontal_lines.iter().filter(|line| line.start.pos_y == level).cloned().collect());
+        }
+        previous_level = level;
+    }
+    phase_list
+}
+
+fn split_at_level_list(vertical_line: &Line, level_list: &[Fixed]) -> Vec<Line> {
+    let top = vertical_line.start.pos_y.min(vertical_line.end.pos_y);
+    let bottom = vertical_line.start.pos_y.max(vertical_line.end.pos_y);
+
+    let mut point_list = vec![top];
+    point_list.extend(level_list.iter().filter(|level| **level > top && **level < bottom));
+    point_list.push(bottom);
+
+    point_list
+        .windows(2)
+        .map(
+            |point_pair| Line {
+                start: Vertex { pos_x: vertical_line.start.pos_x, pos_y: point_pair[0] },
+                end: Vertex { pos_x: vertical_line.start.pos_x, pos_y: point_pair[1] },
+            }
+        )
+        .collect()
+}
+
+// the shortest of the serpentine and the nearest neighbour, both improved by 2-opt
+fn sequence_line_list(line_list: &[Line], start_position: &Vertex) -> Vec<Line> {
+    [
+        order_by_serpentine(line_list),
+        order_by_nearest_neighbour(line_list, start_position),
+    ]
+    .into_iter()
+    .map(
+        |mut sequence| {
+            improve_by_two_opt(&mut sequence, start_position);
+            sequence
+        }
+    )
+    .min_by(|first, second| get_travel(first, start_position).total_cmp(&get_travel(second, start_position)))
+    .unwrap_or_default()
+}
+
+// lines of the same level one after the other, going back and forth from one level to the next
+fn order_by_serpentine(line_list: &[Line]) -> Vec<Line> {
+    let get_level = |line: &Line| if line.is_vertical() { line.start.pos_x } else { line.start.pos_y };
+    let get_along = |line: &Line| if line.is_vertical() { line.start.pos_y } else { line.start.pos_x };
+
+    let mut sorted_line_list = line_list.to_vec();
+    sorted_line_list.sort_by(
+        |first, second| match get_level(first).cmp(&get_level(second)) {
+            Ordering::Equal => get_along(first).cmp(&get_along(second)),
+            ordering => ordering,
+        }
+    );
+
+    let mut sequence = Vec::<Line>::new();
+    let mut is_forward = true;
+    let mut index = 0;
+    while index < sorted_line_list.len() {
+        let level = get_level(&sorted_line_list[index]);
+        let level_line_list: Vec<Line> = sorted_line_list[index..]
+            .iter()
+            .take_while(|line| get_level(line) == level)
+            .cloned()
+            .collect();
+        index += level_line_list.len();
+
+        if is_forward {
+            sequence.extend(level_line_list);
+        } else {
+            sequence.extend(level_line_list.iter().rev().map(Line::get_reversed));
+        }
+        is_forward = !is_forward;
+    }
+    sequence
+}
+
+// from where the head is, the closest end of the lines left, cut towards the other end
+fn order_by_nearest_neighbour(line_list: &[Line], start_position: &Vertex) -> Vec<Line> {
+    let mut line_left_list = line_list.to_vec();
+    let mut sequence = Vec::<Line>::new();
+    let mut position = start_position.clone();
+
+    while !line_left_list.is_empty() {
+        let (index, line) = line_left_list
+            .iter()
+            .enumerate()
+            .flat_map(|(index, line)| [(index, line.clone()), (index, line.get_reversed())])
+            .min_by(|(_, first), (_, second)| get_distance(&position, &first.start).total_cmp(&get_distance(&position, &second.start)))
+            .expect("the list is not empty");
+
+        line_left_list.remove(index);
+        position = line.end.clone();
+        sequence.push(line);
+    }
+    sequence
+}
+
+// Reverses the segments of the sequence that make the travel shorter, a reversed segment is also cut in the other direction
+fn improve_by_two_opt(sequence: &mut [Line], start_position: &Vertex) {
+    for _ in 0..MAX_TWO_OPT_PASS_COUNT {
+        let mut improved = false;
+
+        for first_index in 0..sequence.len() {
+            for last_index in first_index..sequence.len() {
+                let previous_position = if first_index == 0 { start_position.clone() } else { sequence[first_index - 1].end.clone() };
+
+                let mut gain = get_distance(&previous_position, &sequence[first_index].start) -
+                    get_distance(&previous_position, &sequence[last_index].end);
+                if let Some(next_line) = sequence.get(last_index + 1) {
+                    gain += get_distance(&sequence[last_index].end, &next_line.start) -
+                        get_distance(&sequence[first_index].start, &next_line.start);
+                }
+
+                // the travel is compared in hundredths of a millimetre, smaller gains are rounding
+                if gain > 0.5 {
+                    sequence[first_index..=last_index].reverse();
+                    for line in sequence[first_index..=last_index].iter_mut() {
+                        *line = line.get_reversed();
+                    }
+                    improved = true;
+                }
+            }
+        }
+
+        if !improved {
+            break;
+        }
+    }
+}
+
+// each phase starts where the previous one ended
+fn get_phase_list_travel(phase_list: &[CutPhase], start_position: &Vertex, get_cut_line: &dyn Fn(&Line) -> Line) -> f64 {
+    let mut position = start_position.clone();
+    let mut travel = 0.0;
+    for cut_phase in phase_list {
+        let cut_line_list: Vec<Line> = cut_phase.line_list.iter().map(get_cut_line).collect();
+        travel += get_travel(&cut_line_list, &position);
+        if let Some(line) = cut_line_list.last() {
+            position = line.end.clone();
+        }
+    }
+    travel
+}
+
+fn get_travel(line_list: &[Line], start_position: &Vertex) -> f64 {
+    let mut position = start_position;
+    let mut travel = 0.0;
+    for line in line_list {
+        travel += get_distance(position, &line.start);
+        position = &line.end;
+    }
+    travel
+}
+
+fn get_distance(first: &Vertex, second: &Vertex) -> f64 {
+    let distance_x = (second.pos_x - first.pos_x) as f64;
+    let distance_y = (second.pos_y - first.pos_y) as f64;
+    distance_x.hypot(distance_y)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::cutting_lines_service::get_extended_line;
+
+    use super::*;
+
+    fn create_line(start_x: i32, start_y: i32, end_x: i32, end_y: i32) -> Line {
+        Line {
+            start: Vertex { pos_x: start_x, pos_y: start_y },
+            end: Vertex { pos_x: end_x, pos_y: end_y },
+        }
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- down at x=0, up at x=50, down at x=100, then the horizontal lines back from the bottom -->
+        <line x1="0" y1="0" x2="0" y2="100" style="stroke:red;" />
+        <line x1="50" y1="100" x2="50" y2="0" style="stroke:red;" />
+        <line x1="100" y1="0" x2="100" y2="100" style="stroke:red;" />
+        <line x1="100" y1="100" x2="0" y2="100" style="stroke:blue;" />
+        <line x1="0" y1="0" x2="100" y2="0" style="stroke:blue;" />
+    </svg>
+ */
+    #[test]
+    fn sequence_cutting_lines_test() {
+        let cutting_lines = CuttingLines {
+            vertical_lines: vec![
+                create_line(0, 0, 0, 100),
+                create_line(50, 0, 50, 100),
+                create_line(100, 0, 100, 100),
+            ],
+            horizontal_lines: vec![
+                create_line(0, 0, 100, 0),
+                create_line(0, 100, 100, 100),
+            ],
+        };
+
+        let cut_sequence = sequence_cutting_lines(cutting_lines, &Vertex { pos_x: 0, pos_y: 0 }, CutStrategy::VerticalFirst, &|line| line.clone());
+
+        assert_eq!(
+            cut_sequence.cut_phase_list,
+            vec![
+                CutPhase {
+                    is_vertical: true,
+                    line_list: vec![
+                        create_line(0, 0, 0, 100),
+                        create_line(50, 100, 50, 0),
+                        create_line(100, 0, 100, 100),
+                    ]
+                },
+                CutPhase {
+                    is_vertical: false,
+                    line_list: vec![
+                        create_line(100, 100, 0, 100),
+                        create_line(0, 0, 100, 0),
+                    ]
+                },
+            ]
+        );
+        // 50 + 50 between the vertical lines, 0 to the first horizontal line and 100 up to the last one
+        assert_eq!(cut_sequence.travel_after, 2.0);
+        assert!(cut_sequence.travel_before > cut_sequence.travel_after);
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- the lines and, dashed, the cuts extended by 10 on each end -->
+        <line x1="0" y1="20" x2="0" y2="80" style="stroke:red;" />
+        <line x1="50" y1="80" x2="50" y2="20" style="stroke:red;" />
+        <line x1="0" y1="10" x2="0" y2="90" style="stroke:black;stroke-dasharray:4;" />
+        <line x1="50" y1="90" x2="50" y2="10" style="stroke:black;stroke-dasharray:4;" />
+    </svg>
+ */
+    #[test]
+    fn sequence_cutting_lines_travel_on_cut_lines_test() {
+        let get_cutting_lines = || CuttingLines {
+            vertical_lines: vec![create_line(0, 20, 0, 80), create_line(50, 20, 50, 80)],
+            horizontal_lines: Vec::new(),
+        };
+
+        let cut_sequence = sequence_cutting_lines(get_cutting_lines(), &Vertex { pos_x: 0, pos_y: 0 }, CutStrategy::VerticalFirst, &|line| line.clone());
+        // 20 to the first line and 50 between the lines
+        assert_eq!(cut_sequence.travel_after, 0.7);
+
+        let cut_sequence = sequence_cutting_lines(
+            get_cutting_lines(), 
+            &Vertex { pos_x: 0, pos_y: 0 }, 
+            CutStrategy::VerticalFirst, 
+            &|line| get_extended_line(line, 10, 10, &[], 100)
+        );
+        // the same order, the head goes down 10 earlier and goes up 10 later
+        assert_eq!(
+            cut_sequence.cut_phase_list[0].line_list,
+            vec![create_line(0, 20, 0, 80), create_line(50, 80, 50, 20)]
+        );
+        assert_eq!(cut_sequence.travel_after, 0.6);
+    }
+
+    #[test]
+    fn get_cut_phase_list_horizontal_first_test() {
+        let cutting_lines = CuttingLines {
+            vertical_lines: vec![create_line(0, 0, 0, 100)],
+            horizontal_lines: vec![create_line(0, 0, 100, 0)],
+        };
+
+        assert_eq!(
+            get_cut_phase_list(cutting_lines, CutStrategy::HorizontalFirst),
+            vec![
+                CutPhase { is_vertical: false, line_list: vec![create_line(0, 0, 100, 0)] },
+                CutPhase { is_vertical: true, line_list: vec![create_line(0, 0, 0, 100)] },
+            ]
+        );
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- y=0, then the first band down to y=40 and its line, then the second band down to y=100 and its line -->
+        <line x1="0" y1="0" x2="100" y2="0" style="stroke:blue;" />
+        <line x1="0" y1="0" x2="0" y2="40" style="stroke:red;" />
+        <line x1="100" y1="0" x2="100" y2="40" style="stroke:red;" />
+        <line x1="0" y1="40" x2="100" y2="40" style="stroke:blue;" />
+        <line x1="0" y1="40" x2="0" y2="100" style="stroke:green;" />
+        <line x1="100" y1="40" x2="100" y2="100" style="stroke:green;" />
+        <line x1="50" y1="40" x2="50" y2="100" style="stroke:green;" />
+        <line x1="0" y1="100" x2="100" y2="100" style="stroke:blue;" />
+    </svg>
+ */
+    #[test]
+    fn get_cut_phase_list_by_band_test() {
+        let cutting_lines = CuttingLines {
+            vertical_lines: vec![
+                create_line(0, 0, 0, 100),
+                create_line(100, 0, 100, 100),
+                create_line(50, 40, 50, 100),
+            ],
+            horizontal_lines: vec![
+                create_line(0, 0, 100, 0),
+                create_line(0, 100, 100, 100),
+                create_line(0, 40, 100, 40),
+            ],
+        };
+
+        assert_eq!(
+            get_cut_phase_list(cutting_lines, CutStrategy::ByBand),
+            vec![
+                CutPhase { is_vertical: false, line_list: vec![create_line(0, 0, 100, 0)] },
+                CutPhase { is_vertical: true, line_list: vec![create_line(0, 0, 0, 40), create_line(100, 0, 100, 40)] },
+                CutPhase { is_vertical: false, line_list: vec![create_line(0, 40, 100, 40)] },
+                CutPhase { is_vertical: true, line_list: vec![create_line(0, 40, 0, 100), create_line(100, 40, 100, 100), create_line(50, 40, 50, 100)] },
+                CutPhase { is_vertical: false, line_list: vec![create_line(0, 100, 100, 100)] },
+            ]
+        );
+    }
+
+    // every line is cut once, in one of the directions, and the travel never gets longer
+    #[test]
+    fn sequence_line_list_test() {
+        let line_list: Vec<Line> = (0..12)
+            .map(|index| create_line((index * 37) % 200, (index * 53) % 150, (index * 37) % 200, (index * 53) % 150 + 40))
+            .collect();
+        let start_position = Vertex { pos_x: 0, pos_y: 0 };
+
+        let sequence = sequence_line_list(&line_list, &start_position);
+
+        assert_eq!(sequence.len(), line_list.len());
+        for line in line_list.iter() {
+            assert_eq!(sequence.iter().filter(|sequence_line| *sequence_line == line || sequence_line.get_reversed() == *line).count(), 1);
+        }
+        assert!(get_travel(&sequence, &start_position) <= get_travel(&line_list, &start_position));
+        assert!(get_travel(&sequence, &start_position) <= get_travel(&order_by_serpentine(&line_list), &start_position));
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- starting at x=5, down at x=0, up at x=10 and down at x=100 -->
+        <line x1="0" y1="0" x2="0" y2="100" style="stroke:red;" />
+        <line x1="10" y1="100" x2="10" y2="0" style="stroke:red;" />
+        <line x1="100" y1="0" x2="100" y2="100" style="stroke:red;" />
+    </svg>
+ */
+    #[test]
+    fn improve_by_two_opt_test() {
+        let start_position = Vertex { pos_x: 5, pos_y: 0 };
+        let mut sequence = vec![
+            create_line(0, 0, 0, 100),
+            create_line(100, 0, 100, 100),
+            create_line(10, 0, 10, 100),
+        ];
+
+        improve_by_two_opt(&mut sequence, &start_position);
+
+        assert_eq!(
+            sequence,
+            vec![
+                create_line(0, 0, 0, 100),
+                create_line(10, 100, 10, 0),
+                create_line(100, 0, 100, 100),
+            ]
+        );
+    }
+}
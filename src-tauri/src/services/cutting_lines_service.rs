@@ -1,8 +1,10 @@
-use crate::models::{cut_disposition::{PositionedRectangle, Vertex}, cutting_lines::{CuttingLines, Line, line_vertical_closest_to_left_comparator, line_horizontal_closest_to_top_comparator}};
+use crate::models::{cut_disposition::{PositionedRectangle, Vertex, EdgeMargins}, fixed_point::Fixed, cutting_lines::{CuttingLines, Line, line_vertical_closest_to_left_comparator, line_horizontal_closest_to_top_comparator}};
 
 pub fn define_cutting_lines(
     positioned_rectangle_list: Vec<PositionedRectangle>,
-    textile_separation_line_width: Option<i32>
+    textile_separation_line_width: Option<i32>,
+    trim_line_list: Vec<Line>,
+    prohibited_area_list: &[PositionedRectangle]
 
 ) -> CuttingLines {
 
@@ -12,22 +14,13 @@ pub fn define_cutting_lines(
 
     if let Some(width) = textile_separation_line_width {
         if width > 0 {
-            horizontal_lines.push(
-                Line { 
-                    start: Vertex { 
-                        pos_x: 0, 
-                        pos_y: 0 
-                    }, 
-                    end: Vertex { 
-                        pos_x: width, 
-                        pos_y: 0
-                    }
-                }
-            )
+            horizontal_lines.push(get_textile_separation_line(width))
         }
     };
 
     for positioned_rectangle in positioned_rectangle_list {
+        let positioned_rectangle = get_cut_outline(&positioned_rectangle, textile_separation_line_width);
+
         vertical_lines.push(positioned_rectangle.get_left_line());
         vertical_lines.push(positioned_rectangle.get_rigth_line());
 
@@ -35,9 +28,20 @@ pub fn define_cutting_lines(
         horizontal_lines.push(positioned_rectangle.get_bottom_line());
     }
 
-    let mut vertical_lines_result_list = combine_lines(&vertical_lines, &horizontal_lines);
+    // the trim lines cross the whole fabric, so the sides of the rectangles on them are already cut
+    for trim_line in trim_line_list {
+        if trim_line.is_vertical() {
+            vertical_lines.retain(|line| !trim_line.contains(line));
+            vertical_lines.push(trim_line);
+        } else {
+            horizontal_lines.retain(|line| !trim_line.contains(line));
+            horizontal_lines.push(trim_line);
+        }
+    }
+
+    let mut vertical_lines_result_list = split_by_prohibited_areas(combine_lines(&vertical_lines, &horizontal_lines), prohibited_area_list);
 
-    let mut horizontal_lines_result_list = combine_lines(&horizontal_lines, &vertical_lines);
+    let mut horizontal_lines_result_list = split_by_prohibited_areas(combine_lines(&horizontal_lines, &vertical_lines), prohibited_area_list);
     
     vertical_lines_result_list.sort_by(line_vertical_closest_to_left_comparator);
 
@@ -49,6 +53,239 @@ pub fn define_cutting_lines(
     }
 }
 
+// The margin of a piece is a kerf allowance, the piece is cut half of it away from its sides so the blade does not eat into it.
+// The neighbours are kept at least the margin apart, so the outline never reaches them. It stays inside the fabric when its width is known.
+pub fn get_cut_outline(positioned_rectangle: &PositionedRectangle, fabric_width: Option<i32>) -> PositionedRectangle {
+    let allowance = positioned_rectangle.margin.unwrap_or(0).max(0) / 2;
+    if allowance == 0 {
+        return positioned_rectangle.clone();
+    }
+
+    let left = (positioned_rectangle.top_left_vertex.pos_x - allowance).max(0);
+    let top = (positioned_rectangle.top_left_vertex.pos_y - allowance).max(0);
+    let mut right = positioned_rectangle.top_left_vertex.pos_x + positioned_rectangle.width + allowance;
+    if let Some(fabric_width) = fabric_width.filter(|fabric_width| *fabric_width > 0) {
+        right = right.min(fabric_width);
+    }
+    let bottom = positioned_rectangle.top_left_vertex.pos_y + positioned_rectangle.length + allowance;
+
+    PositionedRectangle {
+        width: right - left,
+        length: bottom - top,
+        top_left_vertex: Vertex { pos_x: left, pos_y: top },
+        ..positioned_rectangle.clone()
+    }
+}
+
+// Separates the sheet from the fabric cut before it
+pub fn get_textile_separation_line(width: i32) -> Line {
+    Line { 
+        start: Vertex { 
+            pos_x: 0, 
+            pos_y: 0 
+        }, 
+        end: Vertex { 
+            pos_x: width, 
+            pos_y: 0
+        }
+    }
+}
+
+// The sides of the fabric and the end of a sheet with a defined length are already free, there is nothing to cut on them
+pub fn remove_fabric_border_lines(cutting_lines: CuttingLines, width: i32, defined_length: Option<i32>) -> CuttingLines {
+    CuttingLines {
+        vertical_lines: cutting_lines.vertical_lines
+            .into_iter()
+            .filter(|line| line.start.pos_x != 0 && line.start.pos_x != width)
+            .collect(),
+        horizontal_lines: cutting_lines.horizontal_lines
+            .into_iter()
+            .filter(|line| defined_length != Some(line.start.pos_y))
+            .collect(),
+    }
+}
+
+// Keeps only the parts of the lines with a piece on at least one of the sides, the combined lines also go through the waste
+// between the pieces. The kept lines (selvage trim, textile separation) are cut whole.
+pub fn remove_waste_lines(cutting_lines: CuttingLines, positioned_rectangle_list: &[PositionedRectangle], kept_line_list: &[Line], fabric_width: Option<i32>) -> CuttingLines {
+    // the lines are on the outline of the pieces, with their kerf allowance
+    let positioned_rectangle_list: &[PositionedRectangle] = &positioned_rectangle_list
+        .iter()
+        .map(|positioned_rectangle| get_cut_outline(positioned_rectangle, fabric_width))
+        .collect::<Vec<PositionedRectangle>>();
+
+    let get_section_list = |line_list: Vec<Line>| -> Vec<Line> {
+        line_list
+        .into_iter()
+        .flat_map(
+            |line| {
+                if kept_line_list.iter().any(|kept_line| kept_line.contains(&line)) {
+                    vec![line]
+                } else {
+                    get_line_section_list_next_to_pieces(&line, positioned_rectangle_list)
+                }
+            }
+        )
+        .collect()
+    };
+
+    CuttingLines {
+        vertical_lines: get_section_list(cutting_lines.vertical_lines),
+        horizontal_lines: get_section_list(cutting_lines.horizontal_lines),
+    }
+}
+
+fn get_line_section_list_next_to_pieces(line: &Line, positioned_rectangle_list: &[PositionedRectangle]) -> Vec<Line> {
+    let is_vertical = line.is_vertical();
+    let level = if is_vertical { line.start.pos_x } else { line.start.pos_y };
+    let (line_start, line_end) = if is_vertical { (line.start.pos_y, line.end.pos_y) } else { (line.start.pos_x, line.end.pos_x) };
+
+    // the pieces with a side on the line, as intervals along it
+    let mut interval_list: Vec<(i32, i32)> = positioned_rectangle_list
+        .iter()
+        .filter_map(
+            |rect| {
+                let (side_start, side_end, along_start, along_end) = get_bounds_along_line(rect, is_vertical);
+                let start = along_start.max(line_start);
+                let end = along_end.min(line_end);
+                if (side_start == level || side_end == level) && start < end {
+                    Some((start, end))
+                } else {
+                    None
+                }
+            }
+        )
+        .collect();
+    interval_list.sort();
+
+    let mut merged_interval_list = Vec::<(i32, i32)>::new();
+    for (start, end) in interval_list {
+        match merged_interval_list.last_mut() {
+            Some(last_interval) if start <= last_interval.1 => last_interval.1 = last_interval.1.max(end),
+            _ => merged_interval_list.push((start, end)),
+        }
+    }
+
+    merged_interval_list
+    .into_iter()
+    .map(|(start, end)| create_line_on_level(is_vertical, level, start, end))
+    .collect()
+}
+
+// The blade must not go down inside a prohibited area, the lines that go through one are split at its sides.
+// A line on the side of the area does not go inside it.
+fn split_by_prohibited_areas(line_list: Vec<Line>, prohibited_area_list: &[PositionedRectangle]) -> Vec<Line> {
+    let mut result_line_list = Vec::<Line>::new();
+
+    for line in line_list {
+        let is_vertical = line.is_vertical();
+        let level = if is_vertical { line.start.pos_x } else { line.start.pos_y };
+
+        let mut section_list = vec![if is_vertical { (line.start.pos_y, line.end.pos_y) } else { (line.start.pos_x, line.end.pos_x) }];
+
+        for prohibited_area in prohibited_area_list {
+            let (side_start, side_end, along_start, along_end) = get_bounds_along_line(prohibited_area, is_vertical);
+            if level <= side_start || level >= side_end {
+                continue;
+            }
+
+            section_list = section_list
+                .into_iter()
+                .flat_map(|(start, end)| [(start, end.min(along_start)), (start.max(along_end), end)])
+                .filter(|(start, end)| start < end)
+                .collect();
+        }
+
+        result_line_list.extend(section_list.into_iter().map(|(start, end)| create_line_on_level(is_vertical, level, start, end)));
+    }
+    result_line_list
+}
+
+// Extends the cut before its start and past its end, the line may be cut in any direction.
+// The blade must not go into a piece or a prohibited area (a line on their side does not) nor out of the sheet,
+// so each end stops at the first one it would reach. The sheet starts at zero and goes up to the sheet limit.
+pub fn get_extended_line(line: &Line, start_extension: Fixed, end_extension: Fixed, obstacle_list: &[PositionedRectangle], sheet_limit: Fixed) -> Line {
+    let is_vertical = line.is_vertical();
+    let level = if is_vertical { line.start.pos_x } else { line.start.pos_y };
+    let (start, end) = if is_vertical { (line.start.pos_y, line.end.pos_y) } else { (line.start.pos_x, line.end.pos_x) };
+    let is_forward = start <= end;
+
+    let (lower, upper) = (start.min(end), start.max(end));
+    let (lower_extension, upper_extension) = if is_forward { (start_extension, end_extension) } else { (end_extension, start_extension) };
+
+    let mut lower_limit = 0;
+    let mut upper_limit = sheet_limit;
+    for obstacle in obstacle_list {
+        let (side_start, side_end, along_start, along_end) = get_bounds_along_line(obstacle, is_vertical);
+        if level <= side_start || level >= side_end {
+            continue;
+        }
+        if along_end <= lower {
+            lower_limit = lower_limit.max(along_end);
+        }
+        if along_start >= upper {
+            upper_limit = upper_limit.min(along_start);
+        }
+    }
+
+    // the limits never make the cut shorter than the line
+    let extended_lower = (lower - lower_extension).max(lower_limit.min(lower));
+    let extended_upper = (upper + upper_extension).min(upper_limit.max(upper));
+
+    if is_forward {
+        create_line_on_level(is_vertical, level, extended_lower, extended_upper)
+    } else {
+        create_line_on_level(is_vertical, level, extended_upper, extended_lower)
+    }
+}
+
+// the sides of the rectangle across the line and its start and end along it
+fn get_bounds_along_line(rect: &PositionedRectangle, is_vertical: bool) -> (i32, i32, i32, i32) {
+    if is_vertical {
+        (rect.top_left_vertex.pos_x, rect.top_left_vertex.pos_x + rect.width, rect.top_left_vertex.pos_y, rect.top_left_vertex.pos_y + rect.length)
+    } else {
+        (rect.top_left_vertex.pos_y, rect.top_left_vertex.pos_y + rect.length, rect.top_left_vertex.pos_x, rect.top_left_vertex.pos_x + rect.width)
+    }
+}
+
+fn create_line_on_level(is_vertical: bool, level: i32, start: i32, end: i32) -> Line {
+    if is_vertical {
+        Line { start: Vertex { pos_x: level, pos_y: start }, end: Vertex { pos_x: level, pos_y: end } }
+    } else {
+        Line { start: Vertex { pos_x: start, pos_y: level }, end: Vertex { pos_x: end, pos_y: level } }
+    }
+}
+
+// Cuts the selvage on both sides and the top edge of the fabric along the length used
+pub fn define_selvage_trim_lines(edge_margins: &EdgeMargins, width: i32, length: i32) -> Vec<Line> {
+    let mut trim_line_list = Vec::<Line>::new();
+
+    if length <= 0 {
+        return trim_line_list;
+    }
+
+    if edge_margins.left > 0 {
+        trim_line_list.push(Line {
+            start: Vertex { pos_x: edge_margins.left, pos_y: 0 },
+            end: Vertex { pos_x: edge_margins.left, pos_y: length }
+        });
+    }
+    if edge_margins.right > 0 {
+        trim_line_list.push(Line {
+            start: Vertex { pos_x: width - edge_margins.right, pos_y: 0 },
+            end: Vertex { pos_x: width - edge_margins.right, pos_y: length }
+        });
+    }
+    if edge_margins.top > 0 {
+        trim_line_list.push(Line {
+            start: Vertex { pos_x: 0, pos_y: edge_margins.top },
+            end: Vertex { pos_x: width, pos_y: edge_margins.top }
+        });
+    }
+
+    trim_line_list
+}
+
 fn combine_lines(lines_to_be_combined: &Vec<Line>, lines_not_to_be_crossed: &Vec<Line>) -> Vec<Line> {
     let mut lines_result_list: Vec<Line> = Vec::<Line>::new();
 
@@ -90,9 +327,56 @@ fn combine_lines(lines_to_be_combined: &Vec<Line>, lines_not_to_be_crossed: &Vec
     lines_result_list
 }
 
+// A layout is guillotine cuttable when it can be split recursively by cuts that go from edge to edge of the current panel
+pub fn is_guillotine_cuttable(positioned_rectangle_list: &[PositionedRectangle]) -> bool {
+    if positioned_rectangle_list.len() <= 1 {
+        return true;
+    }
+
+    for is_vertical in [true, false] {
+        if let Some((first_panel, second_panel)) = split_by_through_cut(positioned_rectangle_list, is_vertical) {
+            // any valid cut can be taken, the panels of a guillotine layout are guillotine as well
+            return is_guillotine_cuttable(&first_panel) && is_guillotine_cuttable(&second_panel);
+        }
+    }
+
+    false
+}
+
+fn split_by_through_cut(
+    positioned_rectangle_list: &[PositionedRectangle],
+    is_vertical: bool
+) -> Option<(Vec<PositionedRectangle>, Vec<PositionedRectangle>)> {
+    let get_start = |rect: &PositionedRectangle| if is_vertical { rect.top_left_vertex.pos_x } else { rect.top_left_vertex.pos_y };
+    let get_end = |rect: &PositionedRectangle| if is_vertical { rect.top_left_vertex.pos_x + rect.width } else { rect.top_left_vertex.pos_y + rect.length };
+
+    positioned_rectangle_list
+    .iter()
+    .map(get_end)
+    .find_map(
+        |cut_position| {
+            if positioned_rectangle_list.iter().any(|rect| get_start(rect) < cut_position && get_end(rect) > cut_position) {
+                return None;
+            }
+
+            let (first_panel, second_panel): (Vec<PositionedRectangle>, Vec<PositionedRectangle>) = 
+                positioned_rectangle_list
+                .iter()
+                .cloned()
+                .partition(|rect| get_end(rect) <= cut_position);
+
+            if first_panel.is_empty() || second_panel.is_empty() {
+                None
+            } else {
+                Some((first_panel, second_panel))
+            }
+        }
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{models::{cut_disposition::{PositionedRectangle, Vertex}, cutting_lines::Line}, services::cutting_lines_service::define_cutting_lines};
+    use crate::{models::{cut_disposition::{PositionedRectangle, Vertex, EdgeMargins}, cutting_lines::Line}, services::cutting_lines_service::{define_cutting_lines, define_selvage_trim_lines, is_guillotine_cuttable, remove_fabric_border_lines, remove_waste_lines, get_textile_separation_line, get_extended_line}};
 
     #[test]
     fn define_cutting_lines_test() {
@@ -104,6 +388,8 @@ mod tests {
                 pos_x: 0,
                 pos_y: 0,
             },
+            rotated: false,
+            margin: None,
         };
 
         let pos_rect_2 = PositionedRectangle {
@@ -114,6 +400,8 @@ mod tests {
                 pos_x: 0,
                 pos_y: 60,
             },
+            rotated: false,
+            margin: None,
         };
 
         let pos_rect_3 = PositionedRectangle {
@@ -124,6 +412,8 @@ mod tests {
                 pos_x: 160,
                 pos_y: 0,
             },
+            rotated: false,
+            margin: None,
         };
 
         let pos_rect_4 = PositionedRectangle {
@@ -134,6 +424,8 @@ mod tests {
                 pos_x: 130,
                 pos_y: 60,
             },
+            rotated: false,
+            margin: None,
         };
 
         let pos_rect_5 = PositionedRectangle {
@@ -144,11 +436,13 @@ mod tests {
                 pos_x: 160,
                 pos_y: 80,
             },
+            rotated: false,
+            margin: None,
         };
 
         let positioned_rectangle_list = vec![pos_rect_1, pos_rect_2, pos_rect_3, pos_rect_4, pos_rect_5];
 
-        let cutting_lines = define_cutting_lines(positioned_rectangle_list, None);
+        let cutting_lines = define_cutting_lines(positioned_rectangle_list, None, Vec::new(), &[]);
 
         //expected vertical lines
         let v0 = Line { start: Vertex { pos_x: 0, pos_y: 0 },end: Vertex { pos_x: 0, pos_y: 100 } };
@@ -185,4 +479,294 @@ mod tests {
         assert!(cutting_lines.horizontal_lines.len() == 6);
 
     }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- x=150 stops at the first area and starts again after it, y=100 stops at the second one -->
+        <rect x="0" y="0" width="150" height="50" style="fill:green;stroke:black;" />
+        <rect x="0" y="60" width="120" height="40" style="fill:green;stroke:black;" />
+        <rect x="160" y="0" width="40" height="70" style="fill:green;stroke:black;" />
+        <rect x="130" y="60" width="20" height="40" style="fill:green;stroke:black;" />
+        <rect x="160" y="80" width="20" height="20" style="fill:green;stroke:black;" />
+        <rect x="140" y="52" width="20" height="6" style="fill:red;stroke:black;" />
+        <rect x="151" y="95" width="8" height="10" style="fill:red;stroke:black;" />
+    </svg>
+ */
+    #[test]
+    fn define_cutting_lines_with_prohibited_area_test() {
+        let positioned_rectangle_list = vec![
+            create_positioned_rectangle(1, 0, 0, 150, 50),
+            create_positioned_rectangle(2, 0, 60, 120, 40),
+            create_positioned_rectangle(3, 160, 0, 40, 70),
+            create_positioned_rectangle(4, 130, 60, 20, 40),
+            create_positioned_rectangle(5, 160, 80, 20, 20),
+        ];
+        let prohibited_area_list = vec![
+            create_positioned_rectangle(6, 140, 52, 20, 6),
+            create_positioned_rectangle(7, 151, 95, 8, 10),
+        ];
+
+        let cutting_lines = define_cutting_lines(positioned_rectangle_list, None, Vec::new(), &prohibited_area_list);
+
+        let v1_top = Line { start: Vertex { pos_x: 150, pos_y: 0 }, end: Vertex { pos_x: 150, pos_y: 52 } };
+        let v1_bottom = Line { start: Vertex { pos_x: 150, pos_y: 58 }, end: Vertex { pos_x: 150, pos_y: 100 } };
+        // on the side of the first area
+        let v3 = Line { start: Vertex { pos_x: 160, pos_y: 0 }, end: Vertex { pos_x: 160, pos_y: 100 } };
+
+        let h3_left = Line { start: Vertex { pos_x: 0, pos_y: 100 }, end: Vertex { pos_x: 151, pos_y: 100 } };
+        let h3_right = Line { start: Vertex { pos_x: 159, pos_y: 100 }, end: Vertex { pos_x: 180, pos_y: 100 } };
+
+        assert_eq!(cutting_lines.vertical_lines.len(), 8);
+        assert!(cutting_lines.vertical_lines.contains(&v1_top));
+        assert!(cutting_lines.vertical_lines.contains(&v1_bottom));
+        assert!(cutting_lines.vertical_lines.contains(&v3));
+
+        assert_eq!(cutting_lines.horizontal_lines.len(), 7);
+        assert!(cutting_lines.horizontal_lines.contains(&h3_left));
+        assert!(cutting_lines.horizontal_lines.contains(&h3_right));
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- the piece with a margin of 4 is cut 2 away from its sides, the same piece without margin on its sides -->
+        <rect x="8" y="8" width="54" height="34" style="fill:none;stroke:red;" />
+        <rect x="10" y="10" width="50" height="30" style="fill:green;stroke:black;" />
+    </svg>
+ */
+    #[test]
+    fn define_cutting_lines_with_margin_test() {
+        let plain_rectangle = create_positioned_rectangle(1, 10, 10, 50, 30);
+        let margin_rectangle = PositionedRectangle { margin: Some(4), ..plain_rectangle.clone() };
+
+        let plain_cutting_lines = define_cutting_lines(vec![plain_rectangle], None, Vec::new(), &[]);
+        let margin_cutting_lines = define_cutting_lines(vec![margin_rectangle], None, Vec::new(), &[]);
+
+        assert_eq!(
+            plain_cutting_lines.vertical_lines,
+            vec![
+                Line { start: Vertex { pos_x: 60, pos_y: 10 }, end: Vertex { pos_x: 60, pos_y: 40 } },
+                Line { start: Vertex { pos_x: 10, pos_y: 10 }, end: Vertex { pos_x: 10, pos_y: 40 } },
+            ]
+        );
+        assert_eq!(
+            margin_cutting_lines.vertical_lines,
+            vec![
+                Line { start: Vertex { pos_x: 62, pos_y: 8 }, end: Vertex { pos_x: 62, pos_y: 42 } },
+                Line { start: Vertex { pos_x: 8, pos_y: 8 }, end: Vertex { pos_x: 8, pos_y: 42 } },
+            ]
+        );
+        assert_eq!(
+            margin_cutting_lines.horizontal_lines,
+            vec![
+                Line { start: Vertex { pos_x: 8, pos_y: 42 }, end: Vertex { pos_x: 62, pos_y: 42 } },
+                Line { start: Vertex { pos_x: 8, pos_y: 8 }, end: Vertex { pos_x: 62, pos_y: 8 } },
+            ]
+        );
+    }
+
+    #[test]
+    fn define_cutting_lines_with_selvage_trim_test() {
+        let positioned_rectangle_list = vec![create_positioned_rectangle(1, 10, 5, 70, 40)];
+
+        let trim_line_list = define_selvage_trim_lines(&EdgeMargins { left: 10, right: 20, top: 5 }, 100, 45);
+
+        let cutting_lines = define_cutting_lines(positioned_rectangle_list, Some(100), trim_line_list, &[]);
+
+        let v0 = Line { start: Vertex { pos_x: 10, pos_y: 0 }, end: Vertex { pos_x: 10, pos_y: 45 } };
+        let v1 = Line { start: Vertex { pos_x: 80, pos_y: 0 }, end: Vertex { pos_x: 80, pos_y: 45 } };
+
+        let h0 = Line { start: Vertex { pos_x: 0, pos_y: 0 }, end: Vertex { pos_x: 100, pos_y: 0 } };
+        let h1 = Line { start: Vertex { pos_x: 0, pos_y: 5 }, end: Vertex { pos_x: 100, pos_y: 5 } };
+        let h2 = Line { start: Vertex { pos_x: 10, pos_y: 45 }, end: Vertex { pos_x: 80, pos_y: 45 } };
+
+        assert_eq!(cutting_lines.vertical_lines.len(), 2);
+        assert!(cutting_lines.vertical_lines.contains(&v0));
+        assert!(cutting_lines.vertical_lines.contains(&v1));
+
+        assert_eq!(cutting_lines.horizontal_lines.len(), 3);
+        assert!(cutting_lines.horizontal_lines.contains(&h0));
+        assert!(cutting_lines.horizontal_lines.contains(&h1));
+        assert!(cutting_lines.horizontal_lines.contains(&h2));
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- the lines on x=0, x=200 and on the end of the sheet (y=100) are not cut -->
+        <rect x="0" y="0" width="150" height="50" style="fill:green;stroke:black;" />
+        <rect x="0" y="60" width="120" height="40" style="fill:green;stroke:black;" />
+        <rect x="160" y="0" width="40" height="70" style="fill:green;stroke:black;" />
+        <rect x="130" y="60" width="20" height="40" style="fill:green;stroke:black;" />
+        <rect x="160" y="80" width="20" height="20" style="fill:green;stroke:black;" />
+    </svg>
+ */
+    #[test]
+    fn remove_fabric_border_lines_test() {
+        let positioned_rectangle_list = vec![
+            create_positioned_rectangle(1, 0, 0, 150, 50),
+            create_positioned_rectangle(2, 0, 60, 120, 40),
+            create_positioned_rectangle(3, 160, 0, 40, 70),
+            create_positioned_rectangle(4, 130, 60, 20, 40),
+            create_positioned_rectangle(5, 160, 80, 20, 20),
+        ];
+
+        let cutting_lines = remove_fabric_border_lines(define_cutting_lines(positioned_rectangle_list, Some(200), Vec::new(), &[]), 200, Some(100));
+
+        assert_eq!(cutting_lines.vertical_lines.len(), 5);
+        assert!(cutting_lines.vertical_lines.iter().all(|line| line.start.pos_x != 0 && line.start.pos_x != 200));
+
+        assert_eq!(cutting_lines.horizontal_lines.len(), 5);
+        assert!(cutting_lines.horizontal_lines.contains(&get_textile_separation_line(200)));
+        assert!(cutting_lines.horizontal_lines.iter().all(|line| line.start.pos_y != 100));
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- the bottom line y=100 is cut only under the pieces, between them there is only waste -->
+        <rect x="0" y="0" width="150" height="50" style="fill:green;stroke:black;" />
+        <rect x="0" y="60" width="120" height="40" style="fill:green;stroke:black;" />
+        <rect x="160" y="0" width="40" height="70" style="fill:green;stroke:black;" />
+        <rect x="130" y="60" width="20" height="40" style="fill:green;stroke:black;" />
+        <rect x="160" y="80" width="20" height="20" style="fill:green;stroke:black;" />
+        <line x1="0" y1="100" x2="120" y2="100" style="stroke:red;" />
+        <line x1="130" y1="100" x2="150" y2="100" style="stroke:red;" />
+        <line x1="160" y1="100" x2="180" y2="100" style="stroke:red;" />
+    </svg>
+ */
+    #[test]
+    fn remove_waste_lines_test() {
+        let positioned_rectangle_list = vec![
+            create_positioned_rectangle(1, 0, 0, 150, 50),
+            create_positioned_rectangle(2, 0, 60, 120, 40),
+            create_positioned_rectangle(3, 160, 0, 40, 70),
+            create_positioned_rectangle(4, 130, 60, 20, 40),
+            create_positioned_rectangle(5, 160, 80, 20, 20),
+        ];
+
+        let cutting_lines = remove_waste_lines(
+            define_cutting_lines(positioned_rectangle_list.clone(), Some(200), Vec::new(), &[]),
+            &positioned_rectangle_list,
+            &[get_textile_separation_line(200)],
+            Some(200)
+        );
+
+        // the separation line is kept whole
+        assert!(cutting_lines.horizontal_lines.contains(&get_textile_separation_line(200)));
+
+        assert!(!cutting_lines.horizontal_lines.contains(&Line { start: Vertex { pos_x: 0, pos_y: 100 }, end: Vertex { pos_x: 180, pos_y: 100 } }));
+        assert!(cutting_lines.horizontal_lines.contains(&Line { start: Vertex { pos_x: 0, pos_y: 100 }, end: Vertex { pos_x: 120, pos_y: 100 } }));
+        assert!(cutting_lines.horizontal_lines.contains(&Line { start: Vertex { pos_x: 130, pos_y: 100 }, end: Vertex { pos_x: 150, pos_y: 100 } }));
+        assert!(cutting_lines.horizontal_lines.contains(&Line { start: Vertex { pos_x: 160, pos_y: 100 }, end: Vertex { pos_x: 180, pos_y: 100 } }));
+
+        // x=150 is a side of the first piece from 0 to 50 and of the fourth one from 60 to 100
+        assert!(cutting_lines.vertical_lines.contains(&Line { start: Vertex { pos_x: 150, pos_y: 0 }, end: Vertex { pos_x: 150, pos_y: 50 } }));
+        assert!(cutting_lines.vertical_lines.contains(&Line { start: Vertex { pos_x: 150, pos_y: 60 }, end: Vertex { pos_x: 150, pos_y: 100 } }));
+        assert!(!cutting_lines.vertical_lines.iter().any(|line| line.start.pos_x == 150 && line.start.pos_y < 60 && line.end.pos_y > 50));
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- x=100 goes down to the piece below it, y=50 goes on up to the side of the sheet -->
+        <rect x="0" y="0" width="100" height="50" style="fill:green;stroke:black;" />
+        <rect x="90" y="70" width="60" height="30" style="fill:green;stroke:black;" />
+        <rect x="110" y="0" width="40" height="50" style="fill:green;stroke:black;" />
+        <line x1="100" y1="0" x2="100" y2="70" style="stroke:red;" />
+        <line x1="0" y1="50" x2="120" y2="50" style="stroke:blue;" />
+    </svg>
+ */
+    #[test]
+    fn get_extended_line_test() {
+        let obstacle_list = vec![
+            create_positioned_rectangle(1, 0, 0, 100, 50),
+            create_positioned_rectangle(2, 90, 70, 60, 30),
+            create_positioned_rectangle(3, 110, 0, 40, 50),
+        ];
+
+        let vertical_line = Line { start: Vertex { pos_x: 100, pos_y: 0 }, end: Vertex { pos_x: 100, pos_y: 50 } };
+        assert_eq!(
+            get_extended_line(&vertical_line, 5, 30, &obstacle_list, 100),
+            Line { start: Vertex { pos_x: 100, pos_y: 0 }, end: Vertex { pos_x: 100, pos_y: 70 } }
+        );
+
+        // cut from the bottom, the lead-in goes down and the overcut stops at the top of the sheet
+        assert_eq!(
+            get_extended_line(&vertical_line.get_reversed(), 5, 30, &obstacle_list, 100),
+            Line { start: Vertex { pos_x: 100, pos_y: 55 }, end: Vertex { pos_x: 100, pos_y: 0 } }
+        );
+
+        // on the side of the third rectangle
+        let horizontal_line = Line { start: Vertex { pos_x: 0, pos_y: 50 }, end: Vertex { pos_x: 100, pos_y: 50 } };
+        assert_eq!(
+            get_extended_line(&horizontal_line, 5, 30, &obstacle_list, 120),
+            Line { start: Vertex { pos_x: 0, pos_y: 50 }, end: Vertex { pos_x: 120, pos_y: 50 } }
+        );
+    }
+
+    fn create_positioned_rectangle(id: u32, pos_x: i32, pos_y: i32, width: i32, length: i32) -> PositionedRectangle {
+        PositionedRectangle {
+            id,
+            width,
+            length,
+            top_left_vertex: Vertex {
+                pos_x,
+                pos_y,
+            },
+            rotated: false,
+            margin: None,
+        }
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- first cut at x=150, then y=50 at the left panel and y=70 at the right panel -->
+        <rect x="0" y="0" width="150" height="50" style="fill:green;stroke:black;" />
+        <rect x="0" y="60" width="120" height="40" style="fill:green;stroke:black;" />
+        <rect x="160" y="0" width="40" height="70" style="fill:green;stroke:black;" />
+        <rect x="130" y="60" width="20" height="40" style="fill:green;stroke:black;" />
+        <rect x="160" y="80" width="20" height="20" style="fill:green;stroke:black;" />
+    </svg>
+
+ */
+    #[test]
+    fn is_guillotine_cuttable_test() {
+        let positioned_rectangle_list = vec![
+            create_positioned_rectangle(1, 0, 0, 150, 50),
+            create_positioned_rectangle(2, 0, 60, 120, 40),
+            create_positioned_rectangle(3, 160, 0, 40, 70),
+            create_positioned_rectangle(4, 130, 60, 20, 40),
+            create_positioned_rectangle(5, 160, 80, 20, 20),
+        ];
+
+        assert!(is_guillotine_cuttable(&positioned_rectangle_list));
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- pinwheel, every cut from edge to edge crosses a rectangle -->
+        <rect x="0" y="0" width="60" height="20" style="fill:green;stroke:black;" />
+        <rect x="60" y="0" width="20" height="60" style="fill:green;stroke:black;" />
+        <rect x="20" y="60" width="60" height="20" style="fill:green;stroke:black;" />
+        <rect x="0" y="20" width="20" height="60" style="fill:green;stroke:black;" />
+        <rect x="20" y="20" width="40" height="40" style="fill:green;stroke:black;" />
+    </svg>
+
+ */
+    #[test]
+    fn is_not_guillotine_cuttable_test() {
+        let positioned_rectangle_list = vec![
+            create_positioned_rectangle(1, 0, 0, 60, 20),
+            create_positioned_rectangle(2, 60, 0, 20, 60),
+            create_positioned_rectangle(3, 20, 60, 60, 20),
+            create_positioned_rectangle(4, 0, 20, 20, 60),
+            create_positioned_rectangle(5, 20, 20, 40, 40),
+        ];
+
+        assert!(!is_guillotine_cuttable(&positioned_rectangle_list));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,348 @@
+use std::collections::HashSet;
+
+use crate::models::{cut_disposition::{CutDispositionOutput, CutDispositionState, PositionedRectangle, Vertex, DispositionViolation}, app_error::AppError, fixed_point::Fixed};
+
+use super::cut_disposition_service::{is_within_boundaries, intersect};
+
+// Checks a sheet organized or adjusted, the pieces and showcases must be inside the usable fabric,
+// away from the prohibited areas by their margins and apart from each other by the biggest of their spacings
+pub fn validate_disposition(cut_disposition_output: &CutDispositionOutput, spacing: i32) -> Vec<DispositionViolation> {
+    let edge_margins = &cut_disposition_output.edge_margins;
+    let usable_width = cut_disposition_output.defined_width - edge_margins.left - edge_margins.right;
+    let usable_length = cut_disposition_output.defined_length.unwrap_or(cut_disposition_output.max_length) - edge_margins.top;
+
+    let mut violation_list: Vec<DispositionViolation> = get_duplicated_id_list(cut_disposition_output)
+        .into_iter()
+        .map(|id| DispositionViolation::DuplicatedId { id })
+        .collect();
+
+    let cut_rectangle_list: Vec<&PositionedRectangle> = cut_disposition_output.positioned_rectangles_list
+        .iter()
+        .chain(cut_disposition_output.showcase_rectangles_located_list.iter())
+        .collect();
+
+    for (index, cut_rectangle) in cut_rectangle_list.iter().enumerate() {
+        let usable_band_rectangle = PositionedRectangle {
+            top_left_vertex: Vertex {
+                pos_x: cut_rectangle.top_left_vertex.pos_x - edge_margins.left,
+                pos_y: cut_rectangle.top_left_vertex.pos_y - edge_margins.top,
+            },
+            ..(*cut_rectangle).clone()
+        };
+        if !is_within_boundaries(&usable_band_rectangle, usable_width, usable_length) {
+            violation_list.push(DispositionViolation::OutOfBounds { id: cut_rectangle.id });
+        }
+
+        for prohibited_area in cut_disposition_output.prohibited_area_list.iter() {
+            // the spacing is not kept from the prohibited areas, only the margin of the piece
+            if intersect(cut_rectangle, prohibited_area, cut_rectangle.margin.unwrap_or(0).max(0)) {
+                violation_list.push(DispositionViolation::ProhibitedAreaIntersection { id: cut_rectangle.id, prohibited_area_id: prohibited_area.id });
+            }
+        }
+
+        for other_cut_rectangle in cut_rectangle_list[index + 1..].iter() {
+            let pair_spacing = cut_rectangle.get_spacing(spacing).max(other_cut_rectangle.get_spacing(spacing));
+            if intersect(cut_rectangle, other_cut_rectangle, 0) {
+                violation_list.push(DispositionViolation::Overlap { id: cut_rectangle.id, other_id: other_cut_rectangle.id });
+            } else if intersect(cut_rectangle, other_cut_rectangle, pair_spacing) {
+                violation_list.push(DispositionViolation::SpacingBreach { id: cut_rectangle.id, other_id: other_cut_rectangle.id, spacing: pair_spacing });
+            }
+        }
+    }
+
+    violation_list
+}
+
+// Refuses the sheets with any violation, all of them are listed so the operator can fix them at once
+pub fn verify_disposition_sheet_list(sheet_list: &[CutDispositionOutput], spacing: i32) -> Result<(), AppError> {
+    let message_list: Vec<String> = sheet_list
+        .iter()
+        .enumerate()
+        .flat_map(
+            |(index, sheet)| validate_disposition(sheet, spacing)
+                .into_iter()
+                .map(move |violation| format!("Folha {}: {}", index + 1, violation))
+        )
+        .collect();
+
+    if message_list.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::new(1, format!("Disposição inválida: {}", message_list.join("; ")).as_str()))
+    }
+}
+
+// A disposition read from a file may have been edited by hand. Each piece keeps the biggest of the margins the file gives it,
+// on the layout and on the definition of the pieces, and the spacing is the biggest of the config and of the organized input.
+pub fn validate_imported_disposition(cut_disposition_state: &CutDispositionState) -> Vec<DispositionViolation> {
+    let layout = match &cut_disposition_state.layout {
+        Some(layout) => layout,
+        None => return Vec::new(),
+    };
+
+    let spacing = cut_disposition_state.spacing.unwrap_or(0).max(layout.cut_disposition_input.spacing.unwrap_or(0));
+
+    let mut defined_margin_list: Vec<(u32, Option<Fixed>)> = cut_disposition_state.get_pieces()
+        .iter()
+        .chain(cut_disposition_state.get_showcase_list().iter())
+        .chain(layout.cut_disposition_input.rectangles_list.iter())
+        .chain(layout.cut_disposition_input.showcase_list.iter())
+        .map(|rectangle| (rectangle.id, rectangle.margin))
+        .collect();
+    defined_margin_list.extend(
+        cut_disposition_state.get_pinned_piece_list()
+            .iter()
+            .chain(layout.cut_disposition_input.pinned_piece_list.iter())
+            .map(|pinned_piece| (pinned_piece.id, pinned_piece.margin))
+    );
+
+    let get_with_defined_margin = |positioned_rectangle: &PositionedRectangle| PositionedRectangle {
+        margin: defined_margin_list
+            .iter()
+            .filter(|(id, _)| *id == positioned_rectangle.id)
+            .filter_map(|(_, margin)| *margin)
+            .chain(positioned_rectangle.margin)
+            .max(),
+        ..positioned_rectangle.clone()
+    };
+
+    layout.sheet_list
+        .iter()
+        .flat_map(
+            |sheet| {
+                let sheet = CutDispositionOutput {
+                    positioned_rectangles_list: sheet.positioned_rectangles_list.iter().map(get_with_defined_margin).collect(),
+                    showcase_rectangles_located_list: sheet.showcase_rectangles_located_list.iter().map(get_with_defined_margin).collect(),
+                    ..sheet.clone()
+                };
+                validate_disposition(&sheet, spacing)
+            }
+        )
+        .collect()
+}
+
+// A piece placed more times than its quantity, or an id shared by pieces, showcases and prohibited areas
+fn get_duplicated_id_list(cut_disposition_output: &CutDispositionOutput) -> Vec<u32> {
+    let mut duplicated_id_list: Vec<u32> = cut_disposition_output.piece_count_list
+        .iter()
+        .filter(
+            |piece_count| {
+                let placed_quantity = cut_disposition_output.positioned_rectangles_list
+                    .iter()
+                    .filter(|piece| piece.id == piece_count.id)
+                    .count();
+                placed_quantity > piece_count.quantity as usize
+            }
+        )
+        .map(|piece_count| piece_count.id)
+        .collect();
+
+    let get_id_set = |rectangle_list: &[PositionedRectangle]| rectangle_list.iter().map(|rectangle| rectangle.id).collect::<HashSet<u32>>();
+
+    let piece_id_set = get_id_set(&cut_disposition_output.positioned_rectangles_list);
+    let showcase_id_set = get_id_set(&cut_disposition_output.showcase_rectangles_located_list);
+    let prohibited_area_id_set = get_id_set(&cut_disposition_output.prohibited_area_list);
+
+    duplicated_id_list.extend(piece_id_set.intersection(&showcase_id_set));
+    duplicated_id_list.extend(piece_id_set.intersection(&prohibited_area_id_set));
+    duplicated_id_list.extend(showcase_id_set.intersection(&prohibited_area_id_set));
+
+    duplicated_id_list.sort();
+    duplicated_id_list.dedup();
+    duplicated_id_list
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{models::cut_disposition::{CutDispositionInput, Rectangle, EdgeMargins, NestingStrategyType}, services::cut_disposition_service::{organize_disposition, NoOrganizeObserver}, test_support::get_cut_disposition_input};
+
+    use super::*;
+
+    // the layouts organized by every strategy are accepted, even with margins, showcases and pinned pieces
+    #[test]
+    fn validate_disposition_organized_test() {
+        let rect1 = Rectangle {
+            id: 1,
+            width: 30,
+            length: 20,
+            allow_rotation: None,
+            quantity: 3,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let rect2 = Rectangle {
+            id: 2,
+            width: 20,
+            length: 20,
+            allow_rotation: None,
+            quantity: 2,
+            max_quantity: None,
+            match_pattern: false,
+            margin: Some(10),
+        };
+        let showcase = Rectangle {
+            id: 3,
+            width: 10,
+            length: 10,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let prohibited_area = PositionedRectangle {
+            id: 4,
+            width: 20,
+            length: 20,
+            top_left_vertex: Vertex { pos_x: 60, pos_y: 40 },
+            rotated: false,
+            margin: None,
+        };
+        let pinned_piece = PositionedRectangle {
+            id: 5,
+            width: 15,
+            length: 15,
+            top_left_vertex: Vertex { pos_x: 70, pos_y: 10 },
+            rotated: false,
+            margin: None,
+        };
+
+        for nesting_strategy in [NestingStrategyType::BottomLeftVertex, NestingStrategyType::MaxRects, NestingStrategyType::Guillotine] {
+            let cut_disposition_input = CutDispositionInput {
+                prohibited_area_list: vec![prohibited_area.clone()],
+                showcase_list: vec![showcase.clone()],
+                spacing: Some(5),
+                max_length: 120,
+                allow_rotation: true,
+                nesting_strategy: nesting_strategy.clone(),
+                edge_margins: EdgeMargins { left: 5, right: 5, top: 5 },
+                pinned_piece_list: vec![pinned_piece.clone()],
+                ..get_cut_disposition_input(vec![rect1.clone(), rect2.clone()])
+            };
+
+            let output = organize_disposition(&cut_disposition_input, 1, &NoOrganizeObserver);
+
+            assert!(!output.positioned_rectangles_list.is_empty());
+            assert_eq!(validate_disposition(&output, 5), Vec::new(), "{:?}", nesting_strategy);
+        }
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- fabric -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <!-- prohibited area -->
+        <rect x="0" y="0" width="20" height="20" style="fill:black;stroke:black;" />
+
+        <!-- pieces -->
+        <rect x="10" y="10" width="30" height="30" style="fill:red;stroke:black;" />
+        <rect x="35" y="0" width="30" height="30" style="fill:red;stroke:black;" />
+        <rect x="68" y="0" width="30" height="30" style="fill:red;stroke:black;" />
+        <rect x="90" y="50" width="30" height="30" style="fill:red;stroke:black;" />
+    </svg>
+ */
+    #[test]
+    fn validate_disposition_violations_test() {
+        let rect = Rectangle {
+            id: 1,
+            width: 30,
+            length: 30,
+            allow_rotation: None,
+            quantity: 2,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let prohibited_area = PositionedRectangle {
+            id: 2,
+            width: 20,
+            length: 20,
+            top_left_vertex: Vertex { pos_x: 0, pos_y: 0 },
+            rotated: false,
+            margin: None,
+        };
+
+        let cut_disposition_input = CutDispositionInput {
+            prohibited_area_list: vec![prohibited_area],
+            spacing: Some(5),
+            ..get_cut_disposition_input(vec![rect.clone()])
+        };
+
+        let mut output = organize_disposition(&cut_disposition_input, 1, &NoOrganizeObserver);
+        assert!(validate_disposition(&output, 5).is_empty());
+
+        output.positioned_rectangles_list = vec![
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 10, pos_y: 10 }),
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 35, pos_y: 0 }),
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 68, pos_y: 0 }),
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 90, pos_y: 50 }),
+        ];
+
+        assert_eq!(
+            validate_disposition(&output, 5),
+            vec![
+                DispositionViolation::DuplicatedId { id: 1 },
+                DispositionViolation::ProhibitedAreaIntersection { id: 1, prohibited_area_id: 2 },
+                DispositionViolation::Overlap { id: 1, other_id: 1 },
+                DispositionViolation::SpacingBreach { id: 1, other_id: 1, spacing: 5 },
+                DispositionViolation::OutOfBounds { id: 1 },
+            ]
+        );
+
+        // the margin of a piece is kept from the prohibited areas, the spacing is not
+        let margin_rect = PositionedRectangle { margin: Some(10), ..PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 25, pos_y: 0 }) };
+        output.positioned_rectangles_list = vec![margin_rect.clone()];
+        assert_eq!(
+            validate_disposition(&output, 5),
+            vec![DispositionViolation::ProhibitedAreaIntersection { id: 1, prohibited_area_id: 2 }]
+        );
+
+        output.positioned_rectangles_list = vec![PositionedRectangle { top_left_vertex: Vertex { pos_x: 30, pos_y: 0 }, ..margin_rect }];
+        assert!(validate_disposition(&output, 5).is_empty());
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- the file removed the margin of 10 from the layout and the spacing, the pieces are only 5 apart -->
+        <rect x="0" y="0" width="30" height="30" style="fill:red;stroke:black;" />
+        <rect x="35" y="0" width="30" height="30" style="fill:red;stroke:black;" />
+    </svg>
+ */
+    #[test]
+    fn validate_imported_disposition_test() {
+        let mut cut_disposition_state = CutDispositionState::new();
+        cut_disposition_state.defined_width = 100;
+        cut_disposition_state.max_length = 100;
+
+        let rect = cut_disposition_state.add_piece(
+            &Rectangle {
+                id: 0,
+                width: 30,
+                length: 30,
+                allow_rotation: None,
+                quantity: 2,
+                max_quantity: None,
+                match_pattern: false,
+                margin: Some(10),
+            }
+        );
+
+        let mut sheet = organize_disposition(&cut_disposition_state.get_cut_disposition_input(), 1, &NoOrganizeObserver);
+        sheet.positioned_rectangles_list = vec![
+            PositionedRectangle { margin: None, ..PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 0, pos_y: 0 }) },
+            PositionedRectangle { margin: None, ..PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 35, pos_y: 0 }) },
+        ];
+        assert!(validate_disposition(&sheet, 0).is_empty());
+
+        cut_disposition_state.set_layout(cut_disposition_state.get_cut_disposition_input(), vec![sheet]);
+
+        assert_eq!(
+            validate_imported_disposition(&cut_disposition_state),
+            vec![DispositionViolation::SpacingBreach { id: rect.id, other_id: rect.id, spacing: 10 }]
+        );
+    }
+}
@@ -31,6 +31,18 @@ fn verify_create_fabric_create_fields(fabric: &FabricCreate) -> Result<(), AppEr
     if fabric.width <= 0 {
         return Err(AppError::new(DEFAULT_ERROR_CODE, "Campo deve ser maior que zero: largura"))
     }
+    if fabric.repeat_width.is_some_and(|repeat| repeat <= 0) || fabric.repeat_length.is_some_and(|repeat| repeat <= 0) {
+        return Err(AppError::new(DEFAULT_ERROR_CODE, "Campo deve ser maior que zero ou inexistente: repetição da estampa"))
+    }
+    if fabric.repeat_offset_x < 0 || fabric.repeat_offset_y < 0 {
+        return Err(AppError::new(DEFAULT_ERROR_CODE, "Campo deve ser maior ou igual a zero: deslocamento da estampa"))
+    }
+    if fabric.margin_left < 0 || fabric.margin_right < 0 || fabric.margin_top < 0 {
+        return Err(AppError::new(DEFAULT_ERROR_CODE, "Campo deve ser maior ou igual a zero: margem"))
+    }
+    if fabric.margin_left + fabric.margin_right >= fabric.width {
+        return Err(AppError::new(DEFAULT_ERROR_CODE, "Campo deve ser menor que a largura: margens laterais"))
+    }
     Ok(())
 }
 
@@ -63,5 +75,17 @@ fn verify_create_fabric_fields(fabric: &Fabric) -> Result<(), AppError> {
     if fabric.width <= 0 {
         return Err(AppError::new(DEFAULT_ERROR_CODE, "Campo deve ser maior que zero: largura"))
     }
+    if fabric.repeat_width.is_some_and(|repeat| repeat <= 0) || fabric.repeat_length.is_some_and(|repeat| repeat <= 0) {
+        return Err(AppError::new(DEFAULT_ERROR_CODE, "Campo deve ser maior que zero ou inexistente: repetição da estampa"))
+    }
+    if fabric.repeat_offset_x < 0 || fabric.repeat_offset_y < 0 {
+        return Err(AppError::new(DEFAULT_ERROR_CODE, "Campo deve ser maior ou igual a zero: deslocamento da estampa"))
+    }
+    if fabric.margin_left < 0 || fabric.margin_right < 0 || fabric.margin_top < 0 {
+        return Err(AppError::new(DEFAULT_ERROR_CODE, "Campo deve ser maior ou igual a zero: margem"))
+    }
+    if fabric.margin_left + fabric.margin_right >= fabric.width {
+        return Err(AppError::new(DEFAULT_ERROR_CODE, "Campo deve ser menor que a largura: margens laterais"))
+    }
     Ok(())
 }
\ No newline at end of file
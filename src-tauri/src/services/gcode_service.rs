@@ -1,16 +1,16 @@
 use chrono::Local;
 
-use crate::{services::file_service::{get_file_text, write_to_new_file}, models::cutting_lines::Line};
+use crate::{services::{file_service::{get_file_text, write_to_new_file}, cutting_lines_service::get_extended_line}, models::{cut_disposition::PositionedRectangle, cutting_lines::{Line, SheetCuttingLines}, setting::{Setting, LengthUnit}, fixed_point::Fixed}};
 
 use super::file_service::FileError;
 
 
+// Sheets of the same program are cut one after the other, pulling the fabric of each sheet before its cuts
 pub async fn generate_gcode_file(
-    horizontal_lines: Vec<Line>, 
-    vertical_lines: Vec<Line>, 
-    textile_length_to_pull: Option<u32>,
+    sheet_cutting_lines_list: Vec<SheetCuttingLines>,
     output_folder_path: &str,
-    name: &str
+    name: &str,
+    setting: &Setting
 ) -> Result<String, FileError> {
 
     const CNC_INSTRUCTION_FOLDER_PATH: &str = "configs\\cnc_instructions";
@@ -34,23 +34,58 @@ pub async fn generate_gcode_file(
     instructions.push('\n');
 
 
-    if let Some(length_to_pull) = textile_length_to_pull {
-
-        instructions.push_str(&get_pull_textile_instruction(length_to_pull, &pick_textile_file_path, &drop_textile_file_path).await?);
-        instructions.push('\n');
-        instructions.push('\n');
+    let is_multi_sheet = sheet_cutting_lines_list.len() > 1;
+
+    for (index, sheet_cutting_lines) in sheet_cutting_lines_list.into_iter().enumerate() {
+        if is_multi_sheet {
+            instructions.push_str(&format!("( Inicio folha {} )", index + 1));
+            instructions.push('\n');
+            instructions.push('\n');
+        }
+
+        if let Some(length_to_pull) = sheet_cutting_lines.textile_length_to_pull {
+
+            instructions.push_str(&get_pull_textile_instruction(length_to_pull, &pick_textile_file_path, &drop_textile_file_path, setting).await?);
+            instructions.push('\n');
+            instructions.push('\n');
+        }
+
+        for cut_phase in sheet_cutting_lines.cut_phase_list {
+            if cut_phase.is_vertical {
+                instructions.push_str(
+                    &get_vertical_lines(
+                        cut_phase.line_list, 
+                        &sheet_cutting_lines.obstacle_list, 
+                        sheet_cutting_lines.sheet_length, 
+                        &before_y_cut_file_path, 
+                        &after_y_cut_file_path, 
+                        setting
+                    ).await?
+                );
+            } else {
+                instructions.push_str(
+                    &get_horizontal_lines(
+                        cut_phase.line_list, 
+                        &sheet_cutting_lines.obstacle_list, 
+                        sheet_cutting_lines.sheet_width, 
+                        &before_x_cut_file_path, 
+                        &after_x_cut_file_path, 
+                        setting
+                    ).await?
+                );
+            }
+
+            instructions.push('\n');
+            instructions.push('\n');
+        }
+
+        if is_multi_sheet {
+            instructions.push_str(&format!("( Fim folha {} )", index + 1));
+            instructions.push('\n');
+            instructions.push('\n');
+        }
     }
 
-    instructions.push_str(&get_vertical_lines(vertical_lines, &before_y_cut_file_path, &after_y_cut_file_path).await?);
-
-    instructions.push('\n');
-    instructions.push('\n');
-
-    instructions.push_str(&get_horizontal_lines(horizontal_lines, &before_x_cut_file_path, &after_x_cut_file_path).await?);
-
-    instructions.push('\n');
-    instructions.push('\n');
-
     instructions.push_str(&get_end_program(&end_program_file_path).await?);
 
     let path = write_to_new_file(&format!("{}\\{}.txt", output_folder_path, name), &instructions).await?;
@@ -68,7 +103,7 @@ fn get_title_comment(name: &str) -> String {
     instructions
 }
 
-async fn get_pull_textile_instruction(length_to_pull: u32, pick_textile_file_path: &str, drop_textile_file_path: &str) -> Result<String, FileError> {
+async fn get_pull_textile_instruction(length_to_pull: u32, pick_textile_file_path: &str, drop_textile_file_path: &str, setting: &Setting) -> Result<String, FileError> {
     
     let pick_textile_instruction = get_file_text(pick_textile_file_path).await?;
 
@@ -82,9 +117,13 @@ async fn get_pull_textile_instruction(length_to_pull: u32, pick_textile_file_pat
     instructions.push_str(&pick_textile_instruction);
     instructions.push('\n');
 
-    instructions.push_str(&format!("G1 Y{}", length_to_pull));
+    instructions.push_str(&get_unit_start_instruction(setting.length_unit));
+
+    instructions.push_str(&format!("G1 Y{}", setting.format_g_code_value(length_to_pull as Fixed)));
     instructions.push('\n');
 
+    instructions.push_str(&get_unit_end_instruction(setting.length_unit));
+
     instructions.push_str(&drop_textile_instruction);
     instructions.push('\n');
 
@@ -113,7 +152,14 @@ async fn get_start_program(start_program_file_path: &str) -> Result<String, File
     Ok(instructions)
 }
 
-async fn get_horizontal_lines(horizontal_lines: Vec<Line>, before_x_cut_file_path: &str, after_x_cut_file_path: &str) -> Result<String, FileError> {
+async fn get_horizontal_lines(
+    horizontal_lines: Vec<Line>, 
+    obstacle_list: &[PositionedRectangle], 
+    sheet_width: Fixed, 
+    before_x_cut_file_path: &str, 
+    after_x_cut_file_path: &str, 
+    setting: &Setting
+) -> Result<String, FileError> {
     let before_x_cut_instruction = get_file_text(before_x_cut_file_path).await?;
     let after_x_cut_instruction = get_file_text(after_x_cut_file_path).await?;
 
@@ -122,18 +168,21 @@ async fn get_horizontal_lines(horizontal_lines: Vec<Line>, before_x_cut_file_pat
     instructions.push_str("( Inicio cortar todas linhas horizontais )");
     instructions.push('\n');
 
+    instructions.push_str(&get_unit_start_instruction(setting.length_unit));
+
     for line in horizontal_lines {
+        let line = get_cut_line(&line, obstacle_list, sheet_width, setting);
 
         instructions.push_str("( Inicio cortar linha horizontal )");
         instructions.push('\n');
 
-        instructions.push_str(&get_move_fast_speed(line.start.pos_x, line.start.pos_y));
+        instructions.push_str(&get_move_fast_speed(line.start.pos_x, line.start.pos_y, setting));
         instructions.push('\n');
 
         instructions.push_str(&before_x_cut_instruction);
         instructions.push('\n');
 
-        instructions.push_str(&get_move_slow_speed(line.end.pos_x, line.end.pos_y));
+        instructions.push_str(&get_move_slow_speed(line.end.pos_x, line.end.pos_y, setting));
         instructions.push('\n');
 
         instructions.push_str(&after_x_cut_instruction);
@@ -143,6 +192,8 @@ async fn get_horizontal_lines(horizontal_lines: Vec<Line>, before_x_cut_file_pat
         instructions.push('\n');
     }
 
+    instructions.push_str(&get_unit_end_instruction(setting.length_unit));
+
     instructions.push_str("( Fim cortar todas linhas horizontais )");
     instructions.push('\n');
     instructions.push('\n');
@@ -150,7 +201,14 @@ async fn get_horizontal_lines(horizontal_lines: Vec<Line>, before_x_cut_file_pat
     Ok(instructions)
 }
 
-async fn get_vertical_lines(vertical_lines: Vec<Line>, before_y_cut_file_path: &str, after_y_cut_file_path: &str) -> Result<String, FileError> {
+async fn get_vertical_lines(
+    vertical_lines: Vec<Line>, 
+    obstacle_list: &[PositionedRectangle], 
+    sheet_length: Fixed, 
+    before_y_cut_file_path: &str, 
+    after_y_cut_file_path: &str, 
+    setting: &Setting
+) -> Result<String, FileError> {
     let before_y_cut_instruction = get_file_text(before_y_cut_file_path).await?;
     let after_y_cut_instruction = get_file_text(after_y_cut_file_path).await?;
 
@@ -159,18 +217,21 @@ async fn get_vertical_lines(vertical_lines: Vec<Line>, before_y_cut_file_path: &
     instructions.push_str("( Inicio cortar todas linhas verticais )");
     instructions.push('\n');
 
+    instructions.push_str(&get_unit_start_instruction(setting.length_unit));
+
     for line in vertical_lines {
+        let line = get_cut_line(&line, obstacle_list, sheet_length, setting);
 
         instructions.push_str("( Inicio cortar linha vertical )");
         instructions.push('\n');
 
-        instructions.push_str(&get_move_fast_speed(line.start.pos_x, line.start.pos_y));
+        instructions.push_str(&get_move_fast_speed(line.start.pos_x, line.start.pos_y, setting));
         instructions.push('\n');
 
         instructions.push_str(&before_y_cut_instruction);
         instructions.push('\n');
 
-        instructions.push_str(&get_move_slow_speed(line.end.pos_x, line.end.pos_y));
+        instructions.push_str(&get_move_slow_speed(line.end.pos_x, line.end.pos_y, setting));
         instructions.push('\n');
 
         instructions.push_str(&after_y_cut_instruction);
@@ -180,6 +241,8 @@ async fn get_vertical_lines(vertical_lines: Vec<Line>, before_y_cut_file_path: &
         instructions.push('\n');
     }
 
+    instructions.push_str(&get_unit_end_instruction(setting.length_unit));
+
     instructions.push_str("( Fim cortar todas linhas verticais )");
     instructions.push('\n');
     instructions.push('\n');
@@ -205,10 +268,175 @@ async fn get_end_program(end_program_file_path: &str) -> Result<String, FileErro
     Ok(instructions)
 }
 
-fn get_move_slow_speed(x: i32, y: i32) -> String {
-    format!("G1 X{} Y{}", x, y)
+// the head goes down before the line by the lead-in and the overcut and goes up after it by the overcut,
+// both stop at the obstacles and at the sides of the sheet
+pub fn get_cut_line(line: &Line, obstacle_list: &[PositionedRectangle], sheet_limit: Fixed, setting: &Setting) -> Line {
+    get_extended_line(line, setting.lead_in + setting.overcut, setting.overcut, obstacle_list, sheet_limit)
+}
+
+// the instruction files are written in millimetres, so only the moves generated here follow the unit chosen
+fn get_unit_start_instruction(length_unit: LengthUnit) -> String {
+    match length_unit.get_g_code_unit() {
+        LengthUnit::Inch => "G20 (configura cordenadas em polegadas)\n".to_string(),
+        _ => String::new(),
+    }
+}
+
+fn get_unit_end_instruction(length_unit: LengthUnit) -> String {
+    match length_unit.get_g_code_unit() {
+        LengthUnit::Inch => "G21 (configura cordenadas em mm)\n".to_string(),
+        _ => String::new(),
+    }
+}
+
+fn get_move_slow_speed(x: Fixed, y: Fixed, setting: &Setting) -> String {
+    format!("G1 X{} Y{}", setting.format_g_code_value(x), setting.format_g_code_value(y))
+}
+
+fn get_move_fast_speed(x: Fixed, y: Fixed, setting: &Setting) -> String {
+    format!("G0 X{} Y{}", setting.format_g_code_value(x), setting.format_g_code_value(y))
 }
+#[cfg(test)]
+mod tests {
+    use crate::models::cut_disposition::Vertex;
+
+    use super::*;
+
+    fn create_line(start_x: i32, start_y: i32, end_x: i32, end_y: i32) -> Line {
+        Line {
+            start: Vertex { pos_x: start_x, pos_y: start_y },
+            end: Vertex { pos_x: end_x, pos_y: end_y },
+        }
+    }
 
-fn get_move_fast_speed(x: i32, y: i32) -> String {
-    format!("G0 X{} Y{}", x, y)
-}
\ No newline at end of file
+    // the instructions of the machine are read from files, each test writes its own
+    fn create_instruction_file(name: &str, instruction: &str) -> String {
+        let path = std::env::temp_dir().join(format!("gcode_service_test_{}.txt", name));
+        std::fs::write(&path, instruction).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn get_unit_instruction_test() {
+        assert_eq!(get_unit_start_instruction(LengthUnit::Inch), "G20 (configura cordenadas em polegadas)\n");
+        assert_eq!(get_unit_end_instruction(LengthUnit::Inch), "G21 (configura cordenadas em mm)\n");
+        // the centimetres are written in millimetres, as the instruction files
+        for length_unit in [LengthUnit::Millimeter, LengthUnit::Centimeter] {
+            assert_eq!(get_unit_start_instruction(length_unit), "");
+            assert_eq!(get_unit_end_instruction(length_unit), "");
+        }
+    }
+
+    #[test]
+    fn get_move_decimal_places_test() {
+        let setting = Setting { length_unit: LengthUnit::Millimeter, g_code_decimal_places: 1, overcut: 0, lead_in: 0 };
+        assert_eq!(get_move_fast_speed(1236, 6, &setting), "G0 X12.4 Y0.1");
+        assert_eq!(get_move_slow_speed(-1236, 0, &setting), "G1 X-12.4 Y0.0");
+
+        let setting = Setting { length_unit: LengthUnit::Inch, g_code_decimal_places: 3, overcut: 0, lead_in: 0 };
+        // 25.4 mm and 10 mm
+        assert_eq!(get_move_slow_speed(2540, 1000, &setting), "G1 X1.000 Y0.394");
+
+        let setting = Setting { length_unit: LengthUnit::Centimeter, g_code_decimal_places: 0, overcut: 0, lead_in: 0 };
+        assert_eq!(get_move_fast_speed(1260, 1240, &setting), "G0 X13 Y12");
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- sheet -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+        <!-- obstacle -->
+        <rect x="18" y="40" width="1" height="20" style="fill:green;stroke:black;" />
+
+        <!-- the line and, dashed, the cut: the lead-in stops at the obstacle and the overcut at the side of the sheet -->
+        <line x1="20" y1="50" x2="99" y2="50" style="stroke:red;" />
+        <line x1="19" y1="51" x2="100" y2="51" style="stroke:black;stroke-dasharray:4;" />
+    </svg>
+ */
+    #[tokio::test]
+    async fn get_horizontal_lines_test() {
+        let before_x_cut_file_path = create_instruction_file("before_x_cut", "M3");
+        let after_x_cut_file_path = create_instruction_file("after_x_cut", "M5");
+        let setting = Setting { length_unit: LengthUnit::Millimeter, g_code_decimal_places: 2, overcut: 200, lead_in: 300 };
+        let obstacle_list = vec![
+            PositionedRectangle { id: 1, width: 100, length: 2000, top_left_vertex: Vertex { pos_x: 1800, pos_y: 4000 }, rotated: false, margin: None }
+        ];
+
+        let instructions = get_horizontal_lines(
+            vec![create_line(2000, 5000, 9900, 5000)], 
+            &obstacle_list, 
+            10000, 
+            &before_x_cut_file_path, 
+            &after_x_cut_file_path, 
+            &setting
+        ).await.unwrap();
+
+        assert_eq!(
+            instructions,
+            [
+                "( Inicio cortar todas linhas horizontais )",
+                "( Inicio cortar linha horizontal )",
+                "G0 X19.00 Y50.00",
+                "M3",
+                "G1 X100.00 Y50.00",
+                "M5",
+                "( Fim cortar linha horizontal )",
+                "( Fim cortar todas linhas horizontais )",
+                "",
+                "",
+            ].join("\n")
+        );
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- sheet -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+        <!-- obstacle -->
+        <rect x="40" y="60" width="20" height="10" style="fill:green;stroke:black;" />
+
+        <!-- the line cut upwards and, dashed, the cut: the lead-in stops at the obstacle and the overcut at the top of the sheet -->
+        <line x1="50" y1="58" x2="50" y2="1" style="stroke:red;" />
+        <line x1="51" y1="60" x2="51" y2="0" style="stroke:black;stroke-dasharray:4;" />
+    </svg>
+ */
+    #[tokio::test]
+    async fn get_vertical_lines_inch_test() {
+        let before_y_cut_file_path = create_instruction_file("before_y_cut", "M3");
+        let after_y_cut_file_path = create_instruction_file("after_y_cut", "M5");
+        let setting = Setting { length_unit: LengthUnit::Inch, g_code_decimal_places: 3, overcut: 200, lead_in: 300 };
+        let obstacle_list = vec![
+            PositionedRectangle { id: 1, width: 2000, length: 1000, top_left_vertex: Vertex { pos_x: 4000, pos_y: 6000 }, rotated: false, margin: None }
+        ];
+
+        let instructions = get_vertical_lines(
+            vec![create_line(5000, 5800, 5000, 100)], 
+            &obstacle_list, 
+            10000, 
+            &before_y_cut_file_path, 
+            &after_y_cut_file_path, 
+            &setting
+        ).await.unwrap();
+
+        // 50 mm, 60 mm and 0 mm in inches
+        assert_eq!(
+            instructions,
+            [
+                "( Inicio cortar todas linhas verticais )",
+                "G20 (configura cordenadas em polegadas)",
+                "( Inicio cortar linha vertical )",
+                "G0 X1.969 Y2.362",
+                "M3",
+                "G1 X1.969 Y0.000",
+                "M5",
+                "( Fim cortar linha vertical )",
+                "G21 (configura cordenadas em mm)",
+                "( Fim cortar todas linhas verticais )",
+                "",
+                "",
+            ].join("\n")
+        );
+    }
+}
@@ -0,0 +1,528 @@
+use crate::models::cut_disposition::{CutDispositionInput, CutDispositionOutput, PositionedRectangle, Rectangle, Vertex, PatternRepeat};
+
+use super::{
+    cut_disposition_service::{
+        NestingStrategy,
+        MainRectangleOrganized,
+        complete_cut_disposition_output,
+        get_max_length,
+        group_rectangles_by_id,
+        rectangle_bigger_area_comparator,
+        rectangle_maximum_y_comparator,
+        get_spacing_list,
+        get_max_margin
+    },
+    max_rects_service::{FreeRectangle, find_best_short_side_fit, get_vertices_for_showcases, get_rectangle_instance_list},
+    spatial_index_service::{SpatialIndex, CollisionSearch, get_cell_size}
+};
+
+// Every piece is placed at the top left corner of a free panel, which is then split in two by a single cut
+// from edge to edge, so the whole layout, showcases included, can be cut with through-cuts only
+pub struct GuillotineStrategy {
+    pub collision_search: CollisionSearch,
+}
+
+impl NestingStrategy for GuillotineStrategy {
+    fn get_rectangle_instance_list(&self, rectangles_list: &[Rectangle]) -> Vec<Rectangle> {
+        get_rectangle_instance_list(rectangles_list)
+    }
+
+    fn organize_in_order(&self, cut_disposition_input: &CutDispositionInput, rectangle_instance_list: Vec<Rectangle>) -> CutDispositionOutput {
+        let main_rectangle_organized = organize_main_rectangles(
+            get_max_length(cut_disposition_input),
+            cut_disposition_input.defined_length,
+            cut_disposition_input.spacing.unwrap_or(0),
+            cut_disposition_input.defined_width,
+            cut_disposition_input.allow_rotation,
+            rectangle_instance_list,
+            &cut_disposition_input.showcase_list,
+            &cut_disposition_input.prohibited_area_list,
+            &cut_disposition_input.pinned_piece_list,
+            cut_disposition_input.pattern_repeat.as_ref(),
+            self.collision_search
+        );
+
+        complete_cut_disposition_output(cut_disposition_input, main_rectangle_organized, self.collision_search)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn organize_main_rectangles(
+    max_length: i32,
+    defined_length: Option<i32>,
+    spacing: i32,
+    max_width: i32,
+    allow_rotation: bool,
+    rectangle_instance_list: Vec<Rectangle>,
+    showcase_list: &[Rectangle],
+    prohibited_area_list: &[PositionedRectangle],
+    pinned_piece_list: &[PositionedRectangle],
+    pattern_repeat: Option<&PatternRepeat>,
+    collision_search: CollisionSearch
+) -> MainRectangleOrganized {
+    let max_spacing = get_spacing_list(&rectangle_instance_list, spacing).into_iter().max().unwrap_or(spacing);
+
+    // every piece reserves its spacing at its right and bottom, so the fabric is extended by the biggest spacing as well
+    let mut free_panel_list = vec![
+        FreeRectangle {
+            pos_x: 0,
+            pos_y: 0,
+            width: max_width + max_spacing,
+            length: max_length + max_spacing
+        }
+    ];
+
+    // grown by the biggest margin at its right and bottom, like in the max rects strategy
+    let max_margin = get_max_margin(&rectangle_instance_list);
+
+    for prohibited_area in prohibited_area_list {
+        cut_prohibited_area_from_free_panel_list(
+            &mut free_panel_list,
+            &FreeRectangle {
+                pos_x: prohibited_area.top_left_vertex.pos_x,
+                pos_y: prohibited_area.top_left_vertex.pos_y,
+                width: prohibited_area.width + max_margin,
+                length: prohibited_area.length + max_margin
+            }
+        );
+    }
+
+    // the pinned pieces are cut out of the panels like the prohibited areas, keeping their spacing
+    for pinned_piece in pinned_piece_list {
+        cut_prohibited_area_from_free_panel_list(
+            &mut free_panel_list,
+            &FreeRectangle {
+                pos_x: pinned_piece.top_left_vertex.pos_x,
+                pos_y: pinned_piece.top_left_vertex.pos_y,
+                width: pinned_piece.width + pinned_piece.get_spacing(spacing),
+                length: pinned_piece.length + pinned_piece.get_spacing(spacing)
+            }
+        );
+    }
+
+    let mut unused_instances_list = Vec::<Rectangle>::new();
+
+    let mut positioned_rectangles_list = Vec::from(pinned_piece_list);
+
+    let mut positioned_rectangle_index = SpatialIndex::new_from_rectangle_list(
+        pinned_piece_list,
+        spacing,
+        get_cell_size(&rectangle_instance_list, max_width, collision_search)
+    );
+
+    for rectangle in rectangle_instance_list {
+        match place_in_free_panel_list(
+            &rectangle, 
+            spacing, 
+            allow_rotation, 
+            &mut free_panel_list, 
+            &positioned_rectangle_index, 
+            max_width, 
+            max_length, 
+            pattern_repeat
+        ) {
+            Some(positioned_rectangle) => {
+                positioned_rectangle_index.insert(positioned_rectangle.clone());
+                positioned_rectangles_list.push(positioned_rectangle);
+            },
+            None => unused_instances_list.push(rectangle.clone()),
+        }
+    }
+
+    let unused_rectangles_list = group_rectangles_by_id(&unused_instances_list);
+
+    let length_used =
+    match positioned_rectangles_list.iter().max_by(
+        |first, second| rectangle_maximum_y_comparator(first, second)
+    ) {
+        Some(rect) => rect.top_left_vertex.pos_y + rect.length,
+        None => 0,
+    };
+
+    // showcases can not make the fabric longer, so the panels are cut at the length used
+    let showcase_max_length = defined_length.unwrap_or(length_used);
+
+    free_panel_list = free_panel_list
+    .into_iter()
+    .filter(|free_panel| free_panel.pos_y < showcase_max_length + max_spacing)
+    .map(
+        |free_panel| FreeRectangle {
+            length: free_panel.length.min(showcase_max_length + max_spacing - free_panel.pos_y),
+            ..free_panel
+        }
+    )
+    .collect();
+
+    let positioned_showcase_list = organize_showcase_rectangles(
+        showcase_list, 
+        spacing, 
+        &mut free_panel_list, 
+        &positioned_rectangles_list, 
+        max_width, 
+        showcase_max_length,
+        collision_search
+    );
+
+    MainRectangleOrganized {
+        possible_vertex_for_rectangle_list: get_vertices_for_showcases(
+            spacing,
+            &free_panel_list,
+            &positioned_rectangles_list,
+            prohibited_area_list
+        ),
+        unused_rectangles_list,
+        positioned_rectangles_list,
+        positioned_showcase_list: Some(positioned_showcase_list),
+        length_used
+    }
+}
+
+// showcases are placed from the biggest to the smallest, so the smaller ones fill the panels left by the bigger ones
+fn organize_showcase_rectangles(
+    showcase_list: &[Rectangle],
+    spacing: i32,
+    free_panel_list: &mut Vec<FreeRectangle>,
+    positioned_rectangles_list: &[PositionedRectangle],
+    max_width: i32,
+    max_length: i32,
+    collision_search: CollisionSearch
+) -> Vec<PositionedRectangle> {
+    let mut showcase_list_sorted = Vec::from(showcase_list);
+
+    showcase_list_sorted.sort_by(rectangle_bigger_area_comparator);
+
+    let mut positioned_showcase_list = Vec::<PositionedRectangle>::new();
+
+    let mut neighbour_index = SpatialIndex::new_from_rectangle_list(
+        positioned_rectangles_list,
+        spacing,
+        get_cell_size(showcase_list, max_width, collision_search)
+    );
+
+    for showcase in showcase_list_sorted {
+        let mut placed_quantity = 0;
+
+        loop {
+            if showcase.max_quantity.is_some_and(|max_quantity| placed_quantity >= max_quantity) {
+                break;
+            }
+
+            // showcases keep their orientation, same as when they fill the holes of the other strategies
+            match place_in_free_panel_list(&showcase, spacing, false, free_panel_list, &neighbour_index, max_width, max_length, None) {
+                Some(positioned_showcase) => {
+                    neighbour_index.insert(positioned_showcase.clone());
+                    positioned_showcase_list.push(positioned_showcase);
+                    placed_quantity += 1;
+                },
+                None => break,
+            }
+        }
+    }
+
+    positioned_showcase_list
+}
+
+#[allow(clippy::too_many_arguments)]
+fn place_in_free_panel_list(
+    rectangle: &Rectangle,
+    spacing: i32,
+    allow_rotation: bool,
+    free_panel_list: &mut Vec<FreeRectangle>,
+    neighbour_index: &SpatialIndex,
+    max_width: i32,
+    max_length: i32,
+    pattern_repeat: Option<&PatternRepeat>
+) -> Option<PositionedRectangle> {
+    let positioned_rectangle = find_best_short_side_fit(
+        rectangle, 
+        spacing, 
+        allow_rotation, 
+        free_panel_list, 
+        neighbour_index, 
+        max_width, 
+        max_length, 
+        pattern_repeat
+    )?;
+
+    let vertex = &positioned_rectangle.top_left_vertex;
+
+    // free panels never overlap, so only one of them holds the vertex chosen
+    let free_panel_index = free_panel_list
+    .iter()
+    .position(
+        |free_panel|
+        free_panel.pos_x <= vertex.pos_x && vertex.pos_x < free_panel.pos_x + free_panel.width &&
+        free_panel.pos_y <= vertex.pos_y && vertex.pos_y < free_panel.pos_y + free_panel.length
+    )?;
+
+    let free_panel = free_panel_list.remove(free_panel_index);
+
+    let (mut strip_list, corner_panel) = cut_free_panel_at_vertex(&free_panel, vertex);
+
+    free_panel_list.append(&mut strip_list);
+
+    free_panel_list.append(
+        &mut split_free_panel(
+            &corner_panel,
+            positioned_rectangle.width + positioned_rectangle.get_spacing(spacing),
+            positioned_rectangle.length + positioned_rectangle.get_spacing(spacing)
+        )
+    );
+
+    Some(positioned_rectangle)
+}
+
+// a piece moved to a repeat of the print does not start at the corner of the panel, so the panel is cut
+// from edge to edge at the left of the vertex, then above it
+fn cut_free_panel_at_vertex(free_panel: &FreeRectangle, vertex: &Vertex) -> (Vec<FreeRectangle>, FreeRectangle) {
+    let left_panel = FreeRectangle {
+        width: vertex.pos_x - free_panel.pos_x,
+        ..free_panel.clone()
+    };
+    let top_panel = FreeRectangle {
+        pos_x: vertex.pos_x,
+        pos_y: free_panel.pos_y,
+        width: free_panel.pos_x + free_panel.width - vertex.pos_x,
+        length: vertex.pos_y - free_panel.pos_y
+    };
+    let corner_panel = FreeRectangle {
+        pos_x: vertex.pos_x,
+        pos_y: vertex.pos_y,
+        width: free_panel.pos_x + free_panel.width - vertex.pos_x,
+        length: free_panel.pos_y + free_panel.length - vertex.pos_y
+    };
+
+    (
+        [left_panel, top_panel]
+        .into_iter()
+        .filter(|panel| panel.width > 0 && panel.length > 0)
+        .collect(),
+        corner_panel
+    )
+}
+
+// the cut is made along the shorter side left over, so the bigger panel left is kept whole
+fn split_free_panel(free_panel: &FreeRectangle, used_width: i32, used_length: i32) -> Vec<FreeRectangle> {
+    let width_left_over = free_panel.width - used_width;
+    let length_left_over = free_panel.length - used_length;
+
+    let (right_panel, bottom_panel) = if width_left_over <= length_left_over {
+        // horizontal cut from edge to edge, then a vertical cut at the right of the rectangle
+        (
+            FreeRectangle {
+                pos_x: free_panel.pos_x + used_width,
+                pos_y: free_panel.pos_y,
+                width: width_left_over,
+                length: used_length
+            },
+            FreeRectangle {
+                pos_x: free_panel.pos_x,
+                pos_y: free_panel.pos_y + used_length,
+                width: free_panel.width,
+                length: length_left_over
+            }
+        )
+    } else {
+        // vertical cut from edge to edge, then a horizontal cut below the rectangle
+        (
+            FreeRectangle {
+                pos_x: free_panel.pos_x + used_width,
+                pos_y: free_panel.pos_y,
+                width: width_left_over,
+                length: free_panel.length
+            },
+            FreeRectangle {
+                pos_x: free_panel.pos_x,
+                pos_y: free_panel.pos_y + used_length,
+                width: used_width,
+                length: length_left_over
+            }
+        )
+    };
+
+    [right_panel, bottom_panel]
+    .into_iter()
+    .filter(|panel| panel.width > 0 && panel.length > 0)
+    .collect()
+}
+
+// the panel is cut above and below the prohibited area from edge to edge, then at its left and right
+fn cut_prohibited_area_from_free_panel_list(free_panel_list: &mut Vec<FreeRectangle>, prohibited_area: &FreeRectangle) {
+    let mut new_free_panel_list = Vec::<FreeRectangle>::new();
+
+    for free_panel in free_panel_list.iter() {
+        if !free_panel.intersect(prohibited_area) {
+            new_free_panel_list.push(free_panel.clone());
+            continue;
+        }
+
+        let free_panel_bottom = free_panel.pos_y + free_panel.length;
+        let prohibited_area_bottom = prohibited_area.pos_y + prohibited_area.length;
+
+        let row_top = free_panel.pos_y.max(prohibited_area.pos_y);
+        let row_bottom = free_panel_bottom.min(prohibited_area_bottom);
+
+        let panel_list = [
+            // top side
+            FreeRectangle {
+                length: prohibited_area.pos_y - free_panel.pos_y,
+                ..free_panel.clone()
+            },
+            // bottom side
+            FreeRectangle {
+                pos_y: prohibited_area_bottom,
+                length: free_panel_bottom - prohibited_area_bottom,
+                ..free_panel.clone()
+            },
+            // left side
+            FreeRectangle {
+                pos_x: free_panel.pos_x,
+                pos_y: row_top,
+                width: prohibited_area.pos_x - free_panel.pos_x,
+                length: row_bottom - row_top
+            },
+            // rigth side
+            FreeRectangle {
+                pos_x: prohibited_area.pos_x + prohibited_area.width,
+                pos_y: row_top,
+                width: free_panel.pos_x + free_panel.width - prohibited_area.pos_x - prohibited_area.width,
+                length: row_bottom - row_top
+            },
+        ];
+
+        new_free_panel_list.append(
+            &mut panel_list
+            .into_iter()
+            .filter(|panel| panel.width > 0 && panel.length > 0)
+            .collect()
+        );
+    }
+
+    *free_panel_list = new_free_panel_list;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::cutting_lines_service::is_guillotine_cuttable;
+
+    use super::*;
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- fabric -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <!-- prohibited areas -->
+        <rect x="40" y="40" width="20" height="20" style="fill:black;stroke:black;" />
+
+        <!-- positioned rectangles -->
+        <rect x="0" y="0" width="100" height="30" style="fill:green;stroke:black;" />
+
+        <rect x="0" y="60" width="40" height="40" style="fill:green;stroke:black;" />
+
+        <rect x="40" y="60" width="40" height="40" style="fill:green;stroke:black;" />
+
+        <!-- showcases -->
+        <rect x="0" y="30" width="100" height="10" style="fill:yellow;stroke:black;" />
+
+    </svg>
+
+ */
+    #[test]
+    fn organize_main_rectangles_guillotine_test() {
+        let prohibited_area = PositionedRectangle {
+            id: 1,
+            width: 20,
+            length: 20,
+            top_left_vertex: Vertex { pos_x: 40, pos_y: 40 },
+            rotated: false,
+            margin: None,
+        };
+        let rect1 = Rectangle {
+            id: 2,
+            width: 100,
+            length: 30,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let rect2 = Rectangle {
+            id: 3,
+            width: 40,
+            length: 40,
+            allow_rotation: None,
+            quantity: 2,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let showcase = Rectangle {
+            id: 4,
+            width: 100,
+            length: 10,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: Some(1),
+            match_pattern: false,
+            margin: None,
+        };
+
+        let result = organize_main_rectangles(
+            100,
+            None,
+            0,
+            100,
+            false,
+            get_rectangle_instance_list(&[rect1.clone(), rect2.clone()]),
+            std::slice::from_ref(&showcase),
+            std::slice::from_ref(&prohibited_area),
+            &[],
+            None,
+            CollisionSearch::SpatialIndex
+        );
+
+        let expected_positioned_rectangles_list = vec![
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect1, &Vertex { pos_x: 0, pos_y: 0 }),
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect2, &Vertex { pos_x: 0, pos_y: 60 }),
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect2, &Vertex { pos_x: 40, pos_y: 60 }),
+        ];
+
+        let expected_positioned_showcase_list = vec![
+            PositionedRectangle::new_from_rectangle_and_vertex(&showcase, &Vertex { pos_x: 0, pos_y: 30 }),
+        ];
+
+        assert_eq!(result.positioned_rectangles_list, expected_positioned_rectangles_list);
+        assert_eq!(result.positioned_showcase_list, Some(expected_positioned_showcase_list));
+        assert!(result.unused_rectangles_list.is_empty());
+        assert_eq!(result.length_used, 100);
+
+        let mut cut_rectangles_list = result.positioned_rectangles_list.clone();
+        cut_rectangles_list.append(&mut result.positioned_showcase_list.unwrap_or_default());
+        cut_rectangles_list.push(prohibited_area);
+        assert!(is_guillotine_cuttable(&cut_rectangles_list));
+    }
+
+    #[test]
+    fn split_free_panel_test() {
+        let free_panel = FreeRectangle { pos_x: 0, pos_y: 0, width: 100, length: 200 };
+
+        assert_eq!(
+            split_free_panel(&free_panel, 60, 50),
+            vec![
+                FreeRectangle { pos_x: 60, pos_y: 0, width: 40, length: 50 },
+                FreeRectangle { pos_x: 0, pos_y: 50, width: 100, length: 150 },
+            ]
+        );
+
+        assert_eq!(
+            split_free_panel(&free_panel, 20, 190),
+            vec![
+                FreeRectangle { pos_x: 20, pos_y: 0, width: 80, length: 200 },
+                FreeRectangle { pos_x: 0, pos_y: 190, width: 20, length: 10 },
+            ]
+        );
+    }
+}
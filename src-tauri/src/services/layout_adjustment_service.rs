@@ -0,0 +1,318 @@
+use crate::models::{cut_disposition::{CutDispositionInput, CutDispositionOutput, PositionedRectangle, Rectangle, Vertex, LayoutAdjustment}, app_error::AppError, fixed_point::get_area};
+
+use super::{cut_disposition_service::snap_vertex_to_pattern, cutting_lines_service::is_guillotine_cuttable};
+
+// Applies a manual adjustment to a sheet already organized, the violations it creates are left to validate_disposition
+pub fn adjust_disposition_sheet(
+    cut_disposition_input: &CutDispositionInput,
+    sheet: &CutDispositionOutput,
+    adjustment: &LayoutAdjustment
+) -> Result<CutDispositionOutput, AppError> {
+    let mut positioned_rectangles_list = sheet.positioned_rectangles_list.clone();
+
+    match adjustment {
+        LayoutAdjustment::Move { piece_index, top_left_vertex } => {
+            let piece = get_adjustable_piece(cut_disposition_input, &positioned_rectangles_list, *piece_index)?;
+            positioned_rectangles_list[*piece_index].top_left_vertex = snap_piece_vertex(cut_disposition_input, &piece, top_left_vertex);
+        },
+        LayoutAdjustment::Swap { piece_index, other_piece_index } => {
+            let piece = get_adjustable_piece(cut_disposition_input, &positioned_rectangles_list, *piece_index)?;
+            let other_piece = get_adjustable_piece(cut_disposition_input, &positioned_rectangles_list, *other_piece_index)?;
+            positioned_rectangles_list[*piece_index].top_left_vertex = snap_piece_vertex(cut_disposition_input, &piece, &other_piece.top_left_vertex);
+            positioned_rectangles_list[*other_piece_index].top_left_vertex = snap_piece_vertex(cut_disposition_input, &other_piece, &piece.top_left_vertex);
+        },
+        LayoutAdjustment::Rotate { piece_index } => {
+            let piece = get_adjustable_piece(cut_disposition_input, &positioned_rectangles_list, *piece_index)?;
+            // turning a rotated piece back is always allowed
+            if !piece.rotated && !can_rotate_piece(cut_disposition_input, piece.id) {
+                return Err(AppError::new(1, format!("Erro ao ajustar disposição: peça {} não pode ser girada", piece.id).as_str()));
+            }
+            positioned_rectangles_list[*piece_index] = PositionedRectangle {
+                width: piece.length,
+                length: piece.width,
+                rotated: !piece.rotated,
+                ..piece
+            };
+        },
+    }
+
+    Ok(complete_adjusted_sheet(sheet, positioned_rectangles_list))
+}
+
+// the pinned pieces are cut where the operator placed them
+fn get_adjustable_piece(
+    cut_disposition_input: &CutDispositionInput,
+    positioned_rectangles_list: &[PositionedRectangle],
+    piece_index: usize
+) -> Result<PositionedRectangle, AppError> {
+    let piece = positioned_rectangles_list
+        .get(piece_index)
+        .ok_or_else(|| AppError::new(1, "Erro ao ajustar disposição: peça não encontrada na folha"))?;
+
+    if cut_disposition_input.pinned_piece_list.iter().any(|pinned_piece| pinned_piece.id == piece.id) {
+        return Err(AppError::new(1, format!("Erro ao ajustar disposição: peça fixa {} não pode ser ajustada", piece.id).as_str()));
+    }
+
+    Ok(piece.clone())
+}
+
+fn find_rectangle(cut_disposition_input: &CutDispositionInput, id: u32) -> Option<&Rectangle> {
+    cut_disposition_input.rectangles_list.iter().find(|rectangle| rectangle.id == id)
+}
+
+fn snap_piece_vertex(cut_disposition_input: &CutDispositionInput, piece: &PositionedRectangle, vertex: &Vertex) -> Vertex {
+    match find_rectangle(cut_disposition_input, piece.id) {
+        Some(rectangle) => snap_vertex_to_pattern(rectangle, vertex, cut_disposition_input.pattern_repeat.as_ref()),
+        None => vertex.clone(),
+    }
+}
+
+fn can_rotate_piece(cut_disposition_input: &CutDispositionInput, id: u32) -> bool {
+    cut_disposition_input.fabric_grain.allows_quarter_turn() &&
+    find_rectangle(cut_disposition_input, id).is_some_and(|rectangle| rectangle.can_rotate(cut_disposition_input.allow_rotation))
+}
+
+// The fabric pulled follows the pieces moved past the end of the sheet, a defined length is kept
+fn complete_adjusted_sheet(sheet: &CutDispositionOutput, positioned_rectangles_list: Vec<PositionedRectangle>) -> CutDispositionOutput {
+    let mut cut_rectangles_list = positioned_rectangles_list.clone();
+    cut_rectangles_list.append(&mut sheet.showcase_rectangles_located_list.clone());
+
+    let length_used = match sheet.defined_length {
+        Some(_) => sheet.length_used,
+        None => cut_rectangles_list
+            .iter()
+            .map(|cut_rectangle| cut_rectangle.top_left_vertex.pos_y + cut_rectangle.length)
+            .max()
+            .unwrap_or(0),
+    };
+
+    // the usage is computed against the usable band, as when the sheet was organized
+    let edge_margins = &sheet.edge_margins;
+    let total_area = get_area(sheet.defined_width - edge_margins.left - edge_margins.right, (length_used - edge_margins.top).max(0));
+
+    let mut usage = 0.0;
+    if total_area != 0 && sheet.used_area != 0 {
+        usage = (sheet.used_area as f64) / (total_area as f64);
+    }
+
+    CutDispositionOutput {
+        positioned_rectangles_list,
+        length_used,
+        total_area,
+        usage,
+        is_guillotine: is_guillotine_cuttable(&cut_rectangles_list),
+        ..sheet.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{models::{cut_disposition::DispositionViolation, fabric::FabricGrain}, services::{cut_disposition_service::{organize_disposition, NoOrganizeObserver}, disposition_validation_service::validate_disposition}, test_support::get_cut_disposition_input};
+
+    use super::*;
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- fabric -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <!-- before -->
+        <rect x="0" y="0" width="60" height="30" style="fill:green;stroke:black;" />
+        <rect x="65" y="0" width="30" height="30" style="fill:green;stroke:black;" />
+
+        <!-- after moving the second piece -->
+        <rect x="0" y="35" width="30" height="30" style="fill:blue;stroke:black;" />
+    </svg>
+ */
+    #[test]
+    fn adjust_disposition_sheet_move_test() {
+        let rect1 = Rectangle {
+            id: 1,
+            width: 60,
+            length: 30,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let rect2 = Rectangle {
+            id: 2,
+            width: 30,
+            length: 30,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let cut_disposition_input = CutDispositionInput {
+            spacing: Some(5),
+            ..get_cut_disposition_input(vec![rect1.clone(), rect2.clone()])
+        };
+
+        let sheet = organize_disposition(&cut_disposition_input, 1, &NoOrganizeObserver);
+        assert_eq!(
+            sheet.positioned_rectangles_list,
+            vec![
+                PositionedRectangle::new_from_rectangle_and_vertex(&rect1, &Vertex { pos_x: 0, pos_y: 0 }),
+                PositionedRectangle::new_from_rectangle_and_vertex(&rect2, &Vertex { pos_x: 65, pos_y: 0 }),
+            ]
+        );
+
+        let adjusted_sheet = adjust_disposition_sheet(
+            &cut_disposition_input,
+            &sheet,
+            &LayoutAdjustment::Move { piece_index: 1, top_left_vertex: Vertex { pos_x: 0, pos_y: 35 } }
+        ).unwrap();
+
+        assert_eq!(adjusted_sheet.positioned_rectangles_list[1].top_left_vertex, Vertex { pos_x: 0, pos_y: 35 });
+        assert_eq!(adjusted_sheet.length_used, 65);
+        assert_eq!(adjusted_sheet.total_area, 6500);
+        assert_eq!(adjusted_sheet.used_area, sheet.used_area);
+        assert!(validate_disposition(&adjusted_sheet, 5).is_empty());
+
+        let overlapping_sheet = adjust_disposition_sheet(
+            &cut_disposition_input,
+            &sheet,
+            &LayoutAdjustment::Move { piece_index: 1, top_left_vertex: Vertex { pos_x: 50, pos_y: 0 } }
+        ).unwrap();
+        assert_eq!(validate_disposition(&overlapping_sheet, 5), vec![DispositionViolation::Overlap { id: 1, other_id: 2 }]);
+
+        let too_close_sheet = adjust_disposition_sheet(
+            &cut_disposition_input,
+            &sheet,
+            &LayoutAdjustment::Move { piece_index: 1, top_left_vertex: Vertex { pos_x: 62, pos_y: 0 } }
+        ).unwrap();
+        assert_eq!(validate_disposition(&too_close_sheet, 5), vec![DispositionViolation::SpacingBreach { id: 1, other_id: 2, spacing: 5 }]);
+
+        let outside_sheet = adjust_disposition_sheet(
+            &cut_disposition_input,
+            &sheet,
+            &LayoutAdjustment::Move { piece_index: 1, top_left_vertex: Vertex { pos_x: 80, pos_y: 0 } }
+        ).unwrap();
+        assert_eq!(validate_disposition(&outside_sheet, 5), vec![DispositionViolation::OutOfBounds { id: 2 }]);
+
+        assert!(
+            adjust_disposition_sheet(
+                &cut_disposition_input,
+                &sheet,
+                &LayoutAdjustment::Move { piece_index: 2, top_left_vertex: Vertex { pos_x: 0, pos_y: 0 } }
+            ).is_err()
+        );
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- fabric -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <!-- before -->
+        <rect x="0" y="0" width="60" height="30" style="fill:green;stroke:black;" />
+        <rect x="0" y="35" width="60" height="20" style="fill:green;stroke:black;" />
+
+        <!-- after the swap -->
+        <rect x="150" y="0" width="60" height="20" style="fill:blue;stroke:black;" />
+        <rect x="150" y="35" width="60" height="30" style="fill:blue;stroke:black;" />
+    </svg>
+ */
+    #[test]
+    fn adjust_disposition_sheet_swap_test() {
+        let rect1 = Rectangle {
+            id: 1,
+            width: 60,
+            length: 30,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let rect2 = Rectangle {
+            id: 2,
+            width: 60,
+            length: 20,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let cut_disposition_input = CutDispositionInput {
+            spacing: Some(5),
+            ..get_cut_disposition_input(vec![rect1.clone(), rect2.clone()])
+        };
+
+        let sheet = organize_disposition(&cut_disposition_input, 1, &NoOrganizeObserver);
+        assert_eq!(sheet.length_used, 55);
+
+        let adjusted_sheet = adjust_disposition_sheet(
+            &cut_disposition_input,
+            &sheet,
+            &LayoutAdjustment::Swap { piece_index: 0, other_piece_index: 1 }
+        ).unwrap();
+
+        assert_eq!(
+            adjusted_sheet.positioned_rectangles_list,
+            vec![
+                PositionedRectangle::new_from_rectangle_and_vertex(&rect1, &Vertex { pos_x: 0, pos_y: 35 }),
+                PositionedRectangle::new_from_rectangle_and_vertex(&rect2, &Vertex { pos_x: 0, pos_y: 0 }),
+            ]
+        );
+        assert_eq!(adjusted_sheet.length_used, 65);
+        assert!(adjusted_sheet.is_guillotine);
+        assert!(validate_disposition(&adjusted_sheet, 5).is_empty());
+    }
+
+    #[test]
+    fn adjust_disposition_sheet_rotate_test() {
+        let rect = Rectangle {
+            id: 1,
+            width: 60,
+            length: 30,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let pinned_piece = PositionedRectangle {
+            id: 2,
+            width: 20,
+            length: 20,
+            top_left_vertex: Vertex { pos_x: 80, pos_y: 80 },
+            rotated: false,
+            margin: None,
+        };
+
+        let mut cut_disposition_input = CutDispositionInput {
+            spacing: Some(5),
+            pinned_piece_list: vec![pinned_piece.clone()],
+            ..get_cut_disposition_input(vec![rect.clone()])
+        };
+
+        let sheet = organize_disposition(&cut_disposition_input, 1, &NoOrganizeObserver);
+        assert_eq!(sheet.positioned_rectangles_list[0], pinned_piece);
+
+        let rotate = LayoutAdjustment::Rotate { piece_index: 1 };
+
+        // the disposition does not allow rotation
+        assert!(adjust_disposition_sheet(&cut_disposition_input, &sheet, &rotate).is_err());
+        // the pinned piece is never adjusted
+        assert!(adjust_disposition_sheet(&cut_disposition_input, &sheet, &LayoutAdjustment::Rotate { piece_index: 0 }).is_err());
+
+        cut_disposition_input.allow_rotation = true;
+        let adjusted_sheet = adjust_disposition_sheet(&cut_disposition_input, &sheet, &rotate).unwrap();
+        assert_eq!(
+            adjusted_sheet.positioned_rectangles_list[1],
+            PositionedRectangle::new_from_rotated_rectangle_and_vertex(&rect, &Vertex { pos_x: 0, pos_y: 0 })
+        );
+        assert_eq!(adjusted_sheet.length_used, 100);
+
+        // the grain of the fabric wins over the disposition
+        cut_disposition_input.fabric_grain = FabricGrain::RotationForbidden;
+        assert!(adjust_disposition_sheet(&cut_disposition_input, &sheet, &rotate).is_err());
+    }
+}
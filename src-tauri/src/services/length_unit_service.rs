@@ -0,0 +1,92 @@
+/*
+    The interface shows and types the lengths in the unit of the setting, the backend keeps them in millimetres.
+    The commands read their arguments with FromLengthUnit and answer with InLengthUnit,
+    the values are converted by the serde of the fixed point while the unit is set.
+ */
+
+use std::marker::PhantomData;
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::{Pool, Sqlite};
+
+use crate::models::{
+    app_error::{AppError, DEFAULT_ERROR_CODE},
+    fixed_point::with_length_unit,
+    setting::{self, LengthUnit},
+};
+
+pub async fn get_length_unit(poll: &Pool<Sqlite>) -> Result<LengthUnit, AppError> {
+    match setting::get(poll).await {
+        Ok(setting) => Ok(setting.length_unit),
+        Err(_error) => Err(AppError::new(DEFAULT_ERROR_CODE, "Falha ao buscar a unidade de medida")),
+    }
+}
+
+// Answer of a command, the lengths are written in the unit given
+pub struct InLengthUnit<T> {
+    value: T,
+    length_unit: LengthUnit,
+}
+impl<T> InLengthUnit<T> {
+    pub fn new(value: T, length_unit: LengthUnit) -> InLengthUnit<T> {
+        InLengthUnit { value, length_unit }
+    }
+}
+impl<T: Serialize> Serialize for InLengthUnit<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        with_length_unit(self.length_unit, || self.value.serialize(serializer))
+    }
+}
+
+// Argument of a command, kept as it came until the unit is known
+pub struct FromLengthUnit<T> {
+    value: serde_json::Value,
+    phantom: PhantomData<T>,
+}
+impl<T: DeserializeOwned> FromLengthUnit<T> {
+    pub fn convert(&self, length_unit: LengthUnit) -> Result<T, AppError> {
+        match with_length_unit(length_unit, || T::deserialize(&self.value)) {
+            Ok(value) => Ok(value),
+            Err(_error) => Err(AppError::new(DEFAULT_ERROR_CODE, "Falha ao ler as medidas")),
+        }
+    }
+}
+impl<'de, T> Deserialize<'de> for FromLengthUnit<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(FromLengthUnit {
+            value: serde_json::Value::deserialize(deserializer)?,
+            phantom: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::cut_disposition::{PositionedRectangle, Vertex};
+
+    use super::*;
+
+    #[test]
+    fn length_unit_round_trip_test() {
+        let piece = PositionedRectangle {
+            id: 1,
+            width: 25_400,
+            length: 1_000,
+            top_left_vertex: Vertex { pos_x: 12_700, pos_y: 0 },
+            rotated: false,
+            margin: Some(50),
+        };
+
+        let json = serde_json::to_value(InLengthUnit::new(piece.clone(), LengthUnit::Inch)).unwrap();
+        assert_eq!(json["width"], 10.0);
+        assert_eq!(json["length"], 0.3937);
+        assert_eq!(json["top_left_vertex"]["pos_x"], 5.0);
+        assert_eq!(json["margin"], 0.0197);
+
+        let argument: FromLengthUnit<PositionedRectangle> = serde_json::from_value(json).unwrap();
+        assert_eq!(argument.convert(LengthUnit::Inch).unwrap(), piece);
+
+        let argument: FromLengthUnit<PositionedRectangle> = serde_json::from_value(serde_json::json!({ "id": 1 })).unwrap();
+        assert!(argument.convert(LengthUnit::Centimeter).is_err());
+    }
+}
@@ -0,0 +1,571 @@
+use std::cmp::Ordering;
+
+use crate::models::cut_disposition::{CutDispositionInput, CutDispositionOutput, PositionedRectangle, Rectangle, Vertex, PatternRepeat};
+
+use super::cut_disposition_service::{
+    NestingStrategy,
+    MainRectangleOrganized,
+    complete_cut_disposition_output,
+    get_max_length,
+    expand_rectangles_by_quantity,
+    group_rectangles_by_id,
+    rectangle_bigger_area_comparator,
+    vertex_closest_to_top_and_left_comparator,
+    create_available_vertices_for_prohibited_area,
+    create_available_vertices_for_positioning,
+    rectangle_maximum_y_comparator,
+    snap_vertex_to_pattern,
+    get_spacing_list,
+    get_max_margin,
+    is_within_boundaries
+};
+use super::spatial_index_service::{SpatialIndex, CollisionSearch, get_cell_size};
+
+// Keeps the list of maximal free rectangles of the fabric and places each piece, from the biggest to the smallest area,
+// in the free rectangle that leaves the shortest side left over (best short side fit)
+pub struct MaxRectsStrategy {
+    pub collision_search: CollisionSearch,
+}
+
+impl NestingStrategy for MaxRectsStrategy {
+    fn get_rectangle_instance_list(&self, rectangles_list: &[Rectangle]) -> Vec<Rectangle> {
+        get_rectangle_instance_list(rectangles_list)
+    }
+
+    fn organize_in_order(&self, cut_disposition_input: &CutDispositionInput, rectangle_instance_list: Vec<Rectangle>) -> CutDispositionOutput {
+        let main_rectangle_organized = organize_main_rectangles(
+            get_max_length(cut_disposition_input),
+            cut_disposition_input.spacing.unwrap_or(0),
+            cut_disposition_input.defined_width,
+            cut_disposition_input.allow_rotation,
+            rectangle_instance_list,
+            &cut_disposition_input.prohibited_area_list,
+            &cut_disposition_input.pinned_piece_list,
+            cut_disposition_input.pattern_repeat.as_ref(),
+            self.collision_search
+        );
+
+        complete_cut_disposition_output(cut_disposition_input, main_rectangle_organized, self.collision_search)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FreeRectangle {
+    pub pos_x: i32,
+    pub pos_y: i32,
+    pub width: i32,
+    pub length: i32,
+}
+impl FreeRectangle {
+    pub fn contains(&self, other: &FreeRectangle) -> bool {
+        other.pos_x >= self.pos_x &&
+        other.pos_y >= self.pos_y &&
+        other.pos_x + other.width <= self.pos_x + self.width &&
+        other.pos_y + other.length <= self.pos_y + self.length
+    }
+
+    pub fn intersect(&self, other: &FreeRectangle) -> bool {
+        other.pos_x < self.pos_x + self.width &&
+        other.pos_x + other.width > self.pos_x &&
+        other.pos_y < self.pos_y + self.length &&
+        other.pos_y + other.length > self.pos_y
+    }
+}
+
+struct Placement {
+    positioned_rectangle: PositionedRectangle,
+    // (short side left over, long side left over, pos y, pos x)
+    score: (i32, i32, i32, i32),
+}
+
+pub fn get_rectangle_instance_list(rectangles_list: &[Rectangle]) -> Vec<Rectangle> {
+    let mut rectangle_instance_list = expand_rectangles_by_quantity(rectangles_list);
+
+    rectangle_instance_list.sort_by(rectangle_bigger_area_comparator);
+
+    rectangle_instance_list
+}
+
+#[allow(clippy::too_many_arguments)]
+fn organize_main_rectangles(
+    max_length: i32,
+    spacing: i32,
+    max_width: i32,
+    allow_rotation: bool,
+    rectangle_instance_list: Vec<Rectangle>,
+    prohibited_area_list: &[PositionedRectangle],
+    pinned_piece_list: &[PositionedRectangle],
+    pattern_repeat: Option<&PatternRepeat>,
+    collision_search: CollisionSearch
+) -> MainRectangleOrganized {
+    let max_spacing = get_spacing_list(&rectangle_instance_list, spacing).into_iter().max().unwrap_or(spacing);
+
+    // every piece reserves its spacing at its right and bottom, so the fabric is extended by the biggest spacing as well
+    let mut free_rectangle_list = vec![
+        FreeRectangle {
+            pos_x: 0,
+            pos_y: 0,
+            width: max_width + max_spacing,
+            length: max_length + max_spacing
+        }
+    ];
+
+    // a piece at the left or above a prohibited area already reserves its margin, the area is grown at its right and bottom
+    // so a piece with a margin does not butt up to it from the other sides
+    let max_margin = get_max_margin(&rectangle_instance_list);
+
+    for prohibited_area in prohibited_area_list {
+        split_free_rectangle_list(
+            &mut free_rectangle_list,
+            &FreeRectangle {
+                pos_x: prohibited_area.top_left_vertex.pos_x,
+                pos_y: prohibited_area.top_left_vertex.pos_y,
+                width: prohibited_area.width + max_margin,
+                length: prohibited_area.length + max_margin
+            }
+        );
+    }
+
+    // the pinned pieces reserve their spacing like any piece placed
+    for pinned_piece in pinned_piece_list {
+        split_free_rectangle_list(
+            &mut free_rectangle_list,
+            &FreeRectangle {
+                pos_x: pinned_piece.top_left_vertex.pos_x,
+                pos_y: pinned_piece.top_left_vertex.pos_y,
+                width: pinned_piece.width + pinned_piece.get_spacing(spacing),
+                length: pinned_piece.length + pinned_piece.get_spacing(spacing)
+            }
+        );
+    }
+
+    let mut unused_instances_list = Vec::<Rectangle>::new();
+
+    let mut positioned_rectangles_list = Vec::from(pinned_piece_list);
+
+    let mut positioned_rectangle_index = SpatialIndex::new_from_rectangle_list(
+        pinned_piece_list,
+        spacing,
+        get_cell_size(&rectangle_instance_list, max_width, collision_search)
+    );
+
+    for rectangle in rectangle_instance_list {
+        match find_best_short_side_fit(
+            &rectangle, 
+            spacing, 
+            allow_rotation, 
+            &free_rectangle_list, 
+            &positioned_rectangle_index, 
+            max_width, 
+            max_length, 
+            pattern_repeat
+        ) {
+            Some(positioned_rectangle) => {
+                split_free_rectangle_list(
+                    &mut free_rectangle_list,
+                    &FreeRectangle {
+                        pos_x: positioned_rectangle.top_left_vertex.pos_x,
+                        pos_y: positioned_rectangle.top_left_vertex.pos_y,
+                        width: positioned_rectangle.width + positioned_rectangle.get_spacing(spacing),
+                        length: positioned_rectangle.length + positioned_rectangle.get_spacing(spacing)
+                    }
+                );
+                positioned_rectangle_index.insert(positioned_rectangle.clone());
+                positioned_rectangles_list.push(positioned_rectangle);
+            },
+            None => {
+                unused_instances_list.push(rectangle.clone());
+            },
+        }
+    }
+
+    let unused_rectangles_list = group_rectangles_by_id(&unused_instances_list);
+
+    let length_used =
+    match positioned_rectangles_list.iter().max_by(
+        |first, second| rectangle_maximum_y_comparator(first, second)
+    ) {
+        Some(rect) => rect.top_left_vertex.pos_y + rect.length,
+        None => 0,
+    };
+
+    MainRectangleOrganized {
+        possible_vertex_for_rectangle_list: get_vertices_for_showcases(
+            spacing,
+            &free_rectangle_list,
+            &positioned_rectangles_list,
+            prohibited_area_list
+        ),
+        unused_rectangles_list,
+        positioned_rectangles_list,
+        positioned_showcase_list: None,
+        length_used
+    }
+}
+
+// the reserved area of a neighbour only keeps its own spacing, so a piece with a bigger margin is checked against the neighbours
+#[allow(clippy::too_many_arguments)]
+pub fn find_best_short_side_fit(
+    rectangle: &Rectangle,
+    spacing: i32,
+    allow_rotation: bool,
+    free_rectangle_list: &[FreeRectangle],
+    positioned_rectangle_index: &SpatialIndex,
+    max_width: i32,
+    max_length: i32,
+    pattern_repeat: Option<&PatternRepeat>
+) -> Option<PositionedRectangle> {
+    let rectangle_spacing = rectangle.get_spacing(spacing);
+
+    // a free rectangle may start right at a neighbour with a smaller spacing, then the piece is moved away by its own spacing
+    let shift_list = [(0, 0), (rectangle_spacing, 0), (0, rectangle_spacing), (rectangle_spacing, rectangle_spacing)];
+
+    free_rectangle_list
+    .iter()
+    .flat_map(
+        |free_rectangle| {
+            let mut rotated_list = vec![false];
+            if rectangle.can_rotate(allow_rotation) {
+                rotated_list.push(true);
+            }
+
+            rotated_list
+            .into_iter()
+            .filter_map(
+                |rotated| shift_list.iter().find_map(
+                    |(shift_x, shift_y)| {
+                        let vertex = snap_vertex_to_pattern(
+                            rectangle,
+                            &Vertex {
+                                pos_x: free_rectangle.pos_x + shift_x,
+                                pos_y: free_rectangle.pos_y + shift_y
+                            },
+                            pattern_repeat
+                        );
+
+                        let subject = if rotated {
+                            PositionedRectangle::new_from_rotated_rectangle_and_vertex(rectangle, &vertex)
+                        } else {
+                            PositionedRectangle::new_from_rectangle_and_vertex(rectangle, &vertex)
+                        };
+
+                        let width_left_over = free_rectangle.pos_x + free_rectangle.width - subject.top_left_vertex.pos_x - subject.width - rectangle_spacing;
+                        let length_left_over = free_rectangle.pos_y + free_rectangle.length - subject.top_left_vertex.pos_y - subject.length - rectangle_spacing;
+
+                        if width_left_over < 0 || length_left_over < 0 {
+                            return None;
+                        }
+
+                        if 
+                        !is_within_boundaries(&subject, max_width, max_length) ||
+                        positioned_rectangle_index.intersect(&subject) {
+                            return None;
+                        }
+
+                        Some(Placement {
+                            score: (
+                                width_left_over.min(length_left_over),
+                                width_left_over.max(length_left_over),
+                                subject.top_left_vertex.pos_y,
+                                subject.top_left_vertex.pos_x
+                            ),
+                            positioned_rectangle: subject,
+                        })
+                    }
+                )
+            )
+            .collect::<Vec<Placement>>()
+        }
+    )
+    .min_by(placement_comparator)
+    .map(|placement| placement.positioned_rectangle)
+}
+
+// removes the used area from every free rectangle it touches, keeping only the maximal free rectangles
+fn split_free_rectangle_list(free_rectangle_list: &mut Vec<FreeRectangle>, used: &FreeRectangle) {
+    let mut new_free_rectangle_list = Vec::<FreeRectangle>::new();
+
+    for free_rectangle in free_rectangle_list.iter() {
+        if !free_rectangle.intersect(used) {
+            new_free_rectangle_list.push(free_rectangle.clone());
+            continue;
+        }
+
+        // left side
+        if used.pos_x > free_rectangle.pos_x {
+            new_free_rectangle_list.push(FreeRectangle {
+                width: used.pos_x - free_rectangle.pos_x,
+                ..free_rectangle.clone()
+            });
+        }
+        // rigth side
+        if used.pos_x + used.width < free_rectangle.pos_x + free_rectangle.width {
+            new_free_rectangle_list.push(FreeRectangle {
+                pos_x: used.pos_x + used.width,
+                width: free_rectangle.pos_x + free_rectangle.width - used.pos_x - used.width,
+                ..free_rectangle.clone()
+            });
+        }
+        // top side
+        if used.pos_y > free_rectangle.pos_y {
+            new_free_rectangle_list.push(FreeRectangle {
+                length: used.pos_y - free_rectangle.pos_y,
+                ..free_rectangle.clone()
+            });
+        }
+        // bottom side
+        if used.pos_y + used.length < free_rectangle.pos_y + free_rectangle.length {
+            new_free_rectangle_list.push(FreeRectangle {
+                pos_y: used.pos_y + used.length,
+                length: free_rectangle.pos_y + free_rectangle.length - used.pos_y - used.length,
+                ..free_rectangle.clone()
+            });
+        }
+    }
+
+    new_free_rectangle_list.dedup();
+
+    *free_rectangle_list = new_free_rectangle_list
+    .iter()
+    .enumerate()
+    .filter(
+        |(index, free_rectangle)|
+        !new_free_rectangle_list
+        .iter()
+        .enumerate()
+        .any(
+            |(other_index, other)|
+            other_index != *index &&
+            other.contains(free_rectangle) &&
+            // when two rectangles are equal only the first one is kept
+            (other != *free_rectangle || other_index < *index)
+        )
+    )
+    .map(|(_, free_rectangle)| free_rectangle.clone())
+    .collect();
+}
+
+// the showcases are filled by the vertex search, so it receives the corners of the free space left
+pub fn get_vertices_for_showcases(
+    spacing: i32,
+    free_rectangle_list: &[FreeRectangle],
+    positioned_rectangles_list: &[PositionedRectangle],
+    prohibited_area_list: &[PositionedRectangle]
+) -> Vec<Vertex> {
+    let mut vertex_list = vec![Vertex { pos_x: 0, pos_y: 0 }];
+
+    for prohibited_area in prohibited_area_list {
+        vertex_list.append(&mut create_available_vertices_for_prohibited_area(prohibited_area));
+    }
+
+    for positioned_rectangle in positioned_rectangles_list {
+        vertex_list.append(&mut create_available_vertices_for_positioning(positioned_rectangle, spacing));
+    }
+
+    for free_rectangle in free_rectangle_list {
+        vertex_list.push(Vertex {
+            pos_x: free_rectangle.pos_x,
+            pos_y: free_rectangle.pos_y
+        });
+    }
+
+    vertex_list.sort_by(vertex_closest_to_top_and_left_comparator);
+
+    vertex_list.dedup();
+
+    vertex_list
+}
+
+fn placement_comparator(first: &Placement, second: &Placement) -> Ordering {
+    first.score.cmp(&second.score)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::cut_disposition_service::intersect;
+
+    use super::*;
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- fabric -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <!-- positioned rectangles -->
+        <rect x="0" y="0" width="60" height="60" style="fill:green;stroke:black;" />
+
+        <rect x="60" y="0" width="40" height="60" style="fill:green;stroke:black;" />
+
+        <rect x="0" y="60" width="100" height="40" style="fill:green;stroke:black;" />
+
+    </svg>
+
+ */
+    #[test]
+    fn organize_main_rectangles_best_short_side_fit_test() {
+        let rect1 = Rectangle {
+            id: 1,
+            width: 60,
+            length: 60,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let rect2 = Rectangle {
+            id: 2,
+            width: 100,
+            length: 40,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let rect3 = Rectangle {
+            id: 3,
+            width: 60,
+            length: 40,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+
+        let rectangles_list = vec![rect1.clone(), rect2.clone(), rect3.clone()];
+
+        let result = organize_main_rectangles(100, 0, 100, true, get_rectangle_instance_list(&rectangles_list), &[], &[], None, CollisionSearch::SpatialIndex);
+
+        let expected_positioned_rectangles_list = vec![
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect2, &Vertex { pos_x: 0, pos_y: 0 }),
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect1, &Vertex { pos_x: 0, pos_y: 40 }),
+            PositionedRectangle::new_from_rotated_rectangle_and_vertex(&rect3, &Vertex { pos_x: 60, pos_y: 40 }),
+        ];
+
+        assert_eq!(result.positioned_rectangles_list, expected_positioned_rectangles_list);
+        assert!(result.unused_rectangles_list.is_empty());
+        assert_eq!(result.length_used, 100);
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- fabric -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <!-- prohibited areas -->
+        <rect x="0" y="0" width="50" height="50" style="fill:black;stroke:black;" />
+
+        <!-- positioned rectangles -->
+        <rect x="50" y="0" width="40" height="40" style="fill:green;stroke:black;" />
+
+        <rect x="0" y="50" width="40" height="40" style="fill:green;stroke:black;" />
+
+        <rect x="50" y="50" width="40" height="40" style="fill:green;stroke:black;" />
+
+    </svg>
+
+ */
+    #[test]
+    fn organize_main_rectangles_with_prohibited_area_and_spacing_test() {
+        let prohibited_area = PositionedRectangle {
+            id: 1,
+            width: 50,
+            length: 50,
+            top_left_vertex: Vertex { pos_x: 0, pos_y: 0 },
+            rotated: false,
+            margin: None,
+        };
+        let rect = Rectangle {
+            id: 2,
+            width: 40,
+            length: 40,
+            allow_rotation: None,
+            quantity: 4,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+
+        let result = organize_main_rectangles(100, 10, 100, false, get_rectangle_instance_list(std::slice::from_ref(&rect)), &[prohibited_area], &[], None, CollisionSearch::SpatialIndex);
+
+        let expected_positioned_rectangles_list = vec![
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 50, pos_y: 0 }),
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 0, pos_y: 50 }),
+            PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 50, pos_y: 50 }),
+        ];
+
+        assert_eq!(result.positioned_rectangles_list, expected_positioned_rectangles_list);
+        assert_eq!(result.unused_rectangles_list, vec![Rectangle { quantity: 1, ..rect.clone() }]);
+        assert_eq!(result.length_used, 90);
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- fabric -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <!-- prohibited areas -->
+        <rect x="0" y="0" width="50" height="50" style="fill:black;stroke:black;" />
+
+        <!-- positioned rectangles, the margin of 10 is kept from the prohibited area -->
+        <rect x="60" y="0" width="40" height="40" style="fill:green;stroke:black;" />
+
+    </svg>
+
+ */
+    #[test]
+    fn organize_main_rectangles_margin_next_to_prohibited_area_test() {
+        let prohibited_area = PositionedRectangle {
+            id: 1,
+            width: 50,
+            length: 50,
+            top_left_vertex: Vertex { pos_x: 0, pos_y: 0 },
+            rotated: false,
+            margin: None,
+        };
+        let rect = Rectangle {
+            id: 2,
+            width: 40,
+            length: 40,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: Some(10),
+        };
+
+        let result = organize_main_rectangles(100, 0, 100, false, get_rectangle_instance_list(std::slice::from_ref(&rect)), std::slice::from_ref(&prohibited_area), &[], None, CollisionSearch::SpatialIndex);
+
+        assert_eq!(
+            result.positioned_rectangles_list,
+            vec![PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 60, pos_y: 0 })]
+        );
+        assert!(!intersect(&result.positioned_rectangles_list[0], &prohibited_area, 10));
+    }
+
+    #[test]
+    fn split_free_rectangle_list_test() {
+        let mut free_rectangle_list = vec![
+            FreeRectangle { pos_x: 0, pos_y: 0, width: 100, length: 100 }
+        ];
+
+        split_free_rectangle_list(
+            &mut free_rectangle_list,
+            &FreeRectangle { pos_x: 20, pos_y: 0, width: 30, length: 40 }
+        );
+
+        assert_eq!(
+            free_rectangle_list,
+            vec![
+                FreeRectangle { pos_x: 0, pos_y: 0, width: 20, length: 100 },
+                FreeRectangle { pos_x: 50, pos_y: 0, width: 50, length: 100 },
+                FreeRectangle { pos_x: 0, pos_y: 40, width: 100, length: 60 },
+            ]
+        );
+    }
+}
@@ -5,3 +5,12 @@ pub mod fabric_service;
 pub mod file_service;
 pub mod cutting_lines_service;
 pub mod gcode_service;
+pub mod guillotine_service;
+pub mod max_rects_service;
+pub mod optimizer_service;
+pub mod layout_adjustment_service;
+pub mod disposition_validation_service;
+pub mod unplaced_piece_service;
+pub mod spatial_index_service;
+pub mod cut_sequence_service;
+pub mod length_unit_service;
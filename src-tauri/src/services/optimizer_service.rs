@@ -0,0 +1,370 @@
+use std::{time::{Duration, Instant}, thread};
+
+use crate::models::cut_disposition::{CutDispositionInput, CutDispositionOutput, OptimizationConfig, Rectangle, OrganizeProgress};
+
+use super::cut_disposition_service::{get_nesting_strategy, OrganizeObserver};
+
+// every few swaps the order is shuffled, so the search does not get stuck around the first good order found
+const RESTART_INTERVAL: u32 = 20;
+
+// orders tried at once, split among the cores; fixed so a batch compares the same orders on any machine and the
+// same seed with a maximum of iterations gives the same layout
+const CANDIDATE_COUNT: u32 = 16;
+
+// Reruns the strategy with other orders of the pieces until the time limit, the maximum of iterations or the cancellation,
+// keeping the best layout found. Each sheet is optimized on its own, so a job of several sheets takes a limit for each one
+pub fn optimize_disposition(
+    cut_disposition_input: &CutDispositionInput,
+    optimization: &OptimizationConfig,
+    sheet: u32,
+    observer: &dyn OrganizeObserver
+) -> CutDispositionOutput {
+    let start = Instant::now();
+
+    let time_limit = Duration::from_millis(optimization.time_limit as u64);
+
+    let is_cancelled = || observer.is_cancelled();
+
+    optimize_orders(
+        cut_disposition_input,
+        optimization.seed,
+        |iteration, best_output| {
+            observer.on_progress(OrganizeProgress { sheet, iteration, best_usage: best_output.usage });
+            is_cancelled() || match optimization.max_iterations {
+                Some(max_iterations) => iteration >= max_iterations,
+                None => start.elapsed() >= time_limit,
+            }
+        },
+        &is_cancelled
+    )
+}
+
+// should_stop is checked between the batches, the time limit only ends the search there so every batch compares all its
+// orders; is_cancelled is also checked before each order of a batch, so the cancellation does not wait for the whole batch
+fn optimize_orders(
+    cut_disposition_input: &CutDispositionInput,
+    seed: u32,
+    mut should_stop: impl FnMut(u32, &CutDispositionOutput) -> bool,
+    is_cancelled: &(dyn Fn() -> bool + Sync)
+) -> CutDispositionOutput {
+    let nesting_strategy = get_nesting_strategy(&cut_disposition_input.nesting_strategy);
+
+    let mut best_order = nesting_strategy.get_rectangle_instance_list(&cut_disposition_input.rectangles_list);
+
+    let mut best_output = nesting_strategy.organize_in_order(cut_disposition_input, best_order.clone());
+
+    if best_order.len() < 2 {
+        return best_output;
+    }
+
+    let mut random_generator = RandomGenerator::new(seed);
+
+    let mut iteration = 0;
+
+    while !should_stop(iteration, &best_output) {
+        let order_list: Vec<Vec<Rectangle>> = (1..=CANDIDATE_COUNT)
+            .map(
+                |offset| {
+                    let mut order = best_order.clone();
+
+                    if (iteration + offset) % RESTART_INTERVAL == 0 {
+                        shuffle(&mut order, &mut random_generator);
+                    } else {
+                        let first_index = random_generator.next_index(order.len());
+                        let second_index = random_generator.next_index(order.len());
+                        order.swap(first_index, second_index);
+                    }
+
+                    order
+                }
+            )
+            .collect();
+
+        iteration += CANDIDATE_COUNT;
+
+        let output_list = evaluate_order_list(cut_disposition_input, &order_list, is_cancelled);
+
+        // the outputs keep the order of the candidates, on a tie the one with the lowest index is kept
+        for (order, output) in order_list.into_iter().zip(output_list) {
+            let Some(output) = output else {
+                continue;
+            };
+
+            if is_better_output(&output, &best_output) {
+                best_output = output;
+                best_order = order;
+            }
+        }
+    }
+
+    best_output
+}
+
+// Organizes every order on its own thread share, the outputs come back in the same order as the orders,
+// the orders left when the organize is cancelled are skipped
+fn evaluate_order_list(
+    cut_disposition_input: &CutDispositionInput,
+    order_list: &[Vec<Rectangle>],
+    is_cancelled: &(dyn Fn() -> bool + Sync)
+) -> Vec<Option<CutDispositionOutput>> {
+    let thread_count = thread::available_parallelism()
+        .map(|thread_count| thread_count.get())
+        .unwrap_or(1)
+        .clamp(1, order_list.len().max(1));
+
+    let chunk_size = order_list.len().div_ceil(thread_count).max(1);
+
+    thread::scope(
+        |scope| {
+            let handle_list: Vec<_> = order_list
+                .chunks(chunk_size)
+                .map(
+                    |chunk| scope.spawn(
+                        move || {
+                            let nesting_strategy = get_nesting_strategy(&cut_disposition_input.nesting_strategy);
+                            chunk
+                            .iter()
+                            .map(
+                                |order| match is_cancelled() {
+                                    true => None,
+                                    false => Some(nesting_strategy.organize_in_order(cut_disposition_input, order.clone())),
+                                }
+                            )
+                            .collect::<Vec<Option<CutDispositionOutput>>>()
+                        }
+                    )
+                )
+                .collect();
+
+            handle_list
+            .into_iter()
+            .flat_map(
+                |handle| match handle.join() {
+                    Ok(output_list) => output_list,
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            )
+            .collect()
+        }
+    )
+}
+
+// more area placed is better, then the shortest fabric
+fn is_better_output(output: &CutDispositionOutput, best_output: &CutDispositionOutput) -> bool {
+    output.used_area > best_output.used_area ||
+    (output.used_area == best_output.used_area && output.length_used < best_output.length_used)
+}
+
+fn shuffle(order: &mut [Rectangle], random_generator: &mut RandomGenerator) {
+    for index in (1..order.len()).rev() {
+        order.swap(index, random_generator.next_index(index + 1));
+    }
+}
+
+// xorshift, the layouts only have to be reproducible, not unpredictable
+struct RandomGenerator {
+    state: u32,
+}
+impl RandomGenerator {
+    fn new(seed: u32) -> RandomGenerator {
+        // zero is the only state xorshift can not leave
+        RandomGenerator { state: if seed == 0 { 0x9E37_79B9 } else { seed } }
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state
+    }
+
+    fn next_index(&mut self, len: usize) -> usize {
+        (self.next_u32() as usize) % len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Mutex, atomic::{AtomicUsize, Ordering}};
+
+    use crate::{models::cut_disposition::NestingStrategyType, test_support::get_cut_disposition_input};
+
+    use super::*;
+
+    fn create_rectangle(id: u32, width: i32, length: i32, quantity: u32) -> Rectangle {
+        Rectangle {
+            id,
+            width,
+            length,
+            allow_rotation: None,
+            quantity,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        }
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- widest first, the 40x60 and the 20x70 pieces find no room -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+        <rect x="0" y="0" width="50" height="50" style="fill:green;stroke:black;" />
+        <rect x="50" y="0" width="50" height="50" style="fill:green;stroke:black;" />
+
+        <!-- optimized, only the 40x60 piece is left -->
+        <rect x="200" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+        <rect x="200" y="0" width="20" height="70" style="fill:green;stroke:black;" />
+        <rect x="220" y="0" width="20" height="70" style="fill:green;stroke:black;" />
+        <rect x="240" y="0" width="50" height="50" style="fill:green;stroke:black;" />
+        <rect x="240" y="50" width="50" height="50" style="fill:green;stroke:black;" />
+    </svg>
+
+ */
+    #[test]
+    fn optimize_orders_test() {
+        let cut_disposition_input = get_cut_disposition_input(vec![
+            create_rectangle(1, 50, 50, 2),
+            create_rectangle(2, 40, 60, 1),
+            create_rectangle(3, 20, 70, 2),
+        ]);
+
+        let default_output = optimize_orders(&cut_disposition_input, 7, |_iteration, _best_output| true, &|| false);
+
+        let optimized_output = optimize_orders(&cut_disposition_input, 7, |iteration, _best_output| iteration >= 200, &|| false);
+
+        let optimized_again_output = optimize_orders(&cut_disposition_input, 7, |iteration, _best_output| iteration >= 200, &|| false);
+
+        assert_eq!(default_output.used_area, 5000);
+        assert_eq!(optimized_output.used_area, 7800);
+        assert_eq!(optimized_output.unused_rectangles_list, vec![create_rectangle(2, 40, 60, 1)]);
+        assert_eq!(optimized_output, optimized_again_output);
+    }
+
+    struct CancelAfterObserver {
+        progress_list: Mutex<Vec<OrganizeProgress>>,
+        cancel_after: usize,
+    }
+    impl OrganizeObserver for CancelAfterObserver {
+        fn on_progress(&self, organize_progress: OrganizeProgress) {
+            self.progress_list.lock().unwrap().push(organize_progress);
+        }
+
+        fn is_cancelled(&self) -> bool {
+            self.progress_list.lock().unwrap().len() >= self.cancel_after
+        }
+    }
+
+    #[test]
+    fn optimize_disposition_cancel_test() {
+        let cut_disposition_input = get_cut_disposition_input(vec![
+            create_rectangle(1, 50, 50, 2),
+            create_rectangle(2, 40, 60, 1),
+            create_rectangle(3, 20, 70, 2),
+        ]);
+
+        let observer = CancelAfterObserver { progress_list: Mutex::new(Vec::new()), cancel_after: 50 };
+
+        // without the cancellation the time limit would never be reached by the test
+        let output = optimize_disposition(
+            &cut_disposition_input,
+            &OptimizationConfig { time_limit: u32::MAX, seed: 7, max_iterations: None },
+            1,
+            &observer
+        );
+
+        let progress_list = observer.progress_list.into_inner().unwrap();
+
+        assert_eq!(progress_list.len(), 50);
+        assert!(progress_list.iter().enumerate().all(|(index, progress)| progress.sheet == 1 && progress.iteration == index as u32 * CANDIDATE_COUNT));
+        assert_eq!(progress_list.last().map(|progress| progress.best_usage), Some(output.usage));
+    }
+
+    // with the maximum of iterations the time limit is ignored, the same seed gives the same layout
+    #[test]
+    fn optimize_disposition_max_iterations_test() {
+        let cut_disposition_input = get_cut_disposition_input(vec![
+            create_rectangle(1, 50, 50, 2),
+            create_rectangle(2, 40, 60, 1),
+            create_rectangle(3, 20, 70, 2),
+        ]);
+
+        let optimization = OptimizationConfig { time_limit: 1, seed: 7, max_iterations: Some(200) };
+
+        let observer = CancelAfterObserver { progress_list: Mutex::new(Vec::new()), cancel_after: usize::MAX };
+        let output = optimize_disposition(&cut_disposition_input, &optimization, 1, &observer);
+
+        let observer_again = CancelAfterObserver { progress_list: Mutex::new(Vec::new()), cancel_after: usize::MAX };
+        let output_again = optimize_disposition(&cut_disposition_input, &optimization, 1, &observer_again);
+
+        let progress_list = observer.progress_list.into_inner().unwrap();
+
+        // 200 orders are rounded up to 13 batches of 16
+        assert_eq!(progress_list.last().map(|progress| progress.iteration), Some(208));
+        assert_eq!(progress_list, observer_again.progress_list.into_inner().unwrap());
+        assert_eq!(output.used_area, 7800);
+        assert_eq!(output, output_again);
+    }
+
+    // the outputs of the threads are the same as organizing each order in sequence
+    #[test]
+    fn evaluate_order_list_test() {
+        let cut_disposition_input = CutDispositionInput {
+            nesting_strategy: NestingStrategyType::MaxRects,
+            ..get_cut_disposition_input(vec![
+                create_rectangle(1, 50, 50, 2),
+                create_rectangle(2, 40, 60, 1),
+                create_rectangle(3, 20, 70, 2),
+            ])
+        };
+
+        let nesting_strategy = get_nesting_strategy(&cut_disposition_input.nesting_strategy);
+
+        let mut random_generator = RandomGenerator::new(3);
+
+        let order_list: Vec<Vec<Rectangle>> = (0..CANDIDATE_COUNT)
+            .map(
+                |_| {
+                    let mut order = nesting_strategy.get_rectangle_instance_list(&cut_disposition_input.rectangles_list);
+                    shuffle(&mut order, &mut random_generator);
+                    order
+                }
+            )
+            .collect();
+
+        let sequential_output_list: Vec<Option<CutDispositionOutput>> = order_list
+            .iter()
+            .map(|order| Some(nesting_strategy.organize_in_order(&cut_disposition_input, order.clone())))
+            .collect();
+
+        assert_eq!(evaluate_order_list(&cut_disposition_input, &order_list, &|| false), sequential_output_list);
+        assert_eq!(evaluate_order_list(&cut_disposition_input, &[], &|| false), Vec::new());
+
+        // the cancellation in the middle of the batch skips the orders not organized yet
+        let organized_count = AtomicUsize::new(0);
+        let output_list = evaluate_order_list(
+            &cut_disposition_input,
+            &order_list,
+            &|| organized_count.fetch_add(1, Ordering::SeqCst) >= 3
+        );
+
+        assert_eq!(output_list.len(), order_list.len());
+        assert_eq!(output_list.iter().filter(|output| output.is_some()).count(), 3);
+        assert!(output_list.iter().zip(sequential_output_list.iter()).all(|(output, sequential_output)| output.is_none() || output == sequential_output));
+    }
+
+    #[test]
+    fn random_generator_test() {
+        let mut first_generator = RandomGenerator::new(42);
+        let mut second_generator = RandomGenerator::new(42);
+
+        let first_list: Vec<usize> = (0..10).map(|_| first_generator.next_index(7)).collect();
+        let second_list: Vec<usize> = (0..10).map(|_| second_generator.next_index(7)).collect();
+
+        assert_eq!(first_list, second_list);
+        assert!(first_list.iter().all(|index| *index < 7));
+
+        let mut zero_generator = RandomGenerator::new(0);
+        assert_ne!(zero_generator.next_u32(), 0);
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use crate::models::cut_disposition::{PositionedRectangle, Rectangle};
+
+use super::cut_disposition_service::intersect;
+
+// the fabric is split in at least this many columns, so a few small pieces do not fill the grid with huge areas
+const MIN_COLUMN_COUNT: i32 = 64;
+
+// Uniform grid over the fabric, each rectangle is kept in every cell covered by it and its spacing,
+// so a collision check only compares the subject with the rectangles around it instead of all of them
+pub struct SpatialIndex {
+    spacing: i32,
+    cell_size: i32,
+    rectangle_list: Vec<PositionedRectangle>,
+    cell_map: HashMap<(i32, i32), Vec<usize>>,
+}
+impl SpatialIndex {
+    pub fn new(spacing: i32, cell_size: i32) -> SpatialIndex {
+        SpatialIndex {
+            spacing,
+            cell_size: cell_size.max(1),
+            rectangle_list: Vec::new(),
+            cell_map: HashMap::new(),
+        }
+    }
+
+    pub fn new_from_rectangle_list(rectangle_list: &[PositionedRectangle], spacing: i32, cell_size: i32) -> SpatialIndex {
+        let mut spatial_index = SpatialIndex::new(spacing, cell_size);
+        for rectangle in rectangle_list {
+            spatial_index.insert(rectangle.clone());
+        }
+        spatial_index
+    }
+
+    pub fn insert(&mut self, rectangle: PositionedRectangle) {
+        let index = self.rectangle_list.len();
+        for cell in self.get_cell_list(&rectangle) {
+            self.cell_map.entry(cell).or_default().push(index);
+        }
+        self.rectangle_list.push(rectangle);
+    }
+
+    // same as checking the whole list, two neighbours are kept apart by the biggest of their margins
+    pub fn intersect(&self, subject: &PositionedRectangle) -> bool {
+        self.get_cell_list(subject)
+        .iter()
+        .filter_map(|cell| self.cell_map.get(cell))
+        .flatten()
+        .any(
+            |index| {
+                let rect = &self.rectangle_list[*index];
+                intersect(subject, rect, subject.get_spacing(self.spacing).max(rect.get_spacing(self.spacing)))
+            }
+        )
+    }
+
+    // a rectangle grown by its own spacing, two rectangles closer than the biggest spacing share at least one cell
+    fn get_cell_list(&self, rectangle: &PositionedRectangle) -> Vec<(i32, i32)> {
+        let rectangle_spacing = rectangle.get_spacing(self.spacing);
+
+        let first_column = (rectangle.top_left_vertex.pos_x - rectangle_spacing).div_euclid(self.cell_size);
+        let last_column = (rectangle.top_left_vertex.pos_x + rectangle.width + rectangle_spacing - 1).div_euclid(self.cell_size);
+        let first_row = (rectangle.top_left_vertex.pos_y - rectangle_spacing).div_euclid(self.cell_size);
+        let last_row = (rectangle.top_left_vertex.pos_y + rectangle.length + rectangle_spacing - 1).div_euclid(self.cell_size);
+
+        (first_row..=last_row)
+        .flat_map(|row| (first_column..=last_column).map(move |column| (column, row)))
+        .collect()
+    }
+}
+
+// How the collision checks find the rectangles around a subject, the linear scan is the search before the index,
+// kept to measure the index against it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionSearch {
+    SpatialIndex,
+    LinearScan,
+}
+
+// cells about the size of the pieces keep few rectangles in each cell and few cells for each rectangle,
+// the linear scan keeps every rectangle in a single cell, so each check compares the whole list
+pub fn get_cell_size(rectangle_list: &[Rectangle], max_width: i32, collision_search: CollisionSearch) -> i32 {
+    if collision_search == CollisionSearch::LinearScan {
+        return i32::MAX / 4;
+    }
+
+    // nothing will be looked for, the rectangles already placed are kept in as few cells as possible
+    if rectangle_list.is_empty() {
+        return max_width.max(1);
+    }
+
+    let min_cell_size = max_width / MIN_COLUMN_COUNT;
+
+    let longest_side_sum: i64 = rectangle_list
+        .iter()
+        .map(|rectangle| rectangle.width.max(rectangle.length) as i64)
+        .sum();
+
+    ((longest_side_sum / rectangle_list.len() as i64) as i32).max(min_cell_size).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{models::cut_disposition::Vertex, services::cut_disposition_service::subject_intesect_with_positioned_rectangles_list};
+
+    use super::*;
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- fabric -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <rect x="10" y="10" width="20" height="20" style="fill:green;stroke:black;" />
+        <rect x="60" y="60" width="30" height="30" style="fill:green;stroke:black;" />
+
+        <!-- subjects -->
+        <rect x="35" y="10" width="10" height="10" style="fill:red;stroke:black;opacity:0.5;" />
+        <rect x="36" y="40" width="10" height="10" style="fill:blue;stroke:black;opacity:0.5;" />
+        <rect x="-5" y="95" width="10" height="10" style="fill:blue;stroke:black;opacity:0.5;" />
+    </svg>
+ */
+    #[test]
+    fn spatial_index_intersect_test() {
+        let first = PositionedRectangle {
+            id: 1,
+            width: 20,
+            length: 20,
+            top_left_vertex: Vertex { pos_x: 10, pos_y: 10 },
+            rotated: false,
+            margin: None,
+        };
+        let second = PositionedRectangle {
+            id: 2,
+            width: 30,
+            length: 30,
+            top_left_vertex: Vertex { pos_x: 60, pos_y: 60 },
+            rotated: false,
+            margin: Some(15),
+        };
+
+        let spatial_index = SpatialIndex::new_from_rectangle_list(&[first.clone(), second.clone()], 5, 10);
+
+        let subject_list = [
+            // closer than the spacing to the first
+            PositionedRectangle { id: 3, width: 10, length: 10, top_left_vertex: Vertex { pos_x: 34, pos_y: 10 }, rotated: false, margin: None },
+            // touches the spacing of the first
+            PositionedRectangle { id: 3, width: 10, length: 10, top_left_vertex: Vertex { pos_x: 35, pos_y: 10 }, rotated: false, margin: None },
+            // inside the margin of the second only
+            PositionedRectangle { id: 3, width: 10, length: 10, top_left_vertex: Vertex { pos_x: 36, pos_y: 40 }, rotated: false, margin: None },
+            // away from both
+            PositionedRectangle { id: 3, width: 10, length: 10, top_left_vertex: Vertex { pos_x: -5, pos_y: 95 }, rotated: false, margin: None },
+            // its own margin reaches the second
+            PositionedRectangle { id: 3, width: 10, length: 10, top_left_vertex: Vertex { pos_x: 90, pos_y: 5 }, rotated: false, margin: Some(60) },
+        ];
+
+        for subject in subject_list.iter() {
+            assert_eq!(
+                spatial_index.intersect(subject),
+                subject_intesect_with_positioned_rectangles_list(subject, &[first.clone(), second.clone()], 5),
+                "{:?}",
+                subject.top_left_vertex
+            );
+        }
+        assert_eq!(
+            subject_list.iter().map(|subject| spatial_index.intersect(subject)).collect::<Vec<bool>>(),
+            vec![true, false, true, false, true]
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use std::cmp::Ordering;
+
+use crate::models::{cut_disposition::{CutDispositionInput, CutDispositionOutput, PositionedRectangle, Rectangle, Vertex, UnplacedPiece, UnplacedReason, NearMiss}, fixed_point::{Fixed, FixedArea, get_area}};
+
+use super::cut_disposition_service::{get_max_length, vertex_closest_to_top_and_left_comparator};
+
+// usable area of the fabric, the edge margins are outside of it
+struct UsableArea {
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+}
+
+// Tells the operator why each piece left was not placed on the sheet, and where it came closest to fit
+pub fn explain_unplaced_pieces(cut_disposition_input: &CutDispositionInput, cut_disposition_output: &CutDispositionOutput) -> Vec<UnplacedPiece> {
+    let spacing = cut_disposition_input.spacing.unwrap_or(0);
+    let usable_area = UsableArea {
+        left: cut_disposition_input.edge_margins.left,
+        top: cut_disposition_input.edge_margins.top,
+        right: cut_disposition_input.defined_width - cut_disposition_input.edge_margins.right,
+        bottom: get_max_length(cut_disposition_input),
+    };
+
+    cut_disposition_output.unused_rectangles_list
+    .iter()
+    .map(
+        |rectangle| {
+            let orientation_list = get_orientation_list(rectangle, cut_disposition_input.allow_rotation);
+
+            let narrow_orientation_list: Vec<PositionedRectangle> = orientation_list
+                .into_iter()
+                .filter(|orientation| orientation.width <= usable_area.right - usable_area.left)
+                .collect();
+
+            let fitting_orientation_list: Vec<PositionedRectangle> = narrow_orientation_list
+                .iter()
+                .filter(|orientation| orientation.length <= usable_area.bottom - usable_area.top)
+                .cloned()
+                .collect();
+
+            let (reason, near_miss) = if narrow_orientation_list.is_empty() {
+                (UnplacedReason::WiderThanFabric, None)
+            } else if fitting_orientation_list.is_empty() {
+                (UnplacedReason::LongerThanFabric, None)
+            } else {
+                let fits_between_prohibited_areas = find_near_miss(
+                    &fitting_orientation_list,
+                    &usable_area,
+                    &[],
+                    &cut_disposition_output.prohibited_area_list,
+                    spacing
+                ).is_some_and(|near_miss| near_miss.overlap_area == 0);
+
+                let near_miss = find_near_miss(
+                    &fitting_orientation_list,
+                    &usable_area,
+                    &cut_disposition_output.positioned_rectangles_list,
+                    &cut_disposition_output.prohibited_area_list,
+                    spacing
+                );
+
+                if fits_between_prohibited_areas {
+                    (UnplacedReason::NoRoomLeft, near_miss)
+                } else {
+                    (UnplacedReason::BlockedByProhibitedAreas, near_miss)
+                }
+            };
+
+            UnplacedPiece {
+                id: rectangle.id,
+                quantity: rectangle.quantity,
+                reason,
+                near_miss,
+            }
+        }
+    )
+    .collect()
+}
+
+fn get_orientation_list(rectangle: &Rectangle, allow_rotation: bool) -> Vec<PositionedRectangle> {
+    let origin = Vertex { pos_x: 0, pos_y: 0 };
+
+    let mut orientation_list = vec![PositionedRectangle::new_from_rectangle_and_vertex(rectangle, &origin)];
+    if rectangle.can_rotate(allow_rotation) {
+        orientation_list.push(PositionedRectangle::new_from_rotated_rectangle_and_vertex(rectangle, &origin));
+    }
+    orientation_list
+}
+
+// A piece that fits is pushed up and left until it touches the fabric or an obstacle,
+// so those edges are enough to find the position with the least overlap
+fn find_near_miss(
+    orientation_list: &[PositionedRectangle],
+    usable_area: &UsableArea,
+    positioned_rectangles_list: &[PositionedRectangle],
+    prohibited_area_list: &[PositionedRectangle],
+    spacing: i32
+) -> Option<NearMiss> {
+    orientation_list
+    .iter()
+    .flat_map(
+        |orientation| {
+            let gap_list: Vec<i32> = positioned_rectangles_list
+                .iter()
+                .map(|positioned_rectangle| orientation.get_spacing(spacing).max(positioned_rectangle.get_spacing(spacing)))
+                .collect();
+
+            let mut pos_x_list = vec![usable_area.left, usable_area.right - orientation.width];
+            let mut pos_y_list = vec![usable_area.top, usable_area.bottom - orientation.length];
+            for (positioned_rectangle, gap) in positioned_rectangles_list.iter().zip(gap_list.iter()) {
+                pos_x_list.push(positioned_rectangle.top_left_vertex.pos_x + positioned_rectangle.width + gap);
+                pos_y_list.push(positioned_rectangle.top_left_vertex.pos_y + positioned_rectangle.length + gap);
+            }
+            for prohibited_area in prohibited_area_list {
+                pos_x_list.push(prohibited_area.top_left_vertex.pos_x + prohibited_area.width);
+                pos_y_list.push(prohibited_area.top_left_vertex.pos_y + prohibited_area.length);
+            }
+            pos_x_list.retain(|pos_x| *pos_x >= usable_area.left && *pos_x + orientation.width <= usable_area.right);
+            pos_y_list.retain(|pos_y| *pos_y >= usable_area.top && *pos_y + orientation.length <= usable_area.bottom);
+
+            pos_y_list
+            .into_iter()
+            .flat_map(move |pos_y| pos_x_list.clone().into_iter().map(move |pos_x| Vertex { pos_x, pos_y }))
+            .map(
+                move |top_left_vertex| {
+                    let candidate = PositionedRectangle { top_left_vertex, ..orientation.clone() };
+
+                    let overlap_area = positioned_rectangles_list
+                        .iter()
+                        .zip(gap_list.iter())
+                        .map(|(positioned_rectangle, gap)| get_overlap_area(&candidate, positioned_rectangle, *gap))
+                        .chain(prohibited_area_list.iter().map(|prohibited_area| get_overlap_area(&candidate, prohibited_area, 0)))
+                        .sum();
+
+                    NearMiss { positioned_rectangle: candidate, overlap_area }
+                }
+            )
+            .collect::<Vec<NearMiss>>()
+        }
+    )
+    .min_by(near_miss_comparator)
+}
+
+fn near_miss_comparator(first: &NearMiss, second: &NearMiss) -> Ordering {
+    first.overlap_area
+        .cmp(&second.overlap_area)
+        .then_with(
+            || vertex_closest_to_top_and_left_comparator(
+                &first.positioned_rectangle.top_left_vertex,
+                &second.positioned_rectangle.top_left_vertex
+            )
+        )
+}
+
+// the obstacle grows by the gap on every side
+fn get_overlap_area(subject: &PositionedRectangle, obstacle: &PositionedRectangle, gap: Fixed) -> FixedArea {
+    let overlap_width =
+        (subject.top_left_vertex.pos_x + subject.width).min(obstacle.top_left_vertex.pos_x + obstacle.width + gap) -
+        subject.top_left_vertex.pos_x.max(obstacle.top_left_vertex.pos_x - gap);
+    let overlap_length =
+        (subject.top_left_vertex.pos_y + subject.length).min(obstacle.top_left_vertex.pos_y + obstacle.length + gap) -
+        subject.top_left_vertex.pos_y.max(obstacle.top_left_vertex.pos_y - gap);
+
+    get_area(overlap_width.max(0), overlap_length.max(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{services::cut_disposition_service::{organize_disposition, NoOrganizeObserver}, test_support::get_cut_disposition_input};
+
+    use super::*;
+
+    #[test]
+    fn explain_unplaced_pieces_bigger_than_fabric_test() {
+        let wide_rect = Rectangle {
+            id: 1,
+            width: 120,
+            length: 10,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let long_rect = Rectangle {
+            id: 2,
+            width: 10,
+            length: 150,
+            allow_rotation: None,
+            quantity: 2,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+
+        let output = organize_disposition(
+            &get_cut_disposition_input(vec![wide_rect, long_rect]),
+            1,
+            &NoOrganizeObserver
+        );
+
+        assert_eq!(
+            output.unplaced_piece_list,
+            vec![
+                UnplacedPiece { id: 1, quantity: 1, reason: UnplacedReason::WiderThanFabric, near_miss: None },
+                UnplacedPiece { id: 2, quantity: 2, reason: UnplacedReason::LongerThanFabric, near_miss: None },
+            ]
+        );
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- fabric -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <!-- prohibited area -->
+        <rect x="30" y="0" width="40" height="100" style="fill:black;stroke:black;" />
+
+        <!-- near miss -->
+        <rect x="0" y="0" width="50" height="50" style="fill:red;stroke:black;opacity:0.5;" />
+    </svg>
+ */
+    #[test]
+    fn explain_unplaced_pieces_blocked_by_prohibited_areas_test() {
+        let rect = Rectangle {
+            id: 1,
+            width: 50,
+            length: 50,
+            allow_rotation: None,
+            quantity: 1,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+        let prohibited_area = PositionedRectangle {
+            id: 2,
+            width: 40,
+            length: 100,
+            top_left_vertex: Vertex { pos_x: 30, pos_y: 0 },
+            rotated: false,
+            margin: None,
+        };
+
+        let output = organize_disposition(
+            &CutDispositionInput {
+                prohibited_area_list: vec![prohibited_area],
+                ..get_cut_disposition_input(vec![rect.clone()])
+            },
+            1,
+            &NoOrganizeObserver
+        );
+
+        assert_eq!(
+            output.unplaced_piece_list,
+            vec![
+                UnplacedPiece {
+                    id: 1,
+                    quantity: 1,
+                    reason: UnplacedReason::BlockedByProhibitedAreas,
+                    near_miss: Some(NearMiss {
+                        positioned_rectangle: PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 0, pos_y: 0 }),
+                        overlap_area: 1000,
+                    }),
+                },
+            ]
+        );
+    }
+
+/*
+    Expected Result in html svg
+    <svg width="400" height="180">
+        <!-- fabric -->
+        <rect x="0" y="0" width="100" height="100" style="fill:grey;stroke:black;" />
+
+        <rect x="0" y="0" width="60" height="60" style="fill:green;stroke:black;" />
+
+        <!-- near miss -->
+        <rect x="40" y="40" width="60" height="60" style="fill:red;stroke:black;opacity:0.5;" />
+    </svg>
+ */
+    #[test]
+    fn explain_unplaced_pieces_no_room_left_test() {
+        let rect = Rectangle {
+            id: 1,
+            width: 60,
+            length: 60,
+            allow_rotation: None,
+            quantity: 2,
+            max_quantity: None,
+            match_pattern: false,
+            margin: None,
+        };
+
+        let output = organize_disposition(
+            &get_cut_disposition_input(vec![rect.clone()]),
+            1,
+            &NoOrganizeObserver
+        );
+
+        assert_eq!(
+            output.unplaced_piece_list,
+            vec![
+                UnplacedPiece {
+                    id: 1,
+                    quantity: 1,
+                    reason: UnplacedReason::NoRoomLeft,
+                    near_miss: Some(NearMiss {
+                        positioned_rectangle: PositionedRectangle::new_from_rectangle_and_vertex(&rect, &Vertex { pos_x: 40, pos_y: 40 }),
+                        overlap_area: 400,
+                    }),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,26 @@
+/*
+    Inputs shared by the tests of the services, each test only sets what it checks
+ */
+
+use crate::models::{cut_disposition::{CutDispositionInput, EdgeMargins, NestingStrategyType, Rectangle}, fabric::FabricGrain};
+
+// a fabric of 100 x 100 without spacing, rotation or margins
+pub fn get_cut_disposition_input(rectangles_list: Vec<Rectangle>) -> CutDispositionInput {
+    CutDispositionInput {
+        rectangles_list,
+        prohibited_area_list: Vec::new(),
+        showcase_list: Vec::new(),
+        spacing: None,
+        max_length: 100,
+        defined_length: None,
+        defined_width: 100,
+        allow_rotation: false,
+        nesting_strategy: NestingStrategyType::BottomLeftVertex,
+        multi_sheet: false,
+        optimization: None,
+        fabric_grain: FabricGrain::RotationAllowed,
+        pattern_repeat: None,
+        edge_margins: EdgeMargins::default(),
+        pinned_piece_list: Vec::new(),
+    }
+}
@@ -13,7 +13,8 @@ use pages::{
     fabric_cut::FabricCutPage,
     home::HomePage,
     not_found::NotFoundPage,
-    piece::{PieceItemPage, EditPieceItemPage}
+    piece::{PieceItemPage, EditPieceItemPage},
+    setting::SettingPage
 };
 
 use crate::app::pages::{generate_gcode::GenerateGCodePage, export_disposition::ExportDispositionPage};
@@ -23,6 +24,9 @@ extern "C" {
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "tauri"], catch)]
     pub async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
 
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "event"], catch)]
+    pub async fn listen(event: &str, handler: &Closure<dyn FnMut(JsValue)>) -> Result<JsValue, JsValue>;
+
     #[wasm_bindgen(js_namespace = console)]
     pub fn log(s: &str);
 }
@@ -50,6 +54,7 @@ fn AppNav<G: Html>(cx: Scope) -> View<G> {
                     a(class="navbar-item", href="/fabric") { "Tecidos" }
                     a(class="navbar-item", href="/cutting-table") { "Mesas de corte" }
                     a(class="navbar-item", href="/fabric-cut") { "Cortes" }
+                    a(class="navbar-item", href="/setting") { "Configurações" }
                 }
             }
         }
@@ -89,7 +94,8 @@ fn AppRouter<G: Html>(cx: Scope) -> View<G> {
                         },
                         AppRoutes::ExportDisposition => view!(
                             cx, ExportDispositionPage {}
-                        )
+                        ),
+                        AppRoutes::Setting => view! { cx, SettingPage {} },
                     }
                 )}
             }
@@ -121,4 +127,6 @@ enum AppRoutes {
     GenerateGCode,
     #[to("/export-disposition")]
     ExportDisposition,
+    #[to("/setting")]
+    Setting,
 }
@@ -11,25 +11,81 @@
     Y(length)
  */
 
+use std::fmt::Display;
+
 use serde::{Serialize, Deserialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+use super::fabric::FabricGrain;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct CutDispositionInput {
     pub rectangles_list: Vec<Rectangle>,
     pub prohibited_area_list: Vec<PositionedRectangle>,
-    pub showcase: Option<Rectangle>,
-    pub spacing: Option<i32>,
-    pub max_length: i32,
-    pub defined_length: Option<i32>,
-    pub defined_width: i32,
+    pub showcase_list: Vec<Rectangle>,
+    pub spacing: Option<f64>,
+    pub max_length: f64,
+    pub defined_length: Option<f64>,
+    pub defined_width: f64,
+    pub allow_rotation: bool,
+    pub nesting_strategy: NestingStrategyType,
+    pub multi_sheet: bool,
+    pub optimization: Option<OptimizationConfig>,
+    pub fabric_grain: FabricGrain,
+    pub pattern_repeat: Option<PatternRepeat>,
+    pub edge_margins: EdgeMargins,
+    pub pinned_piece_list: Vec<PositionedRectangle>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ConfigCutDispositionInput {
-    pub spacing: Option<i32>,
-    pub max_length: i32,
-    pub defined_length: Option<i32>,
-    pub defined_width: i32,
+    pub spacing: Option<f64>,
+    pub max_length: f64,
+    pub defined_length: Option<f64>,
+    pub defined_width: f64,
+    pub allow_rotation: bool,
+    pub nesting_strategy: NestingStrategyType,
+    pub multi_sheet: bool,
+    pub optimization: Option<OptimizationConfig>,
+    pub fabric_id: Option<i32>,
+    pub edge_margins: EdgeMargins,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct EdgeMargins {
+    pub left: f64,
+    pub right: f64,
+    pub top: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PatternRepeat {
+    pub repeat_width: Option<f64>,
+    pub repeat_length: Option<f64>,
+    pub offset_x: f64,
+    pub offset_y: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OptimizationConfig {
+    pub time_limit: u32,
+    pub seed: u32,
+    #[serde(default)]
+    pub max_iterations: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OrganizeProgress {
+    pub sheet: u32,
+    pub iteration: u32,
+    pub best_usage: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub enum NestingStrategyType {
+    #[default]
+    BottomLeftVertex,
+    MaxRects,
+    Guillotine,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -38,33 +94,142 @@ pub struct CutDispositionOutput {
     pub showcase_rectangles_located_list: Vec<PositionedRectangle>,
     pub unused_rectangles_list: Vec<Rectangle>,
     pub prohibited_area_list: Vec<PositionedRectangle>,
-    pub length_used: i32,
-    pub total_area: i32,
-    pub used_area: i32,
+    pub length_used: f64,
+    pub total_area: f64,
+    pub used_area: f64,
     pub usage: f64,
-    pub max_length: i32,
-    pub defined_length: Option<i32>,
-    pub defined_width: i32,
+    pub piece_count_list: Vec<PieceCount>,
+    pub showcase_count_list: Vec<ShowcaseCount>,
+    pub is_guillotine: bool,
+    pub edge_margins: EdgeMargins,
+    pub max_length: f64,
+    pub defined_length: Option<f64>,
+    pub defined_width: f64,
+    pub unplaced_piece_list: Vec<UnplacedPiece>,
 }
 
-
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum UnplacedReason {
+    WiderThanFabric,
+    LongerThanFabric,
+    BlockedByProhibitedAreas,
+    NoRoomLeft,
+}
+impl Display for UnplacedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnplacedReason::WiderThanFabric => write!(f, "Mais larga que o tecido"),
+            UnplacedReason::LongerThanFabric => write!(f, "Mais comprida que o tecido"),
+            UnplacedReason::BlockedByProhibitedAreas => write!(f, "Bloqueada por áreas proibidas"),
+            UnplacedReason::NoRoomLeft => write!(f, "Sem espaço"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NearMiss {
+    pub positioned_rectangle: PositionedRectangle,
+    pub overlap_area: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UnplacedPiece {
+    pub id: u32,
+    pub quantity: u32,
+    pub reason: UnplacedReason,
+    pub near_miss: Option<NearMiss>,
+}
+// the lengths come in the unit of the setting
+impl UnplacedPiece {
+    pub fn get_message(&self, length_unit_symbol: &str) -> String {
+        match &self.near_miss {
+            Some(near_miss) => format!(
+                "{} ({} restantes), mais próxima em ({}, {}) com sobreposição de {} {}²",
+                self.reason,
+                self.quantity,
+                near_miss.positioned_rectangle.top_left_vertex.pos_x,
+                near_miss.positioned_rectangle.top_left_vertex.pos_y,
+                near_miss.overlap_area,
+                length_unit_symbol
+            ),
+            None => format!("{} ({} restantes)", self.reason, self.quantity),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum LayoutAdjustment {
+    Move { piece_index: usize, top_left_vertex: Vertex },
+    Swap { piece_index: usize, other_piece_index: usize },
+    Rotate { piece_index: usize },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum DispositionViolation {
+    Overlap { id: u32, other_id: u32 },
+    SpacingBreach { id: u32, other_id: u32, spacing: f64 },
+    OutOfBounds { id: u32 },
+    ProhibitedAreaIntersection { id: u32, prohibited_area_id: u32 },
+    DuplicatedId { id: u32 },
+}
+impl DispositionViolation {
+    pub fn get_message(&self, length_unit_symbol: &str) -> String {
+        match self {
+            DispositionViolation::Overlap { id, other_id } => format!("Peça {} sobreposta à peça {}", id, other_id),
+            DispositionViolation::SpacingBreach { id, other_id, spacing } => format!("Peça {} a menos de {}{} da peça {}", id, spacing, length_unit_symbol, other_id),
+            DispositionViolation::OutOfBounds { id } => format!("Peça {} fora da área útil do tecido", id),
+            DispositionViolation::ProhibitedAreaIntersection { id, prohibited_area_id } => format!("Peça {} sobre a área proibida {}", id, prohibited_area_id),
+            DispositionViolation::DuplicatedId { id } => format!("Id {} repetido na disposição", id),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AdjustedCutDisposition {
+    pub cut_disposition_output: CutDispositionOutput,
+    pub violation_list: Vec<DispositionViolation>,
+}
+
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Rectangle {
     pub id: u32,
-    pub width: i32,
-    pub length: i32,
+    pub width: f64,
+    pub length: f64,
+    pub allow_rotation: Option<bool>,
+    pub quantity: u32,
+    pub max_quantity: Option<u32>,
+    pub match_pattern: bool,
+    pub margin: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PieceCount {
+    pub id: u32,
+    pub quantity: u32,
+    pub placed_quantity: u32,
+    pub unplaced_quantity: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShowcaseCount {
+    pub id: u32,
+    pub placed_quantity: u32,
+    pub used_area: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct PositionedRectangle {
     pub id: u32,
-    pub width: i32,
-    pub length: i32,
+    pub width: f64,
+    pub length: f64,
     pub top_left_vertex: Vertex,
+    pub rotated: bool,
+    pub margin: Option<f64>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Vertex {
-    pub pos_x: i32,
-    pub pos_y: i32,
+    pub pos_x: f64,
+    pub pos_y: f64,
 }
@@ -0,0 +1,9 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CutStrategy {
+    #[default]
+    VerticalFirst,
+    HorizontalFirst,
+    ByBand,
+}
@@ -1,16 +1,16 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct CuttingTable {
     pub id: i32,
     pub name: String,
-    pub width: i32,
-    pub length: i32,
+    pub width: f64,
+    pub length: f64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CuttingTableCreate {
     pub name: String,
-    pub width: i32,
-    pub length: i32,
+    pub width: f64,
+    pub length: f64,
 }
@@ -1,18 +1,42 @@
 use serde::{Serialize, Deserialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Fabric {
     pub id: i32,
     pub name: String,
     pub manufacturer: String,
-    pub width: i32,
+    pub width: f64,
     pub code: String,
+    pub grain: FabricGrain,
+    pub repeat_width: Option<f64>,
+    pub repeat_length: Option<f64>,
+    pub repeat_offset_x: f64,
+    pub repeat_offset_y: f64,
+    pub margin_left: f64,
+    pub margin_right: f64,
+    pub margin_top: f64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FabricCreate {
     pub name: String,
     pub manufacturer: String,
-    pub width: i32,
+    pub width: f64,
     pub code: String,
-}
\ No newline at end of file
+    pub grain: FabricGrain,
+    pub repeat_width: Option<f64>,
+    pub repeat_length: Option<f64>,
+    pub repeat_offset_x: f64,
+    pub repeat_offset_y: f64,
+    pub margin_left: f64,
+    pub margin_right: f64,
+    pub margin_top: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FabricGrain {
+    #[default]
+    RotationAllowed,
+    RotationForbidden,
+    HalfTurnOnly,
+}
@@ -1,5 +1,7 @@
 pub mod app_error;
 pub mod cut_disposition;
+pub mod cutting_lines;
 pub mod cutting_table;
 pub mod fabric;
 pub mod piece;
+pub mod setting;
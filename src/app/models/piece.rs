@@ -2,9 +2,10 @@ use serde::{Serialize, Deserialize};
 
 use super::cut_disposition::{Rectangle, PositionedRectangle};
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum RectangleType {
     Piece(Rectangle),
     Showcase(Rectangle),
-    ProhibitedArea(PositionedRectangle)
+    ProhibitedArea(PositionedRectangle),
+    PinnedPiece(PositionedRectangle)
 }
@@ -0,0 +1,10 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Setting {
+    // Millimeter, Centimeter or Inch, the lengths are converted by the backend
+    pub length_unit: String,
+    pub g_code_decimal_places: u32,
+    pub overcut: f64,
+    pub lead_in: f64,
+}
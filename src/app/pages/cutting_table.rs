@@ -3,10 +3,10 @@ use sycamore_router::navigate;
 
 use crate::app::{
     models::cutting_table::{CuttingTable, CuttingTableCreate},
-    services::cutting_table_service::{
+    services::{cutting_table_service::{
         create_cutting_table, delete_cutting_table, get_all_cutting_table, get_cutting_table_by_id,
         update_cutting_table,
-    },
+    }, setting_service::get_length_unit_symbol},
 };
 
 #[component(inline_props)]
@@ -16,8 +16,8 @@ fn CuttingTableItem<G: Html>(cx: Scope, cutting_table: CuttingTable) -> View<G>
         tr(class="p-7") {
             td (style="width:5%; vertical-align:middle;") { (item.id.clone()) }
             td (style="width:30%; vertical-align:middle;") { (item.name.clone())  }
-            td (style="width:10%; vertical-align:middle;") { (item.width.clone()) }
-            td (style="width:10%; vertical-align:middle;") { (item.length.clone()) }
+            td (style="width:10%; vertical-align:middle;") { (item.width) }
+            td (style="width:10%; vertical-align:middle;") { (item.length) }
             td (style="width:10%; vertical-align:middle;") {
                 a(class="button is-medium is-fullwidth is-success", href=(format!("/cutting-table/{}", item.id ))) { "Editar" }
             }
@@ -30,8 +30,11 @@ pub fn CuttingTableListPage<G: Html>(cx: Scope<'_>) -> View<G> {
     let cutting_table_list: &Signal<Vec<CuttingTable>> =
         create_signal(cx, Vec::<CuttingTable>::new());
 
+    let length_unit_symbol = create_signal(cx, String::new());
+
     let fetch_all_cutting_table = move || {
         spawn_local_scoped(cx, async move {
+            length_unit_symbol.set(get_length_unit_symbol().await);
             let new_cutting_table_list = get_all_cutting_table().await;
 
             match new_cutting_table_list {
@@ -69,8 +72,8 @@ pub fn CuttingTableListPage<G: Html>(cx: Scope<'_>) -> View<G> {
                         tr {
                             th(style="width:5%; vertical-align:middle;") { "ID" }
                             th(style="width:50%; vertical-align:middle;") { "Nome" }
-                            th(style="width:15%; vertical-align:middle;") { "Largura (mm)" }
-                            th(style="width:20%; vertical-align:middle;") { "Comprimento (mm)" }
+                            th(style="width:15%; vertical-align:middle;") { (format!("Largura ({})", length_unit_symbol.get())) }
+                            th(style="width:20%; vertical-align:middle;") { (format!("Comprimento ({})", length_unit_symbol.get())) }
                             th(style="width:10%; vertical-align:middle;") {
                                 a(class="button is-medium is-success is-fullwidth", href="/cutting-table/0") { "Novo" }
                             }
@@ -103,25 +106,27 @@ pub fn CuttingTableItemPage<G: Html>(cx: Scope<'_>, props: CuttingTableItemProps
     let width = create_signal(cx, 0.0);
     let length = create_signal(cx, 0.0);
     let error_message = create_signal(cx, String::new());
+    let length_unit_symbol = create_signal(cx, String::new());
 
     let param_id = props.id;
-    if param_id > 0 {
-        spawn_local_scoped(cx, async move {
+    spawn_local_scoped(cx, async move {
+        length_unit_symbol.set(get_length_unit_symbol().await);
+        if param_id > 0 {
             if let Ok(item) = get_cutting_table_by_id(param_id).await {
                 id.set(item.id as f64);
                 name.set(item.name);
-                width.set(item.width as f64);
-                length.set(item.length as f64);
+                width.set(item.width);
+                length.set(item.length);
             }
-        })
-    }
+        }
+    });
 
     let save_item = move |_| {
         spawn_local_scoped(cx, async move {
             let param_id = id.get().as_ref().round() as i32;
             let param_name = name.get().as_ref().clone();
-            let param_width = width.get().as_ref().round() as i32;
-            let param_length = length.get().as_ref().round() as i32;
+            let param_width = *width.get();
+            let param_length = *length.get();
             let response = match param_id {
                 0 => {
                     let item = CuttingTableCreate {
@@ -195,13 +200,13 @@ pub fn CuttingTableItemPage<G: Html>(cx: Scope<'_>, props: CuttingTableItemProps
                         }
                     }
                     div(class="field") {
-                        label(class="label") { "Largura (mm)" }
+                        label(class="label") { (format!("Largura ({})", length_unit_symbol.get())) }
                         div(class="control") {
                             input(class="input", type="number", placeholder="identificador", bind:valueAsNumber=width)
                         }
                     }
                     div(class="field") {
-                        label(class="label") { "Comprimento (mm)" }
+                        label(class="label") { (format!("Comprimento ({})", length_unit_symbol.get())) }
                         div(class="control") {
                             input(class="input", type="number", placeholder="identificador", bind:valueAsNumber=length)
                         }
@@ -1,8 +1,23 @@
 use sycamore::{prelude::*, component, futures::spawn_local_scoped};
 use sycamore_router::navigate;
 
-use crate::app::{models::fabric::{Fabric, FabricCreate}, services::fabric_service::{get_all_fabric, get_fabric_by_id, create_fabric, update_fabric, delete_fabric}};
+use crate::app::{models::fabric::{Fabric, FabricCreate, FabricGrain}, services::{fabric_service::{get_all_fabric, get_fabric_by_id, create_fabric, update_fabric, delete_fabric}, setting_service::get_length_unit_symbol}, utils::utils::get_optional_from_boolean_and_value};
 
+fn get_fabric_grain_from_option(option: &str) -> FabricGrain {
+    match option {
+        "1" => FabricGrain::RotationForbidden,
+        "2" => FabricGrain::HalfTurnOnly,
+        _ => FabricGrain::RotationAllowed,
+    }
+}
+
+fn get_option_from_fabric_grain(fabric_grain: &FabricGrain) -> String {
+    match fabric_grain {
+        FabricGrain::RotationAllowed => String::from("0"),
+        FabricGrain::RotationForbidden => String::from("1"),
+        FabricGrain::HalfTurnOnly => String::from("2"),
+    }
+}
 
 #[component(inline_props)]
 fn FabricItem<G: Html>(cx: Scope, fabric: Fabric) -> View<G> {
@@ -12,7 +27,7 @@ fn FabricItem<G: Html>(cx: Scope, fabric: Fabric) -> View<G> {
             td (style="width:5%; vertical-align:middle;") { (item.id.clone()) }
             td (style="width:30%; vertical-align:middle;") { (item.name.clone())  }
             td (style="width:30%; vertical-align:middle;") { (item.manufacturer.clone()) }
-            td (style="width:10%; vertical-align:middle;") { (item.width.clone()) }
+            td (style="width:10%; vertical-align:middle;") { (item.width) }
             td (style="width:15%; vertical-align:middle;") { (item.code.clone()) }
             td (style="width:10%; vertical-align:middle;") {
                 a(class="button is-medium is-fullwidth is-success", href=(format!("/fabric/{}", item.id ))) { "Editar" }
@@ -25,8 +40,11 @@ fn FabricItem<G: Html>(cx: Scope, fabric: Fabric) -> View<G> {
 pub fn FabricListPage<G: Html>(cx: Scope<'_>) -> View<G> {
     let fabric_list: &Signal<Vec::<Fabric>> = create_signal(cx, Vec::<Fabric>::new());
 
+    let length_unit_symbol = create_signal(cx, String::new());
+
     let fetch_all_fabric = move || {
         spawn_local_scoped(cx, async move {
+            length_unit_symbol.set(get_length_unit_symbol().await);
             let new_fabric_list =
                 get_all_fabric().await;
 
@@ -67,7 +85,7 @@ pub fn FabricListPage<G: Html>(cx: Scope<'_>) -> View<G> {
                             th(style="width:5%; vertical-align:middle;") { "ID" }
                             th(style="width:30%; vertical-align:middle;") { "Nome" }
                             th(style="width:30%; vertical-align:middle;") { "Fabricante" }
-                            th(style="width:10%; vertical-align:middle;") { "Largura (mm)" }
+                            th(style="width:10%; vertical-align:middle;") { (format!("Largura ({})", length_unit_symbol.get())) }
                             th(style="width:15%; vertical-align:middle;") { "Código" }
                             th(style="width:10%; vertical-align:middle;") {
                                 a(class="button is-medium is-success is-fullwidth", href="/fabric/0") { "Novo" }
@@ -103,28 +121,64 @@ pub fn FabricItemPage< G: Html>(cx: Scope<'_>, props: FabricItemProps) -> View<G
     let manufacturer = create_signal(cx, String::new());
     let width = create_signal(cx, 0.0);
     let code = create_signal(cx, String::new());
+    let grain = create_signal(cx, get_option_from_fabric_grain(&FabricGrain::RotationAllowed));
+    let repeat_width = create_signal(cx, 0.0);
+    let repeat_width_selection = create_signal(cx, false);
+    let toggle_repeat_width_selection = || {
+        repeat_width_selection.set(!*repeat_width_selection.get())
+    };
+    let repeat_length = create_signal(cx, 0.0);
+    let repeat_length_selection = create_signal(cx, false);
+    let toggle_repeat_length_selection = || {
+        repeat_length_selection.set(!*repeat_length_selection.get())
+    };
+    let repeat_offset_x = create_signal(cx, 0.0);
+    let repeat_offset_y = create_signal(cx, 0.0);
+    let margin_left = create_signal(cx, 0.0);
+    let margin_right = create_signal(cx, 0.0);
+    let margin_top = create_signal(cx, 0.0);
     let error_message = create_signal(cx, String::new());
+    let length_unit_symbol = create_signal(cx, String::new());
 
     let param_id = props.id;
-    if param_id > 0 {
-        spawn_local_scoped(cx, async move {
+    spawn_local_scoped(cx, async move {
+        length_unit_symbol.set(get_length_unit_symbol().await);
+        if param_id > 0 {
             if let Ok(item) = get_fabric_by_id(param_id).await {
                 id.set(item.id as f64);
                 name.set(item.name);
                 manufacturer.set(item.manufacturer);
-                width.set(item.width as f64);
+                width.set(item.width);
                 code.set(item.code);
+                grain.set(get_option_from_fabric_grain(&item.grain));
+                repeat_width.set(item.repeat_width.unwrap_or(0.0));
+                repeat_width_selection.set(item.repeat_width.is_some());
+                repeat_length.set(item.repeat_length.unwrap_or(0.0));
+                repeat_length_selection.set(item.repeat_length.is_some());
+                repeat_offset_x.set(item.repeat_offset_x);
+                repeat_offset_y.set(item.repeat_offset_y);
+                margin_left.set(item.margin_left);
+                margin_right.set(item.margin_right);
+                margin_top.set(item.margin_top);
             }
-        })
-    }
+        }
+    });
 
     let save_item =  move |_| {
         spawn_local_scoped(cx, async move {
             let param_id = id.get().as_ref().round() as i32;
             let param_name = name.get().as_ref().clone();
             let param_manufacturer = manufacturer.get().as_ref().clone();
-            let param_width = width.get().as_ref().round() as i32;
+            let param_width = *width.get();
             let param_code = code.get().as_ref().clone();
+            let param_grain = get_fabric_grain_from_option(grain.get().as_str());
+            let param_repeat_width = get_optional_from_boolean_and_value(*repeat_width_selection.get(), *repeat_width.get());
+            let param_repeat_length = get_optional_from_boolean_and_value(*repeat_length_selection.get(), *repeat_length.get());
+            let param_repeat_offset_x = *repeat_offset_x.get();
+            let param_repeat_offset_y = *repeat_offset_y.get();
+            let param_margin_left = *margin_left.get();
+            let param_margin_right = *margin_right.get();
+            let param_margin_top = *margin_top.get();
             let response = match param_id {
                 0 => {
                     let item = FabricCreate {
@@ -132,6 +186,14 @@ pub fn FabricItemPage< G: Html>(cx: Scope<'_>, props: FabricItemProps) -> View<G
                         manufacturer: param_manufacturer,
                         width: param_width,
                         code: param_code,
+                        grain: param_grain,
+                        repeat_width: param_repeat_width,
+                        repeat_length: param_repeat_length,
+                        repeat_offset_x: param_repeat_offset_x,
+                        repeat_offset_y: param_repeat_offset_y,
+                        margin_left: param_margin_left,
+                        margin_right: param_margin_right,
+                        margin_top: param_margin_top,
                     };
                     create_fabric(item).await
                 },
@@ -142,6 +204,14 @@ pub fn FabricItemPage< G: Html>(cx: Scope<'_>, props: FabricItemProps) -> View<G
                         manufacturer: param_manufacturer,
                         width: param_width,
                         code: param_code,
+                        grain: param_grain,
+                        repeat_width: param_repeat_width,
+                        repeat_length: param_repeat_length,
+                        repeat_offset_x: param_repeat_offset_x,
+                        repeat_offset_y: param_repeat_offset_y,
+                        margin_left: param_margin_left,
+                        margin_right: param_margin_right,
+                        margin_top: param_margin_top,
                     };
                     update_fabric(item).await
                 }
@@ -208,7 +278,7 @@ pub fn FabricItemPage< G: Html>(cx: Scope<'_>, props: FabricItemProps) -> View<G
                         }
                     }
                     div(class="field") {
-                        label(class="label") { "Largura (mm)" }
+                        label(class="label") { (format!("Largura ({})", length_unit_symbol.get())) }
                         div(class="control") {
                             input(class="input", type="number", placeholder="identificador", bind:valueAsNumber=width)
                         }
@@ -219,6 +289,98 @@ pub fn FabricItemPage< G: Html>(cx: Scope<'_>, props: FabricItemProps) -> View<G
                             input(class="input", type="text", placeholder="identificador", bind:value=code)
                         }
                     }
+                    div(class="field") {
+                        label(class="label") { "Fio do tecido" }
+                        div(class="control") {
+                            div(class="select is-fullwidth") {
+                                select(bind:value=grain) {
+                                    option(value="0") { "Rotação permitida" }
+                                    option(value="1") { "Rotação proibida" }
+                                    option(value="2") { "Somente 180°" }
+                                }
+                            }
+                        }
+                    }
+                    div(class="field") {
+                        label(class="label") { (format!("Repetição da estampa na largura ({})", length_unit_symbol.get())) }
+                        div (class="level")  {
+                            div(class="level-left") {
+                                input(
+                                    class="toggle",
+                                    type="checkbox",
+                                    on:input=move |_| toggle_repeat_width_selection(),
+                                    bind:checked=repeat_width_selection
+                                )
+                            }
+                            div(class="level-item") {
+                                div (class="control")  {
+                                    input(
+                                        class="input", 
+                                        type="number", 
+                                        bind:valueAsNumber=repeat_width, 
+                                        disabled = !*repeat_width_selection.get(),
+                                        step="any",
+                                        min="1"
+                                    ) {}
+                                }
+                            }
+                        }
+                    }
+                    div(class="field") {
+                        label(class="label") { (format!("Repetição da estampa no comprimento ({})", length_unit_symbol.get())) }
+                        div (class="level")  {
+                            div(class="level-left") {
+                                input(
+                                    class="toggle",
+                                    type="checkbox",
+                                    on:input=move |_| toggle_repeat_length_selection(),
+                                    bind:checked=repeat_length_selection
+                                )
+                            }
+                            div(class="level-item") {
+                                div (class="control")  {
+                                    input(
+                                        class="input", 
+                                        type="number", 
+                                        bind:valueAsNumber=repeat_length, 
+                                        disabled = !*repeat_length_selection.get(),
+                                        step="any",
+                                        min="1"
+                                    ) {}
+                                }
+                            }
+                        }
+                    }
+                    div(class="field") {
+                        label(class="label") { (format!("Início da estampa na largura ({})", length_unit_symbol.get())) }
+                        div(class="control") {
+                            input(class="input", type="number", bind:valueAsNumber=repeat_offset_x, step="any", min="0")
+                        }
+                    }
+                    div(class="field") {
+                        label(class="label") { (format!("Início da estampa no comprimento ({})", length_unit_symbol.get())) }
+                        div(class="control") {
+                            input(class="input", type="number", bind:valueAsNumber=repeat_offset_y, step="any", min="0")
+                        }
+                    }
+                    div(class="field") {
+                        label(class="label") { (format!("Ourela esquerda ({})", length_unit_symbol.get())) }
+                        div(class="control") {
+                            input(class="input", type="number", bind:valueAsNumber=margin_left, step="any", min="0")
+                        }
+                    }
+                    div(class="field") {
+                        label(class="label") { (format!("Ourela direita ({})", length_unit_symbol.get())) }
+                        div(class="control") {
+                            input(class="input", type="number", bind:valueAsNumber=margin_right, step="any", min="0")
+                        }
+                    }
+                    div(class="field") {
+                        label(class="label") { (format!("Margem inicial ({})", length_unit_symbol.get())) }
+                        div(class="control") {
+                            input(class="input", type="number", bind:valueAsNumber=margin_top, step="any", min="0")
+                        }
+                    }
                     div {
                         p(class="has-text-danger") { (error_message.get()) }
                     }
@@ -241,4 +403,4 @@ pub fn FabricItemPage< G: Html>(cx: Scope<'_>, props: FabricItemProps) -> View<G
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,30 @@
 use sycamore::{prelude::*, futures::spawn_local_scoped};
 
-use crate::app::{services::{cut_disposition_service::{get_cut_disposition_input, set_config_cut_disposition_input, get_config_cut_disposition_input, get_cut_disposition_output}, cutting_table_service::get_all_cutting_table, fabric_service::get_all_fabric, export_import_service::import_disposition}, utils::utils::get_optional_from_boolean_and_value, models::{cut_disposition::{ConfigCutDispositionInput, Rectangle, PositionedRectangle}, cutting_table::CuttingTable, fabric::Fabric}};
+use crate::app::{services::{cut_disposition_service::{get_cut_disposition_input, set_config_cut_disposition_input, get_config_cut_disposition_input, get_cut_disposition_output, cancel_organize_cut_disposition, listen_organize_progress, get_cut_disposition_layout, adjust_cut_disposition}, cutting_table_service::get_all_cutting_table, fabric_service::get_all_fabric, export_import_service::import_disposition, setting_service::get_length_unit_symbol}, utils::utils::get_optional_from_boolean_and_value, models::{cut_disposition::{ConfigCutDispositionInput, Rectangle, PositionedRectangle, PieceCount, ShowcaseCount, NestingStrategyType, CutDispositionOutput, OptimizationConfig, OrganizeProgress, EdgeMargins, LayoutAdjustment, Vertex, DispositionViolation, UnplacedPiece}, cutting_table::CuttingTable, fabric::{Fabric, FabricGrain}}};
 
 enum SelectedPanel {
     Config,
     Pieces,
+    Adjust,
     Info
 }
 
+fn get_nesting_strategy_from_option(option: &str) -> NestingStrategyType {
+    match option {
+        "1" => NestingStrategyType::MaxRects,
+        "2" => NestingStrategyType::Guillotine,
+        _ => NestingStrategyType::BottomLeftVertex,
+    }
+}
+
+fn get_option_from_nesting_strategy(nesting_strategy: &NestingStrategyType) -> String {
+    match nesting_strategy {
+        NestingStrategyType::BottomLeftVertex => "0".to_string(),
+        NestingStrategyType::MaxRects => "1".to_string(),
+        NestingStrategyType::Guillotine => "2".to_string(),
+    }
+}
+
 #[component]
 pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
 
@@ -17,6 +34,8 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
 
     let set_pieces_panel_active = |_| active_panel.set(SelectedPanel::Pieces);
 
+    let set_adjust_panel_active = |_| active_panel.set(SelectedPanel::Adjust);
+
     let set_info_panel_active = |_| active_panel.set(SelectedPanel::Info);
 
     let defined_width = create_signal(cx, 0.0);
@@ -35,39 +54,118 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
         defined_length_selection.set(!*defined_length_selection.get())
     };
 
+    let allow_rotation = create_signal(cx, false);
+    let toggle_allow_rotation = || {
+        allow_rotation.set(!*allow_rotation.get())
+    };
+
+    let margin_left = create_signal(cx, 0.0);
+    let margin_right = create_signal(cx, 0.0);
+    let margin_top = create_signal(cx, 0.0);
+
+    // the values of the inputs are in the unit of the setting, the drawing stays in millimetres
+    let length_unit_symbol = create_signal(cx, String::new());
+
+    let fabric_grain = create_signal(cx, FabricGrain::RotationAllowed);
+    let selected_fabric = create_signal(cx, String::from("0"));
+
+    let nesting_strategy = create_signal(cx, get_option_from_nesting_strategy(&NestingStrategyType::BottomLeftVertex));
+
+    let multi_sheet = create_signal(cx, false);
+    let toggle_multi_sheet = || {
+        multi_sheet.set(!*multi_sheet.get())
+    };
+
+    let optimization_time_limit = create_signal(cx, 1000.0);
+    let optimization_seed = create_signal(cx, 1.0);
+    let optimization_selection = create_signal(cx, false);
+    let toggle_optimization_selection = || {
+        optimization_selection.set(!*optimization_selection.get())
+    };
+    let optimization_max_iterations = create_signal(cx, 1000.0);
+    let optimization_max_iterations_selection = create_signal(cx, false);
+    let toggle_optimization_max_iterations_selection = || {
+        optimization_max_iterations_selection.set(!*optimization_max_iterations_selection.get())
+    };
+
     let config_error_message = create_signal(cx, Option::<String>::None);
 
     let piece_list = create_signal(cx, Vec::<Rectangle>::new());
-    let showcase = create_signal(cx, None);
+    let showcase_list = create_signal(cx, Vec::<Rectangle>::new());
     let prohibited_area_list = create_signal(cx, Vec::<PositionedRectangle>::new());
+    let pinned_piece_list = create_signal(cx, Vec::<PositionedRectangle>::new());
     
     let fit_list_to_draw: &Signal<Vec<PositionedRectangle>> = create_signal(cx, Vec::new());
     let prohibited_list_to_draw: &Signal<Vec<PositionedRectangle>> = create_signal(cx, Vec::new());
     let showcase_list_to_draw: &Signal<Vec<PositionedRectangle>> = create_signal(cx, Vec::new());
     let unused_rectangles_list: &Signal<Vec<Rectangle>> = create_signal(cx, Vec::new());
+    let unplaced_piece_list: &Signal<Vec<UnplacedPiece>> = create_signal(cx, Vec::new());
+    let piece_count_list: &Signal<Vec<PieceCount>> = create_signal(cx, Vec::new());
+    let showcase_count_list: &Signal<Vec<ShowcaseCount>> = create_signal(cx, Vec::new());
 
     let length_used = create_signal(cx, 0.0);
     let total_area = create_signal(cx, 0.0);
     let used_area = create_signal(cx, 0.0);
     let usage = create_signal(cx, 0.0);
+    let is_guillotine = create_signal(cx, false);
     let max_length_to_draw = create_signal(cx, 0.0);
     let defined_width_to_draw = create_signal(cx, 0.0);
 
+    let sheet_output_list: &Signal<Vec<CutDispositionOutput>> = create_signal(cx, Vec::new());
+    let selected_sheet = create_signal(cx, 0_usize);
+
+    let organizing = create_signal(cx, false);
+    // updated by the progress events, outside of the scope of the page
+    let organize_progress = create_ref(cx, create_rc_signal(Option::<OrganizeProgress>::None));
+
     let draw_error_message = create_signal(cx, Option::<String>::None);
 
     let info_error_message = create_signal(cx, Option::<String>::None);
+    let import_violation_list = create_signal(cx, Vec::<DispositionViolation>::new());
+
+    let adjust_piece_index = create_signal(cx, String::from("0"));
+    let adjust_other_piece_index = create_signal(cx, String::from("0"));
+    // 0 moves, 1 swaps with the other piece and 2 rotates
+    let adjust_action = create_signal(cx, String::from("0"));
+    let adjust_pos_x = create_signal(cx, 0.0);
+    let adjust_pos_y = create_signal(cx, 0.0);
+    let adjust_violation_list = create_signal(cx, Vec::<DispositionViolation>::new());
+    let adjust_error_message = create_signal(cx, Option::<String>::None);
+
+    let adjust_piece_option_list = create_memo(cx, || {
+        fit_list_to_draw
+        .get()
+        .iter()
+        .enumerate()
+        .map(
+            |(index, piece)| (
+                index, 
+                format!(
+                    "{} - Peça {} ({} x {}) em ({}, {})", 
+                    index + 1, 
+                    piece.id, 
+                    piece.width, 
+                    piece.length, 
+                    piece.top_left_vertex.pos_x, 
+                    piece.top_left_vertex.pos_y
+                )
+            )
+        )
+        .collect::<Vec<(usize, String)>>()
+    });
 
     let get_cut_disposition_input = move || {
         spawn_local_scoped(cx, async move {
+            length_unit_symbol.set(get_length_unit_symbol().await);
             let cut_disposition_input_result = get_cut_disposition_input().await;
             match cut_disposition_input_result {
                 Ok(cut_disposition_input) => {
-                    defined_width.set(cut_disposition_input.defined_width as f64);
-                    max_length.set(cut_disposition_input.max_length as f64);
+                    defined_width.set(cut_disposition_input.defined_width);
+                    max_length.set(cut_disposition_input.max_length);
 
                     match cut_disposition_input.spacing {
                         Some(value) => {
-                            spacing.set(value as f64);
+                            spacing.set(value);
                             spacing_selection.set(true)
                         },
                         None => {
@@ -78,7 +176,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
 
                     match cut_disposition_input.defined_length {
                         Some(value) => {
-                            defined_length.set(value as f64);
+                            defined_length.set(value);
                             defined_length_selection.set(true)
                         },
                         None => {
@@ -86,9 +184,41 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                             defined_length_selection.set(false)
                         },
                     }
+                    allow_rotation.set(cut_disposition_input.allow_rotation);
+                    margin_left.set(cut_disposition_input.edge_margins.left);
+                    margin_right.set(cut_disposition_input.edge_margins.right);
+                    margin_top.set(cut_disposition_input.edge_margins.top);
+                    fabric_grain.set(cut_disposition_input.fabric_grain);
+                    nesting_strategy.set(get_option_from_nesting_strategy(&cut_disposition_input.nesting_strategy));
+                    multi_sheet.set(cut_disposition_input.multi_sheet);
+                    match cut_disposition_input.optimization {
+                        Some(value) => {
+                            optimization_time_limit.set(value.time_limit as f64);
+                            optimization_seed.set(value.seed as f64);
+                            match value.max_iterations {
+                                Some(max_iterations) => {
+                                    optimization_max_iterations.set(max_iterations as f64);
+                                    optimization_max_iterations_selection.set(true)
+                                },
+                                None => {
+                                    optimization_max_iterations.set(1000.0);
+                                    optimization_max_iterations_selection.set(false)
+                                },
+                            }
+                            optimization_selection.set(true)
+                        },
+                        None => {
+                            optimization_time_limit.set(1000.0);
+                            optimization_seed.set(1.0);
+                            optimization_max_iterations.set(1000.0);
+                            optimization_max_iterations_selection.set(false);
+                            optimization_selection.set(false)
+                        },
+                    }
                     piece_list.set(cut_disposition_input.rectangles_list);
-                    showcase.set(cut_disposition_input.showcase);
+                    showcase_list.set(cut_disposition_input.showcase_list);
                     prohibited_area_list.set(cut_disposition_input.prohibited_area_list);
+                    pinned_piece_list.set(cut_disposition_input.pinned_piece_list);
                 },
                 Err(_error) => todo!(),
             }
@@ -102,12 +232,12 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
             let config_cut_disposition_result = get_config_cut_disposition_input().await;
             match config_cut_disposition_result {
                 Ok(config_cut_disposition) => {
-                    defined_width.set(config_cut_disposition.defined_width as f64);
-                    max_length.set(config_cut_disposition.max_length as f64);
+                    defined_width.set(config_cut_disposition.defined_width);
+                    max_length.set(config_cut_disposition.max_length);
                     config_error_message.set(None);
                     match config_cut_disposition.spacing {
                         Some(value) => {
-                            spacing.set(value as f64);
+                            spacing.set(value);
                             spacing_selection.set(true)
                         },
                         None => {
@@ -118,7 +248,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
 
                     match config_cut_disposition.defined_length {
                         Some(value) => {
-                            defined_length.set(value as f64);
+                            defined_length.set(value);
                             defined_length_selection.set(true)
                         },
                         None => {
@@ -126,6 +256,36 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                             defined_length_selection.set(false)
                         },
                     }
+                    margin_left.set(config_cut_disposition.edge_margins.left);
+                    margin_right.set(config_cut_disposition.edge_margins.right);
+                    margin_top.set(config_cut_disposition.edge_margins.top);
+                    allow_rotation.set(config_cut_disposition.allow_rotation);
+                    nesting_strategy.set(get_option_from_nesting_strategy(&config_cut_disposition.nesting_strategy));
+                    multi_sheet.set(config_cut_disposition.multi_sheet);
+                    match config_cut_disposition.optimization {
+                        Some(value) => {
+                            optimization_time_limit.set(value.time_limit as f64);
+                            optimization_seed.set(value.seed as f64);
+                            match value.max_iterations {
+                                Some(max_iterations) => {
+                                    optimization_max_iterations.set(max_iterations as f64);
+                                    optimization_max_iterations_selection.set(true)
+                                },
+                                None => {
+                                    optimization_max_iterations.set(1000.0);
+                                    optimization_max_iterations_selection.set(false)
+                                },
+                            }
+                            optimization_selection.set(true)
+                        },
+                        None => {
+                            optimization_time_limit.set(1000.0);
+                            optimization_seed.set(1.0);
+                            optimization_max_iterations.set(1000.0);
+                            optimization_max_iterations_selection.set(false);
+                            optimization_selection.set(false)
+                        },
+                    }
                 },
                 Err(error) => {
                     config_error_message.set(Some(error.message));
@@ -134,32 +294,73 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
         })
     };
 
+    let show_sheet = move |index: usize| {
+        if let Some(cut_disposition_output) = sheet_output_list.get().get(index) {
+            selected_sheet.set(index);
+            fit_list_to_draw.set(cut_disposition_output.positioned_rectangles_list.clone());
+            showcase_list_to_draw.set(cut_disposition_output.showcase_rectangles_located_list.clone());
+            prohibited_list_to_draw.set(cut_disposition_output.prohibited_area_list.clone());
+            length_used.set(cut_disposition_output.length_used);
+            total_area.set(cut_disposition_output.total_area);
+            used_area.set(cut_disposition_output.used_area);
+            usage.set(cut_disposition_output.usage);
+            is_guillotine.set(cut_disposition_output.is_guillotine);
+            max_length_to_draw.set(cut_disposition_output.max_length);
+            defined_width_to_draw.set(cut_disposition_output.defined_width);
+        }
+    };
+
+    let set_sheet_output_list = move |cut_disposition_output_list: Vec<CutDispositionOutput>| {
+        // the pieces left by one sheet are given to the next, so the last one has the pieces not placed
+        unused_rectangles_list.set(
+            cut_disposition_output_list
+            .last()
+            .map(|cut_disposition_output| cut_disposition_output.unused_rectangles_list.clone())
+            .unwrap_or_default()
+        );
+        unplaced_piece_list.set(
+            cut_disposition_output_list
+            .last()
+            .map(|cut_disposition_output| cut_disposition_output.unplaced_piece_list.clone())
+            .unwrap_or_default()
+        );
+        piece_count_list.set(sum_piece_count_list(&cut_disposition_output_list));
+        showcase_count_list.set(sum_showcase_count_list(&cut_disposition_output_list));
+        sheet_output_list.set(cut_disposition_output_list);
+        draw_error_message.set(None);
+        adjust_violation_list.set(Vec::new());
+        adjust_error_message.set(None);
+        show_sheet(0);
+    };
+
     let get_cut_disposition_output_fn = move || {
         spawn_local_scoped(cx, async move {
+            organizing.set(true);
+            organize_progress.set(None);
+            let organize_progress_to_update = organize_progress.clone();
+            let organize_progress_listener = listen_organize_progress(
+                move |progress| organize_progress_to_update.set(Some(progress))
+            ).await;
             let cut_disposition_output_result = get_cut_disposition_output().await;
+            drop(organize_progress_listener);
+            organizing.set(false);
             match cut_disposition_output_result {
-                Ok(cut_disposition_output) => {
-                    fit_list_to_draw.set(cut_disposition_output.positioned_rectangles_list);
-                    showcase_list_to_draw.set(cut_disposition_output.showcase_rectangles_located_list);
-                    prohibited_list_to_draw.set(cut_disposition_output.prohibited_area_list);
-                    unused_rectangles_list.set(cut_disposition_output.unused_rectangles_list);
-                    length_used.set(cut_disposition_output.length_used as f64);
-                    total_area.set(cut_disposition_output.total_area as f64);
-                    used_area.set(cut_disposition_output.used_area as f64);
-                    usage.set(cut_disposition_output.usage);
-                    draw_error_message.set(None);
-                    max_length_to_draw.set(cut_disposition_output.max_length as f64);
-                    defined_width_to_draw.set(cut_disposition_output.defined_width as f64);
-                },
+                Ok(cut_disposition_output_list) => set_sheet_output_list(cut_disposition_output_list),
                 Err(error) => {
+                    sheet_output_list.set(Vec::new());
+                    selected_sheet.set(0);
                     fit_list_to_draw.set(Vec::new());
                     showcase_list_to_draw.set(Vec::new());
                     prohibited_list_to_draw.set(Vec::new());
                     unused_rectangles_list.set(Vec::new());
+                    unplaced_piece_list.set(Vec::new());
+                    piece_count_list.set(Vec::new());
+                    showcase_count_list.set(Vec::new());
                     length_used.set(0.0);
                     total_area.set(0.0);
                     used_area.set(0.0);
                     usage.set(0.0);
+                    is_guillotine.set(false);
                     draw_error_message.set(Some(error.message));
                     max_length_to_draw.set(0.0);
                     defined_width_to_draw.set(0.0);
@@ -167,8 +368,63 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
             }
         })
     };
+
+    // the layout kept by the backend has the adjustments made by the operator
+    let get_cut_disposition_layout_fn = move || {
+        spawn_local_scoped(cx, async move {
+            match get_cut_disposition_layout().await {
+                Ok(Some(cut_disposition_output_list)) => set_sheet_output_list(cut_disposition_output_list),
+                _ => get_cut_disposition_output_fn(),
+            }
+        })
+    };
     
-    get_cut_disposition_output_fn();
+    get_cut_disposition_layout_fn();
+
+    let apply_adjustment = move |_| {
+        spawn_local_scoped(cx, async move {
+            let piece_index = adjust_piece_index.get().parse::<usize>().unwrap_or(0);
+            let adjustment = match (*adjust_action.get()).as_str() {
+                "1" => LayoutAdjustment::Swap { 
+                    piece_index, 
+                    other_piece_index: adjust_other_piece_index.get().parse::<usize>().unwrap_or(0) 
+                },
+                "2" => LayoutAdjustment::Rotate { piece_index },
+                _ => LayoutAdjustment::Move { 
+                    piece_index, 
+                    top_left_vertex: Vertex { 
+                        pos_x: *adjust_pos_x.get(), 
+                        pos_y: *adjust_pos_y.get() 
+                    } 
+                },
+            };
+            let sheet_index = *selected_sheet.get();
+            match adjust_cut_disposition(sheet_index, adjustment).await {
+                Ok(adjusted_cut_disposition) => {
+                    let mut cut_disposition_output_list = (*sheet_output_list.get()).clone();
+                    if let Some(cut_disposition_output) = cut_disposition_output_list.get_mut(sheet_index) {
+                        *cut_disposition_output = adjusted_cut_disposition.cut_disposition_output;
+                    }
+                    sheet_output_list.set(cut_disposition_output_list);
+                    show_sheet(sheet_index);
+                    adjust_violation_list.set(adjusted_cut_disposition.violation_list);
+                    adjust_error_message.set(None);
+                },
+                Err(error) => adjust_error_message.set(Some(error.message)),
+            }
+        })
+    };
+
+    create_effect(cx, || {
+        if let Some(piece) = adjust_piece_index
+            .get()
+            .parse::<usize>()
+            .ok()
+            .and_then(|piece_index| fit_list_to_draw.get().get(piece_index).cloned()) {
+            adjust_pos_x.set(piece.top_left_vertex.pos_x);
+            adjust_pos_y.set(piece.top_left_vertex.pos_y);
+        }
+    });
 
     let save_config = 
     move || {
@@ -178,15 +434,36 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
 
         spawn_local_scoped(cx, async move {
             let config = ConfigCutDispositionInput {
-                spacing: get_optional_from_boolean_and_value(*spacing_selection.get(), *spacing.get() as i32),
-                max_length: *max_length.get() as i32,
-                defined_length: get_optional_from_boolean_and_value(*defined_length_selection.get(), *defined_length.get() as i32),
-                defined_width: *defined_width.get() as i32,
+                spacing: get_optional_from_boolean_and_value(*spacing_selection.get(), *spacing.get()),
+                max_length: *max_length.get(),
+                defined_length: get_optional_from_boolean_and_value(*defined_length_selection.get(), *defined_length.get()),
+                defined_width: *defined_width.get(),
+                allow_rotation: *allow_rotation.get(),
+                nesting_strategy: get_nesting_strategy_from_option((*nesting_strategy.get()).as_str()),
+                multi_sheet: *multi_sheet.get(),
+                optimization: get_optional_from_boolean_and_value(
+                    *optimization_selection.get(), 
+                    OptimizationConfig {
+                        time_limit: *optimization_time_limit.get() as u32,
+                        seed: *optimization_seed.get() as u32,
+                        max_iterations: get_optional_from_boolean_and_value(
+                            *optimization_max_iterations_selection.get(),
+                            *optimization_max_iterations.get() as u32
+                        ),
+                    }
+                ),
+                fabric_id: (*selected_fabric.get()).parse::<i32>().ok().filter(|id| *id > 0),
+                edge_margins: EdgeMargins {
+                    left: *margin_left.get(),
+                    right: *margin_right.get(),
+                    top: *margin_top.get(),
+                },
             };
             let response = set_config_cut_disposition_input(config).await;
             match response {
                 Ok(_) => {
                     config_error_message.set(None);
+                    get_cut_disposition_input();
                     get_cut_disposition_output_fn();
                 },
                 Err(error) => {
@@ -197,13 +474,77 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
     };
 
 
-    fn get_row_piece_style(piece_id: u32, fit_list: &Vec<PositionedRectangle>, not_fit_list: &Vec<Rectangle>) -> String {
-        if fit_list.is_empty() && not_fit_list.is_empty() {
-            "".to_string()
-        } else if fit_list.iter().any(|item| item.id == piece_id) {
-            "has-text-white has-background-success".to_string()
-        } else {
-            "has-text-white has-background-danger".to_string()
+    fn get_rotation_text(allow_rotation: Option<bool>) -> String {
+        match allow_rotation {
+            Some(true) => "Sim".to_string(),
+            Some(false) => "Não".to_string(),
+            None => "Padrão".to_string(),
+        }
+    }
+
+    fn sum_piece_count_list(cut_disposition_output_list: &[CutDispositionOutput]) -> Vec<PieceCount> {
+        let mut piece_count_list = cut_disposition_output_list
+            .first()
+            .map(|cut_disposition_output| cut_disposition_output.piece_count_list.clone())
+            .unwrap_or_default();
+        for piece_count in piece_count_list.iter_mut() {
+            piece_count.placed_quantity = cut_disposition_output_list
+                .iter()
+                .flat_map(|cut_disposition_output| cut_disposition_output.piece_count_list.iter())
+                .filter(|item| item.id == piece_count.id)
+                .map(|item| item.placed_quantity)
+                .sum();
+            piece_count.unplaced_quantity = piece_count.quantity - piece_count.placed_quantity;
+        }
+        piece_count_list
+    }
+
+    fn sum_showcase_count_list(cut_disposition_output_list: &[CutDispositionOutput]) -> Vec<ShowcaseCount> {
+        let mut showcase_count_list = Vec::<ShowcaseCount>::new();
+        for showcase_count in cut_disposition_output_list.iter().flat_map(|cut_disposition_output| cut_disposition_output.showcase_count_list.iter()) {
+            match showcase_count_list.iter_mut().find(|item| item.id == showcase_count.id) {
+                Some(item) => {
+                    item.placed_quantity += showcase_count.placed_quantity;
+                    item.used_area += showcase_count.used_area;
+                },
+                None => showcase_count_list.push(showcase_count.clone()),
+            }
+        }
+        showcase_count_list
+    }
+
+    fn get_row_piece_style(piece_id: u32, piece_count_list: &[PieceCount]) -> String {
+        match piece_count_list.iter().find(|item| item.id == piece_id) {
+            Some(piece_count) if piece_count.unplaced_quantity == 0 => "has-text-white has-background-success".to_string(),
+            Some(_) => "has-text-white has-background-danger".to_string(),
+            None => "".to_string(),
+        }
+    }
+
+    fn get_placed_quantity_text(piece_id: u32, quantity: u32, piece_count_list: &[PieceCount]) -> String {
+        match piece_count_list.iter().find(|item| item.id == piece_id) {
+            Some(piece_count) => format!("{} de {} posicionadas", piece_count.placed_quantity, piece_count.quantity),
+            None => quantity.to_string(),
+        }
+    }
+
+    fn get_unplaced_reason_text(piece_id: u32, unplaced_piece_list: &[UnplacedPiece], length_unit_symbol: &str) -> String {
+        match unplaced_piece_list.iter().find(|item| item.id == piece_id) {
+            Some(unplaced_piece) => unplaced_piece.get_message(length_unit_symbol),
+            None => "".to_string(),
+        }
+    }
+
+    fn get_placed_showcase_text(showcase_id: u32, max_quantity: Option<u32>, showcase_count_list: &[ShowcaseCount]) -> String {
+        let placed_quantity = showcase_count_list
+            .iter()
+            .find(|item| item.id == showcase_id)
+            .map(|item| item.placed_quantity);
+        match (placed_quantity, max_quantity) {
+            (Some(placed), Some(max)) => format!("{} (máx. {})", placed, max),
+            (Some(placed), None) => placed.to_string(),
+            (None, Some(max)) => format!("máx. {}", max),
+            (None, None) => "".to_string(),
         }
     }
 
@@ -226,7 +567,6 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
     fetch_all_cutting_table();
 
     let fabric_list = create_signal(cx, Vec::<Fabric>::new());
-    let selected_fabric = create_signal(cx, String::from("0"));
 
     let fetch_all_fabric = move || {
         spawn_local_scoped(cx, async move {
@@ -246,17 +586,23 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
         draw_error_message.set(None);
         info_error_message.set(None);
         config_error_message.set(None);
+        import_violation_list.set(Vec::new());
 
         spawn_local_scoped(cx, async move {
             let import_result =
                 import_disposition().await;
 
             match import_result {
-                Ok(_value) => (),
+                Ok(violation_list) => {
+                    if !violation_list.is_empty() {
+                        info_error_message.set(Some("Disposição inválida, o arquivo não foi importado".to_string()));
+                        import_violation_list.set(violation_list);
+                    }
+                },
                 Err(error) => info_error_message.set(Some(error.message))
             };
             get_cut_disposition_input();
-            get_cut_disposition_output_fn();
+            get_cut_disposition_layout_fn();
             selected_cutting_table.set(String::from("0"));
             selected_fabric.set(String::from("0"));
         })
@@ -274,7 +620,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
             if let Some(table) = (*cutting_table_list.get())
             .iter()
             .find(|item| item.id.to_string() == *selected_cutting_table.get()) {
-                max_length.set(table.length as f64);
+                max_length.set(table.length);
             }
             
         }
@@ -290,7 +636,13 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                 .iter()
                 .find(|item| item.id.to_string() == *selected_cutting_table.get()) {
                     if fabric.width <= table.width {
-                        defined_width.set(fabric.width as f64);
+                        defined_width.set(fabric.width);
+                        // a fabric without margins keeps the ones typed for the disposition
+                        if fabric.margin_left > 0.0 || fabric.margin_right > 0.0 || fabric.margin_top > 0.0 {
+                            margin_left.set(fabric.margin_left);
+                            margin_right.set(fabric.margin_right);
+                            margin_top.set(fabric.margin_top);
+                        }
                     } else {
                         selected_fabric.set(String::from("0"));
                         defined_width.set(0.0);
@@ -310,6 +662,52 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                     p(class="level-left") {"Disposição" }
                     button(class="button is-grey level-rigth", on:click=move |_| get_cut_disposition_output_fn()) { "Recarregar" }
                 }
+                (if *organizing.get() {
+                    view!(cx,
+                        div(class="panel-block level") {
+                            p(class="level-left") {
+                                (match organize_progress.get().as_ref() {
+                                    Some(progress) => format!(
+                                        "Folha {}, iteração {}, aproveitamento {:.2}%", 
+                                        progress.sheet, 
+                                        progress.iteration, 
+                                        progress.best_usage * 100.0
+                                    ),
+                                    None => "Organizando...".to_string(),
+                                })
+                            }
+                            button(
+                                class="button is-small is-warning level-rigth", 
+                                on:click=move |_| spawn_local_scoped(cx, async move {
+                                    if let Err(error) = cancel_organize_cut_disposition().await {
+                                        draw_error_message.set(Some(error.message));
+                                    }
+                                })
+                            ) { "Cancelar" }
+                        }
+                    )
+                } else {
+                    view!(cx, )
+                })
+                (if sheet_output_list.get().len() > 1 {
+                    view!(cx,
+                        div(class="panel-block level") {
+                            button(
+                                class="button is-small level-left", 
+                                disabled=*selected_sheet.get() == 0,
+                                on:click=move |_| show_sheet(selected_sheet.get().saturating_sub(1))
+                            ) { "<" }
+                            p(class="level-item") { (format!("Folha {} de {}", *selected_sheet.get() + 1, sheet_output_list.get().len())) }
+                            button(
+                                class="button is-small level-rigth", 
+                                disabled=*selected_sheet.get() + 1 >= sheet_output_list.get().len(),
+                                on:click=move |_| show_sheet(*selected_sheet.get() + 1)
+                            ) { ">" }
+                        }
+                    )
+                } else {
+                    view!(cx, )
+                })
                 div(class="panel-block is-flex") {
                     div(class="columns") {
                         div(class="column field") {
@@ -331,7 +729,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                                 height=(length_used.get().to_string()), 
                                                 x="0", 
                                                 y="0", 
-                                                style="fill:rgb(200,200,200);stroke-width:1;stroke:rgb(0,0,0)"
+                                                style="fill:rgb(200,200,200);stroke-width:1;stroke:rgb(0,0,0)", vector-effect="non-scaling-stroke"
                                             ) {}
                                             Keyed(
                                                 iterable=fit_list_to_draw,
@@ -342,10 +740,10 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                                             height=(item.length.to_string()), 
                                                             x=(item.top_left_vertex.pos_x.to_string()), 
                                                             y=(item.top_left_vertex.pos_y.to_string()), 
-                                                            style="fill:rgb(0,255,0);stroke-width:1;stroke:rgb(0,0,0)") {}
+                                                            style="fill:rgb(0,255,0);stroke-width:1;stroke:rgb(0,0,0)", vector-effect="non-scaling-stroke") {}
                                                     }
                                                 },
-                                                key=|item| (item.id.to_string()),
+                                                key=|item| (item.id, item.top_left_vertex.pos_x.to_bits(), item.top_left_vertex.pos_y.to_bits()),
                                             )
                                             Keyed(
                                                 iterable=prohibited_list_to_draw,
@@ -356,7 +754,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                                             height=(item.length.to_string()), 
                                                             x=(item.top_left_vertex.pos_x.to_string()), 
                                                             y=(item.top_left_vertex.pos_y.to_string()), 
-                                                            style="fill:rgb(0,0,0);stroke-width:1;stroke:rgb(0,0,0)") {}
+                                                            style="fill:rgb(0,0,0);stroke-width:1;stroke:rgb(0,0,0)", vector-effect="non-scaling-stroke") {}
                                                     }
                                                 },
                                                 key=|item| (item.id.to_string()),
@@ -370,11 +768,11 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                                             height=(item.length.to_string()), 
                                                             x=(item.top_left_vertex.pos_x.to_string()), 
                                                             y=(item.top_left_vertex.pos_y.to_string()), 
-                                                            style="fill:rgb(255,255,0);stroke-width:1;stroke:rgb(0,0,0)"
+                                                            style="fill:rgb(255,255,0);stroke-width:1;stroke:rgb(0,0,0)", vector-effect="non-scaling-stroke"
                                                         ) {}
                                                     }
                                                 },
-                                                key=|item| (item.id.to_string()),
+                                                key=|item| (item.id, item.top_left_vertex.pos_x.to_bits(), item.top_left_vertex.pos_y.to_bits()),
                                             )
                                         }
                                     )
@@ -409,7 +807,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                             Keyed(
                                                 iterable=cutting_table_list,
                                                 view=move |cx, item| view! { cx,
-                                                    FabricCutCuttingTableItem(table=item) {}
+                                                    FabricCutCuttingTableItem(table=item, length_unit_symbol=length_unit_symbol.get().as_ref().clone()) {}
                                                 },
                                                 key=|item| item.id,
                                             )
@@ -427,7 +825,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                             Keyed(
                                                 iterable=fabric_list,
                                                 view=move |cx, item| view! { cx,
-                                                    FabricCutFabricItem(fabric=item) {}
+                                                    FabricCutFabricItem(fabric=item, length_unit_symbol=length_unit_symbol.get().as_ref().clone()) {}
                                                 },
                                                 key=|item| item.id,
                                             )
@@ -438,14 +836,14 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                             }
                             div(class="columns") {
                                 div(class="column field") {
-                                    label(class="label") { "Largura definida (mm)"}
+                                    label(class="label") { (format!("Largura definida ({})", length_unit_symbol.get())) }
                                     div (class="control")  {
                                         input(
                                             class="input", 
                                             type="number", 
                                             placeholder="Number input", 
                                             bind:valueAsNumber=defined_width,
-                                            step="1",
+                                            step="any",
                                             pattern="/d+",
                                             min="0",
                                             disabled=(*selected_cutting_table.get()) != "0" 
@@ -453,14 +851,14 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                     }
                                 }
                                 div(class="column field") {
-                                    label(class="label") { "Comprimento máximo (mm)"}
+                                    label(class="label") { (format!("Comprimento máximo ({})", length_unit_symbol.get())) }
                                     div (class="control")  {
                                         input(
                                             class="input", 
                                             type="number", 
                                             placeholder="Number input", 
                                             bind:valueAsNumber=max_length,
-                                            step="1",
+                                            step="any",
                                             pattern="/d+",
                                             min="0",
                                             disabled=(*selected_cutting_table.get()) != "0" 
@@ -470,7 +868,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                             }
                             div(class="columns") {
                                 div(class="column field") {
-                                    label(class="label") { "Espaçamento(mm)" }
+                                    label(class="label") { (format!("Espaçamento ({})", length_unit_symbol.get())) }
                                     div (class="level")  {
                                         div(class="level-left") {
                                             input(
@@ -488,7 +886,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                                     placeholder="Number input", 
                                                     bind:valueAsNumber=spacing, 
                                                     disabled = !*spacing_selection.get(),
-                                                    step="1",
+                                                    step="any",
                                                     pattern="/d+",
                                                     min="0"
                                                 ) {}
@@ -498,7 +896,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                 }
                                 div(class="column field") {
                                     label(class="label") { 
-                                        "Comprimento definido (mm)"
+                                        (format!("Comprimento definido ({})", length_unit_symbol.get()))
                                     }
                                     div (class="level")  {
                                         div(class="level-left") {
@@ -517,7 +915,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                                     placeholder="Number input",
                                                     bind:valueAsNumber=defined_length, 
                                                     disabled = !*defined_length_selection.get(),
-                                                    step="1",
+                                                    step="any",
                                                     pattern="/d+",
                                                     min="0"
                                                 ) {}
@@ -526,6 +924,150 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                     }
                                 }
                             }
+                            div(class="columns") {
+                                div(class="column field") {
+                                    label(class="label") { (format!("Ourela esquerda ({})", length_unit_symbol.get())) }
+                                    div (class="control")  {
+                                        input(class="input", type="number", bind:valueAsNumber=margin_left, step="any", min="0") {}
+                                    }
+                                }
+                                div(class="column field") {
+                                    label(class="label") { (format!("Ourela direita ({})", length_unit_symbol.get())) }
+                                    div (class="control")  {
+                                        input(class="input", type="number", bind:valueAsNumber=margin_right, step="any", min="0") {}
+                                    }
+                                }
+                                div(class="column field") {
+                                    label(class="label") { (format!("Margem inicial ({})", length_unit_symbol.get())) }
+                                    div (class="control")  {
+                                        input(class="input", type="number", bind:valueAsNumber=margin_top, step="any", min="0") {}
+                                    }
+                                }
+                            }
+                            div(class="columns") {
+                                div(class="column field") {
+                                    label(class="label") { "Permitir rotação das peças" }
+                                    div (class="level")  {
+                                        div(class="level-left") {
+                                            input(
+                                                class="toggle",
+                                                type="checkbox",
+                                                on:input=move |_| toggle_allow_rotation(),
+                                                bind:checked=allow_rotation
+                                            )
+                                        }
+                                    }
+                                    (if *fabric_grain.get() != FabricGrain::RotationAllowed {
+                                        view!(cx, p(class="help") { "O fio do tecido não permite girar as peças" })
+                                    } else {
+                                        view!(cx, )
+                                    })
+                                }
+                                div(class="column field") {
+                                    label(class="label") { "Usar folhas adicionais" }
+                                    div (class="level")  {
+                                        div(class="level-left") {
+                                            input(
+                                                class="toggle",
+                                                type="checkbox",
+                                                on:input=move |_| toggle_multi_sheet(),
+                                                bind:checked=multi_sheet
+                                            )
+                                        }
+                                    }
+                                }
+                                div(class="column field") {
+                                    label(class="label") { "Estratégia de disposição" }
+                                    div (class="control")  {
+                                        div (class="select is-fullwidth") {
+                                            select(bind:value=nesting_strategy) {
+                                                option(value="0") { "Vértice superior esquerdo" }
+                                                option(value="1") { "MaxRects (menor sobra no lado curto)" }
+                                                option(value="2") { "Guilhotina (cortes de borda a borda)" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            div(class="columns") {
+                                div(class="column field") {
+                                    label(class="label") { 
+                                        "Tempo de otimização por folha (ms)"
+                                    }
+                                    div (class="level")  {
+                                        div(class="level-left") {
+                                            input(
+                                                class="toggle",
+                                                type="checkbox",
+                                                on:input=move |_| toggle_optimization_selection(),
+                                                bind:checked=optimization_selection,
+                                            )
+                                        }
+                                        div(class="level-item") {
+                                            div (class="control")  {
+                                                input(
+                                                    class="input", 
+                                                    type="number", 
+                                                    placeholder="Number input",
+                                                    bind:valueAsNumber=optimization_time_limit, 
+                                                    disabled = !*optimization_selection.get() || *optimization_max_iterations_selection.get(),
+                                                    step="1",
+                                                    pattern="/d+",
+                                                    min="1"
+                                                ) {}
+                                            }
+                                        }
+                                    }
+                                }
+                                div(class="column field") {
+                                    label(class="label") { 
+                                        "Semente da otimização"
+                                    }
+                                    div (class="control")  {
+                                        input(
+                                            class="input", 
+                                            type="number", 
+                                            placeholder="Number input",
+                                            bind:valueAsNumber=optimization_seed, 
+                                            disabled = !*optimization_selection.get(),
+                                            step="1",
+                                            pattern="/d+",
+                                            min="0"
+                                        ) {}
+                                    }
+                                }
+                                div(class="column field") {
+                                    label(class="label") { 
+                                        "Tentativas por folha"
+                                    }
+                                    div (class="level")  {
+                                        div(class="level-left") {
+                                            input(
+                                                class="toggle",
+                                                type="checkbox",
+                                                on:input=move |_| toggle_optimization_max_iterations_selection(),
+                                                bind:checked=optimization_max_iterations_selection,
+                                                disabled = !*optimization_selection.get(),
+                                            )
+                                        }
+                                        div(class="level-item") {
+                                            div (class="control")  {
+                                                input(
+                                                    class="input", 
+                                                    type="number", 
+                                                    placeholder="Number input",
+                                                    bind:valueAsNumber=optimization_max_iterations, 
+                                                    disabled = !*optimization_selection.get() || !*optimization_max_iterations_selection.get(),
+                                                    step="1",
+                                                    pattern="/d+",
+                                                    min="1"
+                                                ) {}
+                                            }
+                                        }
+                                    }
+                                    p(class="help") { "Substitui o tempo, a mesma semente gera sempre a mesma disposição." }
+                                }
+                            }
                             div(class="column field") {
                                 p(class="has-text-danger") { (config_error_message.get()) }
                             }
@@ -554,40 +1096,48 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                         th(style="5%") { "Id" }
                                         th(style="20%") { "Largura" }
                                         th(style="20%") { "Comprimento" }
+                                        th(style="10%") { "Quantidade" }
                                         th(style="20%") { "Posição X" }
                                         th(style="20%") { "Posição Y" }
+                                        th(style="10%") { "Rotação" }
+                                        th(style="20%") { "Motivo" }
                                         th(style="10%") {
                                             a(class="button is-responsive is-success", href="/piece-item") { "Nova" }
                                         }
                                     }
                                 }
                                 tbody {
-                                    (if let Some(some_showcase) = (*showcase.get()).clone() {
-                                        view!(cx, 
+                                    Keyed(
+                                        iterable=showcase_list,
+                                        view=move |cx, item| view! { cx,
                                             tr(class="has-background-warning") {
-                                                td (style="vertical-align:middle;") { (some_showcase.id.clone()) }
-                                                td (style="vertical-align:middle;") { (some_showcase.width.clone())  }
-                                                td (style="vertical-align:middle;") { (some_showcase.length.clone()) }
+                                                td (style="vertical-align:middle;") { (item.id.clone()) }
+                                                td (style="vertical-align:middle;") { (item.width) }
+                                                td (style="vertical-align:middle;") { (item.length) }
+                                                td (style="vertical-align:middle;") { (get_placed_showcase_text(item.id, item.max_quantity, &showcase_count_list.get())) }
+                                                td (style="vertical-align:middle;") {  }
+                                                td (style="vertical-align:middle;") {  }
                                                 td (style="vertical-align:middle;") {  }
                                                 td (style="vertical-align:middle;") {  }
                                                 td (style="vertical-align:middle;") {
-                                                    a(class="button is-responsive is-info", href=(format!("/edit-piece-item/2/{}", some_showcase.id ))) { "Editar" }
+                                                    a(class="button is-responsive is-info", href=(format!("/edit-piece-item/2/{}", item.id ))) { "Editar" }
                                                 }
                                             }
-                                        )
-                                    } else {
-                                        view!(cx, )
-                                    })
-                                    
+                                        },
+                                        key=|item| item.id,
+                                    )
                                     Keyed(
                                         iterable=piece_list,
                                         view=move |cx, item| view! { cx,
-                                            tr(class=format!("{}", get_row_piece_style(item.id, &fit_list_to_draw.get(), &unused_rectangles_list.get()))) {
+                                            tr(class=format!("{}", get_row_piece_style(item.id, &piece_count_list.get()))) {
                                                 td (style="vertical-align:middle;") { (item.id.clone()) }
-                                                td (style="vertical-align:middle;") { (item.width.clone())  }
-                                                td (style="vertical-align:middle;") { (item.length.clone()) }
+                                                td (style="vertical-align:middle;") { (item.width) }
+                                                td (style="vertical-align:middle;") { (item.length) }
+                                                td (style="vertical-align:middle;") { (get_placed_quantity_text(item.id, item.quantity, &piece_count_list.get())) }
                                                 td (style="vertical-align:middle;") {  }
                                                 td (style="vertical-align:middle;") {  }
+                                                td (style="vertical-align:middle;") { (get_rotation_text(item.allow_rotation)) }
+                                                td (style="vertical-align:middle;") { (get_unplaced_reason_text(item.id, &unplaced_piece_list.get(), &length_unit_symbol.get())) }
                                                 td (style="vertical-align:middle;") {
                                                     a(class="button is-responsive is-info", href=(format!("/edit-piece-item/1/{}", item.id ))) { "Editar" }
                                                 }
@@ -600,10 +1150,13 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                         view=move |cx, item| view! { cx,
                                             tr(class="has-text-white has-background-black") {
                                                 td (style="vertical-align:middle;") { (item.id.clone()) }
-                                                td (style="vertical-align:middle;") { (item.width.clone())  }
-                                                td (style="vertical-align:middle;") { (item.length.clone()) }
-                                                td (style="vertical-align:middle;") { (item.top_left_vertex.pos_x.clone()) }
-                                                td (style="vertical-align:middle;") { (item.top_left_vertex.pos_y.clone()) }
+                                                td (style="vertical-align:middle;") { (item.width) }
+                                                td (style="vertical-align:middle;") { (item.length) }
+                                                td (style="vertical-align:middle;") {  }
+                                                td (style="vertical-align:middle;") { (item.top_left_vertex.pos_x) }
+                                                td (style="vertical-align:middle;") { (item.top_left_vertex.pos_y) }
+                                                td (style="vertical-align:middle;") {  }
+                                                td (style="vertical-align:middle;") {  }
                                                 td (style="vertical-align:middle;") {
                                                     a(class="button is-responsive is-info", href=(format!("/edit-piece-item/3/{}", item.id ))) { "Editar" }
                                                 }
@@ -611,6 +1164,25 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                         },
                                         key=|item| item.id,
                                     )
+                                    Keyed(
+                                        iterable=pinned_piece_list,
+                                        view=move |cx, item| view! { cx,
+                                            tr(class="has-text-white has-background-info") {
+                                                td (style="vertical-align:middle;") { (item.id.clone()) }
+                                                td (style="vertical-align:middle;") { (item.width) }
+                                                td (style="vertical-align:middle;") { (item.length) }
+                                                td (style="vertical-align:middle;") {  }
+                                                td (style="vertical-align:middle;") { (item.top_left_vertex.pos_x) }
+                                                td (style="vertical-align:middle;") { (item.top_left_vertex.pos_y) }
+                                                td (style="vertical-align:middle;") {  }
+                                                td (style="vertical-align:middle;") {  }
+                                                td (style="vertical-align:middle;") {
+                                                    a(class="button is-responsive is-info", href=(format!("/edit-piece-item/4/{}", item.id ))) { "Editar" }
+                                                }
+                                            }
+                                        },
+                                        key=|item| item.id,
+                                    )
                                 }
                             }
                         }
@@ -619,11 +1191,118 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                             span(class="card-footer-item mx-1 has-text-white has-background-danger") { "Sobraram" }
                             span(class="card-footer-item mx-1 has-background-warning") { "Mostruário" }
                             span(class="card-footer-item mx-1 has-text-white has-background-black") { "Área proibida" }
+                            span(class="card-footer-item mx-1 has-text-white has-background-info") { "Peça fixa" }
                         }
                     }
                 }
 
 
+                div(class="card") {
+                    a(on:click=set_adjust_panel_active) {
+                        header(class="card-header has-background-grey-lighter") {
+                            p(class="card-header-title") {
+                                "Ajustes"
+                            }
+                        }
+                    }
+                    div(id="collapsible-card-adjust", class=(match *active_panel.get() {SelectedPanel::Adjust => "", _ => "is-hidden"})) {
+                        div(class="card-content") {
+                            div(class="columns") {
+                                div(class="column field") {
+                                    label(class="label") { "Peça" }
+                                    div (class="control")  {
+                                        div (class="select is-fullwidth") {
+                                            select(bind:value=adjust_piece_index) {
+                                                Keyed(
+                                                    iterable=adjust_piece_option_list,
+                                                    view=|cx, (index, text)| view! { cx,
+                                                        option(value=index) { (text) }
+                                                    },
+                                                    key=|item| item.clone(),
+                                                )
+                                            }
+                                        }
+                                    }
+                                }
+                                div(class="column field") {
+                                    label(class="label") { "Ajuste" }
+                                    div (class="control")  {
+                                        div (class="select is-fullwidth") {
+                                            select(bind:value=adjust_action) {
+                                                option(value="0") { "Mover" }
+                                                option(value="1") { "Trocar de lugar" }
+                                                option(value="2") { "Girar" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            (match (*adjust_action.get()).as_str() {
+                                "0" => view!(cx,
+                                    div(class="columns") {
+                                        div(class="column field") {
+                                            label(class="label") { (format!("Posição X ({})", length_unit_symbol.get())) }
+                                            div (class="control")  {
+                                                input(
+                                                    class="input", 
+                                                    type="number", 
+                                                    placeholder="Number input", 
+                                                    bind:valueAsNumber=adjust_pos_x, 
+                                                    step="any",
+                                                    pattern="/d+",
+                                                    min="0"
+                                                ) {}
+                                            }
+                                        }
+                                        div(class="column field") {
+                                            label(class="label") { (format!("Posição Y ({})", length_unit_symbol.get())) }
+                                            div (class="control")  {
+                                                input(
+                                                    class="input", 
+                                                    type="number", 
+                                                    placeholder="Number input", 
+                                                    bind:valueAsNumber=adjust_pos_y, 
+                                                    step="any",
+                                                    pattern="/d+",
+                                                    min="0"
+                                                ) {}
+                                            }
+                                        }
+                                    }
+                                ),
+                                "1" => view!(cx,
+                                    div(class="field") {
+                                        label(class="label") { "Trocar com" }
+                                        div (class="control")  {
+                                            div (class="select is-fullwidth") {
+                                                select(bind:value=adjust_other_piece_index) {
+                                                    Keyed(
+                                                        iterable=adjust_piece_option_list,
+                                                        view=|cx, (index, text)| view! { cx,
+                                                            option(value=index) { (text) }
+                                                        },
+                                                        key=|item| item.clone(),
+                                                    )
+                                                }
+                                            }
+                                        }
+                                    }
+                                ),
+                                _ => view!(cx, ),
+                            })
+                            div(class="field") {
+                                button(class="button is-success", on:click=apply_adjustment) { "Aplicar" }
+                            }
+                            Indexed(
+                                iterable=adjust_violation_list,
+                                view=move |cx, violation| view! { cx,
+                                    p(class="has-text-danger") { (violation.get_message(&length_unit_symbol.get())) }
+                                },
+                            )
+                            p(class="has-text-danger") { (adjust_error_message.get()) }
+                        }
+                    }
+                }
                 div(class="card") {
                     a(on:click=set_info_panel_active) {
                         header(class="card-header has-background-grey-lighter") {
@@ -661,10 +1340,18 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                                 }
                                 div(class="column field") {
                                     label(class="label") {
-                                        "Comprimento utilizado(mm)"
+                                        (format!("Comprimento utilizado ({})", length_unit_symbol.get()))
                                     }
                                     p {
-                                        (length_used.get())
+                                        (*length_used.get())
+                                    }
+                                }
+                                div(class="column field") {
+                                    label(class="label") {
+                                        "Corte guilhotina"
+                                    }
+                                    p {
+                                        (if *is_guillotine.get() { "Sim" } else { "Não" })
                                     }
                                 }
                             }
@@ -682,6 +1369,12 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
                             div(class="columns") {
                                 div(class="column level") {
                                     p(class="has-text-danger") { (info_error_message.get()) }
+                                    Indexed(
+                                        iterable=import_violation_list,
+                                        view=move |cx, violation| view! { cx,
+                                            p(class="has-text-danger") { (violation.get_message(&length_unit_symbol.get())) }
+                                        },
+                                    )
                                 }
                             }
                         }
@@ -696,6 +1389,7 @@ pub fn FabricCutPage<G: Html>(cx: Scope<'_>) -> View<G> {
 #[derive(Props)]
 pub struct FabricCutCuttingTableItemProps {
     table: CuttingTable,
+    length_unit_symbol: String,
 }
 
 #[component]
@@ -705,11 +1399,14 @@ pub fn FabricCutCuttingTableItem<G: Html>(
 ) -> View<G> {
     let item = create_ref(cx, props.table);
     let id = item.id;
+    let symbol = &props.length_unit_symbol;
     let text = format!(
-        "{} ({}mm x {}mm)",
+        "{} ({}{} x {}{})",
         item.name,
-        item.width.clone(),
-        item.length.clone()
+        item.width,
+        symbol,
+        item.length,
+        symbol
     );
     view! { cx,
         option(value=id) { (text) }
@@ -719,13 +1416,14 @@ pub fn FabricCutCuttingTableItem<G: Html>(
 #[derive(Props)]
 pub struct FabricCutFabricItemProps {
     fabric: Fabric,
+    length_unit_symbol: String,
 }
 
 #[component]
 pub fn FabricCutFabricItem<G: Html>(cx: Scope, props: FabricCutFabricItemProps) -> View<G> {
     let item = create_ref(cx, props.fabric);
     let id = item.id;
-    let text = format!("{} ({}mm)", item.name, item.width.clone());
+    let text = format!("{} ({}{})", item.name, item.width, props.length_unit_symbol);
     view! { cx,
         option(value=id) { (text) }
     }
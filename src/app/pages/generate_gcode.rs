@@ -1,16 +1,36 @@
 use sycamore::{web::Html, reactive::{Scope, create_signal}, component, view::View, futures::spawn_local_scoped};
 use sycamore::prelude::*;
-use sycamore_router::navigate;
 
-use crate::app::services::generate_g_code_service::generate_g_code_file;
+use crate::app::{models::cutting_lines::CutStrategy, services::generate_g_code_service::generate_g_code_file};
+
+fn get_cut_strategy_from_option(option: &str) -> CutStrategy {
+    match option {
+        "1" => CutStrategy::HorizontalFirst,
+        "2" => CutStrategy::ByBand,
+        _ => CutStrategy::VerticalFirst,
+    }
+}
 
 #[component]
 pub fn GenerateGCodePage< G: Html>(cx: Scope<'_>) -> View<G> {
 
     let error_message = create_signal(cx, String::new());
 
+    // where the files were written and how much the head travels without cutting
+    let success_message = create_signal(cx, String::new());
+
     let pull_textile = create_signal(cx, true);
 
+    let one_file_per_sheet = create_signal(cx, false);
+
+    let trim_selvage = create_signal(cx, false);
+
+    let skip_fabric_border_lines = create_signal(cx, false);
+
+    let skip_waste_lines = create_signal(cx, false);
+
+    let cut_strategy = create_signal(cx, "0".to_string());
+
     let name = create_signal(cx, String::new());
 
     let fill_name_with_date = move |_| {
@@ -19,10 +39,16 @@ pub fn GenerateGCodePage< G: Html>(cx: Scope<'_>) -> View<G> {
 
     let generate = move |_| {
         spawn_local_scoped(cx, async move {
-            let response = generate_g_code_file(name.get().as_ref().clone(), *pull_textile.get().as_ref()).await;
+            let response = generate_g_code_file(name.get().as_ref().clone(), *pull_textile.get().as_ref(), *one_file_per_sheet.get().as_ref(), *trim_selvage.get().as_ref(), *skip_fabric_border_lines.get().as_ref(), *skip_waste_lines.get().as_ref(), get_cut_strategy_from_option(cut_strategy.get().as_str())).await;
             match response {
-                Ok(_) => navigate("/fabric-cut"),
-                Err(e) => error_message.set(e.message),
+                Ok(message) => {
+                    error_message.set(String::new());
+                    success_message.set(message);
+                },
+                Err(e) => {
+                    success_message.set(String::new());
+                    error_message.set(e.message);
+                },
             }
         })
     };
@@ -34,7 +60,9 @@ pub fn GenerateGCodePage< G: Html>(cx: Scope<'_>) -> View<G> {
                 header(class="modal-card-head") {
                     p(class="modal-card-title level-left") { "Gerador de Código G" } 
                     div(class="level-item level-right") {
-                        a(class="button is-medium is-warning", href="/fabric-cut") { "Cancelar" }
+                        a(class="button is-medium is-warning", href="/fabric-cut") { 
+                            (if success_message.get().is_empty() { "Cancelar" } else { "Voltar" }) 
+                        }
                     }
                 }
                 section(class="modal-card-body") {
@@ -61,10 +89,63 @@ pub fn GenerateGCodePage< G: Html>(cx: Scope<'_>) -> View<G> {
                             " Puxar tecido"
                         }
                     }
+                    div(class="field") {
+                        label(class="checkbox") { 
+                            input(
+                                class="toggle",
+                                type="checkbox",
+                                bind:checked=one_file_per_sheet,
+                            )
+                            " Um arquivo por folha"
+                        }
+                    }
+                    div(class="field") {
+                        label(class="checkbox") { 
+                            input(
+                                class="toggle",
+                                type="checkbox",
+                                bind:checked=trim_selvage,
+                            )
+                            " Aparar ourela"
+                        }
+                    }
+                    div(class="field") {
+                        label(class="checkbox") { 
+                            input(
+                                class="toggle",
+                                type="checkbox",
+                                bind:checked=skip_fabric_border_lines,
+                            )
+                            " Não cortar as bordas do tecido"
+                        }
+                    }
+                    div(class="field") {
+                        label(class="checkbox") { 
+                            input(
+                                class="toggle",
+                                type="checkbox",
+                                bind:checked=skip_waste_lines,
+                            )
+                            " Não cortar entre sobras"
+                        }
+                    }
+                    div(class="field") {
+                        label(class="label") { "Ordem dos cortes" }
+                        div(class="control") {
+                            div(class="select") {
+                                select(bind:value=cut_strategy) {
+                                    option(value="0") { "Verticais primeiro" }
+                                    option(value="1") { "Horizontais primeiro" }
+                                    option(value="2") { "Por faixa, de cima para baixo" }
+                                }
+                            }
+                        }
+                    }
                     
 
                     div {
                         p(class="has-text-danger") { (error_message.get()) }
+                        p(class="has-text-success", style="white-space:pre-line;") { (success_message.get()) }
                     }
                 }
                 footer(class="modal-card-foot") {
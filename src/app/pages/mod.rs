@@ -6,3 +6,4 @@ pub mod not_found;
 pub mod piece;
 pub mod generate_gcode;
 pub mod export_disposition;
+pub mod setting;
@@ -4,13 +4,14 @@ use serde::{Serialize, Deserialize};
 use sycamore::{prelude::*, futures::spawn_local_scoped};
 use sycamore_router::navigate;
 
-use crate::app::{models::{piece::RectangleType, cut_disposition::{Rectangle, PositionedRectangle, Vertex}, app_error::AppError}, services::cut_disposition_service::{create_piece, get_piece_by_id, get_showcase, get_prohibited_area_by_id, edit_piece, remove_prohibited_area_by_id, remove_piece, remove_showcase}, log};
+use crate::app::{utils::utils::get_optional_from_boolean_and_value, models::{piece::RectangleType, cut_disposition::{Rectangle, PositionedRectangle, Vertex}, app_error::AppError}, services::{cut_disposition_service::{create_piece, get_piece_by_id, get_showcase_by_id, get_prohibited_area_by_id, get_pinned_piece_by_id, edit_piece, remove_prohibited_area_by_id, remove_pinned_piece_by_id, remove_piece, remove_showcase}, setting_service::get_length_unit_symbol}, log};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum PieceType {
     Piece,
     Showcase,
-    ProhibitedArea
+    ProhibitedArea,
+    PinnedPiece
 }
 impl Display for PieceType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -18,6 +19,7 @@ impl Display for PieceType {
             PieceType::Piece => write!(f, "Peça"),
             PieceType::Showcase => write!(f, "Mostruário"),
             PieceType::ProhibitedArea => write!(f, "Área Proibida"),
+            PieceType::PinnedPiece => write!(f, "Peça Fixa"),
         }
     }
 }
@@ -29,6 +31,22 @@ pub struct PieceOption {
     piece_type: PieceType
 }
 
+fn get_allow_rotation_from_option(option: &str) -> Option<bool> {
+    match option {
+        "1" => Some(true),
+        "2" => Some(false),
+        _ => None,
+    }
+}
+
+fn get_option_from_allow_rotation(allow_rotation: Option<bool>) -> String {
+    match allow_rotation {
+        Some(true) => "1".to_string(),
+        Some(false) => "2".to_string(),
+        None => "0".to_string(),
+    }
+}
+
 #[component]
 pub fn PieceItemPage< G: Html>(cx: Scope<'_>) -> View<G> {
     let id = create_signal(cx, 0.0);
@@ -36,11 +54,32 @@ pub fn PieceItemPage< G: Html>(cx: Scope<'_>) -> View<G> {
     let length = create_signal(cx, 0.0);
     let pos_x = create_signal(cx, 0.0);
     let pos_y = create_signal(cx, 0.0);
+    let allow_rotation = create_signal(cx, "0".to_string());
+    let match_pattern = create_signal(cx, false);
+    let toggle_match_pattern = || {
+        match_pattern.set(!*match_pattern.get())
+    };
+    let margin = create_signal(cx, 0.0);
+    let margin_selection = create_signal(cx, false);
+    let toggle_margin_selection = || {
+        margin_selection.set(!*margin_selection.get())
+    };
+    let quantity = create_signal(cx, 1.0);
+    let max_quantity = create_signal(cx, 0.0);
+    let max_quantity_selection = create_signal(cx, false);
+    let toggle_max_quantity_selection = || {
+        max_quantity_selection.set(!*max_quantity_selection.get())
+    };
 
     let piece_type = create_signal(cx, "1".to_string());
 
     let error_message = create_signal(cx, String::new());
 
+    let length_unit_symbol = create_signal(cx, String::new());
+    spawn_local_scoped(cx, async move {
+        length_unit_symbol.set(get_length_unit_symbol().await);
+    });
+
     let piece_options: Vec<PieceOption>  = vec![
         PieceOption { 
             id: "1".to_string(), 
@@ -57,6 +96,11 @@ pub fn PieceItemPage< G: Html>(cx: Scope<'_>) -> View<G> {
             text: "Área Proibida".to_string(), 
             piece_type: PieceType::ProhibitedArea 
         },
+        PieceOption { 
+            id: "4".to_string(), 
+            text: "Peça Fixa".to_string(), 
+            piece_type: PieceType::PinnedPiece 
+        },
     ];
 
     let piece_options_list = create_signal(cx, piece_options);
@@ -64,10 +108,15 @@ pub fn PieceItemPage< G: Html>(cx: Scope<'_>) -> View<G> {
     let save_item =  move |_| {
         spawn_local_scoped(cx, async move {
             let param_id = *id.get().as_ref() as u32;
-            let param_width = *width.get().as_ref() as i32;
-            let param_length = *length.get().as_ref() as i32;
-            let param_pos_x = *pos_x.get().as_ref() as i32;
-            let param_pos_y = *pos_y.get().as_ref() as i32;
+            let param_width = *width.get();
+            let param_length = *length.get();
+            let param_pos_x = *pos_x.get();
+            let param_pos_y = *pos_y.get();
+            let param_allow_rotation = get_allow_rotation_from_option((*allow_rotation.get()).as_str());
+            let param_match_pattern = *match_pattern.get();
+            let param_margin = get_optional_from_boolean_and_value(*margin_selection.get(), *margin.get());
+            let param_quantity = *quantity.get().as_ref() as u32;
+            let param_max_quantity = get_optional_from_boolean_and_value(*max_quantity_selection.get(), *max_quantity.get() as u32);
 
             log((*piece_type.get()).as_str());
 
@@ -79,6 +128,11 @@ pub fn PieceItemPage< G: Html>(cx: Scope<'_>) -> View<G> {
                                 id: param_id,
                                 width: param_width,
                                 length: param_length,
+                                allow_rotation: param_allow_rotation,
+                                quantity: param_quantity,
+                                max_quantity: None,
+                                match_pattern: param_match_pattern,
+                                margin: param_margin,
                             }
                         )
                     ).await
@@ -90,6 +144,11 @@ pub fn PieceItemPage< G: Html>(cx: Scope<'_>) -> View<G> {
                                 id: param_id,
                                 width: param_width,
                                 length: param_length,
+                                allow_rotation: None,
+                                quantity: 1,
+                                max_quantity: param_max_quantity,
+                                match_pattern: false,
+                                margin: None,
                             }
                         )
                     ).await
@@ -104,7 +163,26 @@ pub fn PieceItemPage< G: Html>(cx: Scope<'_>) -> View<G> {
                                 top_left_vertex: Vertex {
                                     pos_x: param_pos_x,
                                     pos_y: param_pos_y,
-                                }
+                                },
+                                rotated: false,
+                                margin: None,
+                            }
+                        )
+                    ).await
+                },
+                "4" => {
+                    create_piece(
+                        RectangleType::PinnedPiece(
+                            PositionedRectangle {
+                                id: param_id,
+                                width: param_width,
+                                length: param_length,
+                                top_left_vertex: Vertex {
+                                    pos_x: param_pos_x,
+                                    pos_y: param_pos_y,
+                                },
+                                rotated: false,
+                                margin: param_margin,
                             }
                         )
                     ).await
@@ -139,58 +217,170 @@ pub fn PieceItemPage< G: Html>(cx: Scope<'_>) -> View<G> {
                 }
                 section(class="modal-card-body") {
                     div(class="field") {
-                        label(class="label") { "Largura (mm)" }
+                        label(class="label") { (format!("Largura ({})", length_unit_symbol.get())) }
                         div (class="control")  {
                             input(
                                 class="input", 
                                 type="number", 
                                 placeholder="Number input", 
                                 bind:valueAsNumber=width, 
-                                step="1",
+                                step="any",
                                 pattern="/d+",
                                 min="0"
                             ) {}
                         }
                     }
                     div(class="field") {
-                        label(class="label") { "Comprimento (mm)" }
+                        label(class="label") { (format!("Comprimento ({})", length_unit_symbol.get())) }
                         div (class="control")  {
                             input(
                                 class="input", 
                                 type="number", 
                                 placeholder="Number input", 
                                 bind:valueAsNumber=length, 
-                                step="1",
+                                step="any",
                                 pattern="/d+",
                                 min="0"
                             ) {}
                         }
                     }
-                    (if (*piece_type.get()).as_str() == "3" {
+                    (if (*piece_type.get()).as_str() == "1" {
                         view!(cx, 
                             div(class="field") {
-                                label(class="label") { "Posição X (mm)" }
+                                label(class="label") { "Quantidade" }
                                 div (class="control")  {
                                     input(
                                         class="input", 
                                         type="number", 
                                         placeholder="Number input", 
-                                        bind:valueAsNumber=pos_x, 
+                                        bind:valueAsNumber=quantity, 
                                         step="1",
                                         pattern="/d+",
+                                        min="1"
+                                    ) {}
+                                }
+                            }
+                            div(class="field") {
+                                label(class="label") { "Rotação" }
+                                div (class="control")  {
+                                    div (class="select is-fullwidth") {
+                                        select(bind:value=allow_rotation) {
+                                            option(value="0") { "Padrão da disposição" }
+                                            option(value="1") { "Permitir" }
+                                            option(value="2") { "Não permitir" }
+                                        }
+                                    }
+                                }
+                            }
+                            div(class="field") {
+                                label(class="label") { "Alinhar com a estampa" }
+                                div (class="level")  {
+                                    div(class="level-left") {
+                                        input(
+                                            class="toggle",
+                                            type="checkbox",
+                                            on:input=move |_| toggle_match_pattern(),
+                                            bind:checked=match_pattern
+                                        )
+                                    }
+                                }
+                            }
+                        )
+                    } else {
+                        view!(cx, )
+                    })
+                    (if (*piece_type.get()).as_str() == "1" || (*piece_type.get()).as_str() == "4" {
+                        view!(cx, 
+                            div(class="field") {
+                                label(class="label") { (format!("Margem própria ({})", length_unit_symbol.get())) }
+                                div (class="level")  {
+                                    div(class="level-left") {
+                                        input(
+                                            class="toggle",
+                                            type="checkbox",
+                                            on:input=move |_| toggle_margin_selection(),
+                                            bind:checked=margin_selection
+                                        )
+                                    }
+                                    div(class="level-item") {
+                                        div (class="control")  {
+                                            input(
+                                                class="input", 
+                                                type="number", 
+                                                placeholder="Number input", 
+                                                bind:valueAsNumber=margin, 
+                                                disabled = !*margin_selection.get(),
+                                                step="any",
+                                                pattern="/d+",
+                                                min="0"
+                                            ) {}
+                                        }
+                                    }
+                                }
+                                p(class="help") { "Substitui o espaçamento da disposição, zero permite encostar nas vizinhas" }
+                            }
+                        )
+                    } else {
+                        view!(cx, )
+                    })
+                    (if (*piece_type.get()).as_str() == "2" {
+                        view!(cx, 
+                            div(class="field") {
+                                label(class="label") { "Quantidade máxima" }
+                                div (class="level")  {
+                                    div(class="level-left") {
+                                        input(
+                                            class="toggle",
+                                            type="checkbox",
+                                            on:input=move |_| toggle_max_quantity_selection(),
+                                            bind:checked=max_quantity_selection
+                                        )
+                                    }
+                                    div(class="level-item") {
+                                        div (class="control")  {
+                                            input(
+                                                class="input", 
+                                                type="number", 
+                                                placeholder="Number input", 
+                                                bind:valueAsNumber=max_quantity, 
+                                                disabled = !*max_quantity_selection.get(),
+                                                step="1",
+                                                pattern="/d+",
+                                                min="1"
+                                            ) {}
+                                        }
+                                    }
+                                }
+                            }
+                        )
+                    } else {
+                        view!(cx, )
+                    })
+                    (if (*piece_type.get()).as_str() == "3" || (*piece_type.get()).as_str() == "4" {
+                        view!(cx, 
+                            div(class="field") {
+                                label(class="label") { (format!("Posição X ({})", length_unit_symbol.get())) }
+                                div (class="control")  {
+                                    input(
+                                        class="input", 
+                                        type="number", 
+                                        placeholder="Number input", 
+                                        bind:valueAsNumber=pos_x, 
+                                        step="any",
+                                        pattern="/d+",
                                         min="0"
                                     ) {}
                                 }
                             }
                             div(class="field") {
-                                label(class="label") { "Posição Y (mm)" }
+                                label(class="label") { (format!("Posição Y ({})", length_unit_symbol.get())) }
                                 div (class="control")  {
                                     input(
                                         class="input", 
                                         type="number", 
                                         placeholder="Number input", 
                                         bind:valueAsNumber=pos_y, 
-                                        step="1",
+                                        step="any",
                                         pattern="/d+",
                                         min="0"
                                     ) {}
@@ -247,11 +437,29 @@ pub fn EditPieceItemPage< G: Html>(cx: Scope<'_>, props: EditPieceItemProps) ->
     let length = create_signal(cx, 0.0);
     let pos_x = create_signal(cx, 0.0);
     let pos_y = create_signal(cx, 0.0);
+    let allow_rotation = create_signal(cx, "0".to_string());
+    let match_pattern = create_signal(cx, false);
+    let toggle_match_pattern = || {
+        match_pattern.set(!*match_pattern.get())
+    };
+    let margin = create_signal(cx, 0.0);
+    let margin_selection = create_signal(cx, false);
+    let toggle_margin_selection = || {
+        margin_selection.set(!*margin_selection.get())
+    };
+    let quantity = create_signal(cx, 1.0);
+    let max_quantity = create_signal(cx, 0.0);
+    let max_quantity_selection = create_signal(cx, false);
+    let toggle_max_quantity_selection = || {
+        max_quantity_selection.set(!*max_quantity_selection.get())
+    };
 
     let piece_type = create_signal(cx, props.piece_type_id.to_string());
 
     let error_message = create_signal(cx, String::new());
 
+    let length_unit_symbol = create_signal(cx, String::new());
+
     let piece_options: Vec<PieceOption>  = vec![
         PieceOption { 
             id: "1".to_string(), 
@@ -268,6 +476,11 @@ pub fn EditPieceItemPage< G: Html>(cx: Scope<'_>, props: EditPieceItemProps) ->
             text: "Área Proibida".to_string(), 
             piece_type: PieceType::ProhibitedArea 
         },
+        PieceOption { 
+            id: "4".to_string(), 
+            text: "Peça Fixa".to_string(), 
+            piece_type: PieceType::PinnedPiece 
+        },
     ];
 
     let piece_options_list = create_signal(cx, piece_options);
@@ -275,14 +488,20 @@ pub fn EditPieceItemPage< G: Html>(cx: Scope<'_>, props: EditPieceItemProps) ->
     
     let get_data = move || {
         spawn_local_scoped(cx, async move {
+            length_unit_symbol.set(get_length_unit_symbol().await);
             match (*piece_type.get()).as_str() {
                 "1" => {
                     let item = get_piece_by_id(props.id).await;
                     match item {
                         Ok(piece) => {
                             id.set(piece.id as f64);
-                            width.set(piece.width as f64);
-                            length.set(piece.length as f64);
+                            width.set(piece.width);
+                            length.set(piece.length);
+                            allow_rotation.set(get_option_from_allow_rotation(piece.allow_rotation));
+                            match_pattern.set(piece.match_pattern);
+                            margin.set(piece.margin.unwrap_or(0.0));
+                            margin_selection.set(piece.margin.is_some());
+                            quantity.set(piece.quantity as f64);
                         },
                         Err(error) => {
                             error_message.set(error.message);
@@ -290,18 +509,21 @@ pub fn EditPieceItemPage< G: Html>(cx: Scope<'_>, props: EditPieceItemProps) ->
                     }
                 },
                 "2" => {
-                    let item = get_showcase().await;
+                    let item = get_showcase_by_id(props.id).await;
                     match item {
-                        Ok(piece_option) => {
-                            match piece_option {
-                                Some(piece) => {
-                                    id.set(piece.id as f64);
-                                    width.set(piece.width as f64);
-                                    length.set(piece.length as f64);
+                        Ok(piece) => {
+                            id.set(piece.id as f64);
+                            width.set(piece.width);
+                            length.set(piece.length);
+                            match piece.max_quantity {
+                                Some(value) => {
+                                    max_quantity.set(value as f64);
+                                    max_quantity_selection.set(true)
                                 },
                                 None => {
-                                    error_message.set("Mostruário não existe".to_owned());
-                                }
+                                    max_quantity.set(0.0);
+                                    max_quantity_selection.set(false)
+                                },
                             }
                         },
                         Err(error) => {
@@ -314,10 +536,27 @@ pub fn EditPieceItemPage< G: Html>(cx: Scope<'_>, props: EditPieceItemProps) ->
                     match item {
                         Ok(piece) => {
                             id.set(piece.id as f64);
-                            width.set(piece.width as f64);
-                            length.set(piece.length as f64);
-                            pos_x.set(piece.top_left_vertex.pos_x as f64);
-                            pos_y.set(piece.top_left_vertex.pos_y as f64);
+                            width.set(piece.width);
+                            length.set(piece.length);
+                            pos_x.set(piece.top_left_vertex.pos_x);
+                            pos_y.set(piece.top_left_vertex.pos_y);
+                        },
+                        Err(error) => {
+                            error_message.set(error.message);
+                        },
+                    }
+                },
+                "4" => {
+                    let item: Result<PositionedRectangle, AppError> = get_pinned_piece_by_id(props.id).await;
+                    match item {
+                        Ok(piece) => {
+                            id.set(piece.id as f64);
+                            width.set(piece.width);
+                            length.set(piece.length);
+                            pos_x.set(piece.top_left_vertex.pos_x);
+                            pos_y.set(piece.top_left_vertex.pos_y);
+                            margin.set(piece.margin.unwrap_or(0.0));
+                            margin_selection.set(piece.margin.is_some());
                         },
                         Err(error) => {
                             error_message.set(error.message);
@@ -336,10 +575,15 @@ pub fn EditPieceItemPage< G: Html>(cx: Scope<'_>, props: EditPieceItemProps) ->
     let save_item =  move |_| {
         spawn_local_scoped(cx, async move {
             let param_id = *id.get().as_ref() as u32;
-            let param_width = *width.get().as_ref() as i32;
-            let param_length = *length.get().as_ref() as i32;
-            let param_pos_x = *pos_x.get().as_ref() as i32;
-            let param_pos_y = *pos_y.get().as_ref() as i32;
+            let param_width = *width.get();
+            let param_length = *length.get();
+            let param_pos_x = *pos_x.get();
+            let param_pos_y = *pos_y.get();
+            let param_allow_rotation = get_allow_rotation_from_option((*allow_rotation.get()).as_str());
+            let param_match_pattern = *match_pattern.get();
+            let param_margin = get_optional_from_boolean_and_value(*margin_selection.get(), *margin.get());
+            let param_quantity = *quantity.get().as_ref() as u32;
+            let param_max_quantity = get_optional_from_boolean_and_value(*max_quantity_selection.get(), *max_quantity.get() as u32);
 
             log((*piece_type.get()).as_str());
 
@@ -351,6 +595,11 @@ pub fn EditPieceItemPage< G: Html>(cx: Scope<'_>, props: EditPieceItemProps) ->
                                 id: param_id,
                                 width: param_width,
                                 length: param_length,
+                                allow_rotation: param_allow_rotation,
+                                quantity: param_quantity,
+                                max_quantity: None,
+                                match_pattern: param_match_pattern,
+                                margin: param_margin,
                             }
                         )
                     ).await
@@ -362,6 +611,11 @@ pub fn EditPieceItemPage< G: Html>(cx: Scope<'_>, props: EditPieceItemProps) ->
                                 id: param_id,
                                 width: param_width,
                                 length: param_length,
+                                allow_rotation: None,
+                                quantity: 1,
+                                max_quantity: param_max_quantity,
+                                match_pattern: false,
+                                margin: None,
                             }
                         )
                     ).await
@@ -376,7 +630,26 @@ pub fn EditPieceItemPage< G: Html>(cx: Scope<'_>, props: EditPieceItemProps) ->
                                 top_left_vertex: Vertex {
                                     pos_x: param_pos_x,
                                     pos_y: param_pos_y,
-                                }
+                                },
+                                rotated: false,
+                                margin: None,
+                            }
+                        )
+                    ).await
+                },
+                "4" => {
+                    edit_piece(
+                        RectangleType::PinnedPiece(
+                            PositionedRectangle {
+                                id: param_id,
+                                width: param_width,
+                                length: param_length,
+                                top_left_vertex: Vertex {
+                                    pos_x: param_pos_x,
+                                    pos_y: param_pos_y,
+                                },
+                                rotated: false,
+                                margin: param_margin,
                             }
                         )
                     ).await
@@ -415,6 +688,9 @@ pub fn EditPieceItemPage< G: Html>(cx: Scope<'_>, props: EditPieceItemProps) ->
                 "3" => {
                     remove_prohibited_area_by_id(param_id).await
                 },
+                "4" => {
+                    remove_pinned_piece_by_id(param_id).await
+                },
                 _ => {
                     Err(AppError {
                         status:1,
@@ -449,58 +725,170 @@ pub fn EditPieceItemPage< G: Html>(cx: Scope<'_>, props: EditPieceItemProps) ->
                         label(class="label") { (id.get()) }
                     }
                     div(class="field") {
-                        label(class="label") { "Largura (mm)" }
+                        label(class="label") { (format!("Largura ({})", length_unit_symbol.get())) }
                         div (class="control")  {
                             input(
                                 class="input", 
                                 type="number", 
                                 placeholder="Number input", 
                                 bind:valueAsNumber=width, 
-                                step="1",
+                                step="any",
                                 pattern="/d+",
                                 min="0"
                             ) {}
                         }
                     }
                     div(class="field") {
-                        label(class="label") { "Comprimento (mm)" }
+                        label(class="label") { (format!("Comprimento ({})", length_unit_symbol.get())) }
                         div (class="control")  {
                             input(
                                 class="input", 
                                 type="number", 
                                 placeholder="Number input", 
                                 bind:valueAsNumber=length, 
-                                step="1",
+                                step="any",
                                 pattern="/d+",
                                 min="0"
                             ) {}
                         }
                     }
-                    (if (*piece_type.get()).as_str() == "3" {
+                    (if (*piece_type.get()).as_str() == "1" {
                         view!(cx, 
                             div(class="field") {
-                                label(class="label") { "Posição X (mm)" }
+                                label(class="label") { "Quantidade" }
                                 div (class="control")  {
                                     input(
                                         class="input", 
                                         type="number", 
                                         placeholder="Number input", 
-                                        bind:valueAsNumber=pos_x, 
+                                        bind:valueAsNumber=quantity, 
                                         step="1",
                                         pattern="/d+",
+                                        min="1"
+                                    ) {}
+                                }
+                            }
+                            div(class="field") {
+                                label(class="label") { "Rotação" }
+                                div (class="control")  {
+                                    div (class="select is-fullwidth") {
+                                        select(bind:value=allow_rotation) {
+                                            option(value="0") { "Padrão da disposição" }
+                                            option(value="1") { "Permitir" }
+                                            option(value="2") { "Não permitir" }
+                                        }
+                                    }
+                                }
+                            }
+                            div(class="field") {
+                                label(class="label") { "Alinhar com a estampa" }
+                                div (class="level")  {
+                                    div(class="level-left") {
+                                        input(
+                                            class="toggle",
+                                            type="checkbox",
+                                            on:input=move |_| toggle_match_pattern(),
+                                            bind:checked=match_pattern
+                                        )
+                                    }
+                                }
+                            }
+                        )
+                    } else {
+                        view!(cx, )
+                    })
+                    (if (*piece_type.get()).as_str() == "1" || (*piece_type.get()).as_str() == "4" {
+                        view!(cx, 
+                            div(class="field") {
+                                label(class="label") { (format!("Margem própria ({})", length_unit_symbol.get())) }
+                                div (class="level")  {
+                                    div(class="level-left") {
+                                        input(
+                                            class="toggle",
+                                            type="checkbox",
+                                            on:input=move |_| toggle_margin_selection(),
+                                            bind:checked=margin_selection
+                                        )
+                                    }
+                                    div(class="level-item") {
+                                        div (class="control")  {
+                                            input(
+                                                class="input", 
+                                                type="number", 
+                                                placeholder="Number input", 
+                                                bind:valueAsNumber=margin, 
+                                                disabled = !*margin_selection.get(),
+                                                step="any",
+                                                pattern="/d+",
+                                                min="0"
+                                            ) {}
+                                        }
+                                    }
+                                }
+                                p(class="help") { "Substitui o espaçamento da disposição, zero permite encostar nas vizinhas" }
+                            }
+                        )
+                    } else {
+                        view!(cx, )
+                    })
+                    (if (*piece_type.get()).as_str() == "2" {
+                        view!(cx, 
+                            div(class="field") {
+                                label(class="label") { "Quantidade máxima" }
+                                div (class="level")  {
+                                    div(class="level-left") {
+                                        input(
+                                            class="toggle",
+                                            type="checkbox",
+                                            on:input=move |_| toggle_max_quantity_selection(),
+                                            bind:checked=max_quantity_selection
+                                        )
+                                    }
+                                    div(class="level-item") {
+                                        div (class="control")  {
+                                            input(
+                                                class="input", 
+                                                type="number", 
+                                                placeholder="Number input", 
+                                                bind:valueAsNumber=max_quantity, 
+                                                disabled = !*max_quantity_selection.get(),
+                                                step="1",
+                                                pattern="/d+",
+                                                min="1"
+                                            ) {}
+                                        }
+                                    }
+                                }
+                            }
+                        )
+                    } else {
+                        view!(cx, )
+                    })
+                    (if (*piece_type.get()).as_str() == "3" || (*piece_type.get()).as_str() == "4" {
+                        view!(cx, 
+                            div(class="field") {
+                                label(class="label") { (format!("Posição X ({})", length_unit_symbol.get())) }
+                                div (class="control")  {
+                                    input(
+                                        class="input", 
+                                        type="number", 
+                                        placeholder="Number input", 
+                                        bind:valueAsNumber=pos_x, 
+                                        step="any",
+                                        pattern="/d+",
                                         min="0"
                                     ) {}
                                 }
                             }
                             div(class="field") {
-                                label(class="label") { "Posição Y (mm)" }
+                                label(class="label") { (format!("Posição Y ({})", length_unit_symbol.get())) }
                                 div (class="control")  {
                                     input(
                                         class="input", 
                                         type="number", 
                                         placeholder="Number input", 
                                         bind:valueAsNumber=pos_y, 
-                                        step="1",
+                                        step="any",
                                         pattern="/d+",
                                         min="0"
                                     ) {}
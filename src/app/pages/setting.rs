@@ -0,0 +1,91 @@
+use sycamore::{component, futures::spawn_local_scoped, prelude::*};
+
+use crate::app::{models::setting::Setting, services::setting_service::{get_setting, update_setting}};
+
+#[component]
+pub fn SettingPage<G: Html>(cx: Scope<'_>) -> View<G> {
+    let length_unit = create_signal(cx, String::from("Millimeter"));
+    let g_code_decimal_places = create_signal(cx, 2.0);
+    let overcut = create_signal(cx, 0.0);
+    let lead_in = create_signal(cx, 0.0);
+    let error_message = create_signal(cx, String::new());
+    let success_message = create_signal(cx, String::new());
+
+    spawn_local_scoped(cx, async move {
+        match get_setting().await {
+            Ok(setting) => {
+                length_unit.set(setting.length_unit);
+                g_code_decimal_places.set(setting.g_code_decimal_places as f64);
+                overcut.set(setting.overcut);
+                lead_in.set(setting.lead_in);
+            },
+            Err(e) => error_message.set(e.message),
+        }
+    });
+
+    let save_setting = move |_| {
+        spawn_local_scoped(cx, async move {
+            let setting = Setting {
+                length_unit: length_unit.get().as_ref().clone(),
+                g_code_decimal_places: *g_code_decimal_places.get() as u32,
+                overcut: *overcut.get(),
+                lead_in: *lead_in.get(),
+            };
+            match update_setting(setting).await {
+                Ok(_) => {
+                    error_message.set(String::new());
+                    success_message.set("Configurações salvas".to_string());
+                },
+                Err(e) => {
+                    success_message.set(String::new());
+                    error_message.set(e.message);
+                },
+            }
+        })
+    };
+
+    view! { cx,
+        div(class="container") {
+            h1 (class="title is-2") { "Configurações" }
+            div(class="field") {
+                label(class="label") { "Unidade de medida" }
+                div(class="control") {
+                    div(class="select") {
+                        select(bind:value=length_unit) {
+                            option(value="Millimeter") { "Milímetros (mm)" }
+                            option(value="Centimeter") { "Centímetros (cm)" }
+                            option(value="Inch") { "Polegadas (pol)" }
+                        }
+                    }
+                }
+                p(class="help") { "As medidas são sempre salvas em milímetros. Em polegadas o código G é gerado com G20, nas outras unidades com G21. O sobrecorte e a entrada da lâmina são lidos na unidade escolhida." }
+            }
+            div(class="field") {
+                label(class="label") { "Casas decimais do código G" }
+                div(class="control") {
+                    input(class="input", type="number", bind:valueAsNumber=g_code_decimal_places, step="1", min="0", max="4") {}
+                }
+                p(class="help") { "As peças são organizadas com precisão de centésimos de milímetro." }
+            }
+            div(class="field") {
+                label(class="label") { "Sobrecorte" }
+                div(class="control") {
+                    input(class="input", type="number", bind:valueAsNumber=overcut, step="any", min="0") {}
+                }
+                p(class="help") { "O corte continua além das duas pontas de cada linha, sem entrar nas peças, nas áreas proibidas ou fora da folha." }
+            }
+            div(class="field") {
+                label(class="label") { "Entrada da lâmina" }
+                div(class="control") {
+                    input(class="input", type="number", bind:valueAsNumber=lead_in, step="any", min="0") {}
+                }
+                p(class="help") { "A lâmina desce antes do início de cada corte, somada ao sobrecorte." }
+            }
+            div {
+                p(class="has-text-danger") { (error_message.get()) }
+                p(class="has-text-success") { (success_message.get()) }
+            }
+            button(class="button is-medium is-success", on:click=save_setting) { "Salvar" }
+        }
+    }
+}
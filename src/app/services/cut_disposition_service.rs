@@ -1,6 +1,8 @@
+use js_sys::{Function, Reflect};
 use serde::{Serialize, Deserialize};
 use serde_wasm_bindgen::to_value;
-use crate::app::{models::{cut_disposition::{CutDispositionInput, ConfigCutDispositionInput, Rectangle, PositionedRectangle, CutDispositionOutput}, app_error::AppError, piece::RectangleType}, invoke, log};
+use wasm_bindgen::{JsValue, closure::Closure};
+use crate::app::{models::{cut_disposition::{CutDispositionInput, ConfigCutDispositionInput, Rectangle, PositionedRectangle, CutDispositionOutput, OrganizeProgress, LayoutAdjustment, AdjustedCutDisposition}, app_error::AppError, piece::RectangleType}, invoke, listen, log};
 
 #[derive(Serialize, Deserialize)]
 struct NoArgs<> {
@@ -198,11 +200,11 @@ pub async fn get_piece_by_id(id: u32) -> Result<Rectangle, AppError> {
     }
 }
 
-pub async fn get_showcase() -> Result<Option<Rectangle>, AppError> {
-    let value = invoke("get_showcase", to_value(&NoArgs { }).unwrap()).await;
+pub async fn get_showcase_by_id(id: u32) -> Result<Rectangle, AppError> {
+    let value = invoke("get_showcase", to_value(&GetWithIdArgs { id }).unwrap()).await;
     match value {
         Ok(ok_js_value) => {
-            let a = serde_wasm_bindgen::from_value::<Option<Rectangle>>(ok_js_value);
+            let a = serde_wasm_bindgen::from_value::<Rectangle>(ok_js_value);
             match a {
                 Ok(a) => Ok(a),
                 Err(error) => {
@@ -274,6 +276,44 @@ pub async fn get_prohibited_area_by_id(id: u32) -> Result<PositionedRectangle, A
     }
 }
 
+pub async fn get_pinned_piece_by_id(id: u32) -> Result<PositionedRectangle, AppError> {
+    let value = invoke("get_pinned_piece", to_value(&GetWithIdArgs { id }).unwrap()).await;
+    match value {
+        Ok(ok_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<PositionedRectangle>(ok_js_value);
+            match a {
+                Ok(a) => Ok(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(
+                        AppError {
+                            status:1, 
+                            message: "Falha ao buscar Peça Fixa".to_owned(), 
+                            timestamp: 1
+                        }
+                    )
+                }
+            }
+        },
+        Err(err_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<AppError>(err_js_value);
+            match a {
+                Ok(a) => Err(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(
+                        AppError {
+                            status:1, 
+                            message: "Falha ao buscar Peça Fixa".to_owned(), 
+                            timestamp: 1
+                        }
+                    )
+                }
+            }
+        }
+    }
+}
+
 pub async fn edit_piece(piece: RectangleType) -> Result<(), AppError> {
     let value = invoke("edit_piece", to_value(&CreatePieceArgs { piece }).unwrap()).await;
     match value {
@@ -374,11 +414,36 @@ pub async fn remove_prohibited_area_by_id(id: u32) -> Result<(), AppError> {
     }
 }
 
-pub async fn get_cut_disposition_output() -> Result<CutDispositionOutput, AppError> { 
+pub async fn remove_pinned_piece_by_id(id: u32) -> Result<(), AppError> {
+    let value = invoke("delete_pinned_piece", to_value(&GetWithIdArgs { id }).unwrap()).await;
+    match value {
+        Ok(_ok_js_value) => {
+            Ok(())
+        },
+        Err(err_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<AppError>(err_js_value);
+            match a {
+                Ok(a) => Err(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(
+                        AppError {
+                            status:1, 
+                            message: "Falha ao remover Peça Fixa".to_owned(), 
+                            timestamp: 1
+                        }
+                    )
+                }
+            }
+        }
+    }
+}
+
+pub async fn get_cut_disposition_output() -> Result<Vec<CutDispositionOutput>, AppError> { 
     let value = invoke("organize_cut_disposition", to_value(&NoArgs { }).unwrap()).await;
     match value {
         Ok(ok_js_value) => {
-            let a = serde_wasm_bindgen::from_value::<CutDispositionOutput>(ok_js_value);
+            let a = serde_wasm_bindgen::from_value::<Vec<CutDispositionOutput>>(ok_js_value);
             match a {
                 Ok(a) => Ok(a),
                 Err(error) => {
@@ -410,4 +475,140 @@ pub async fn get_cut_disposition_output() -> Result<CutDispositionOutput, AppErr
             }
         }
     }
-}
\ No newline at end of file
+}
+// None when the pieces or the config changed after the last organization
+pub async fn get_cut_disposition_layout() -> Result<Option<Vec<CutDispositionOutput>>, AppError> { 
+    let value = invoke("get_cut_disposition_layout", to_value(&NoArgs { }).unwrap()).await;
+    match value {
+        Ok(ok_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<Option<Vec<CutDispositionOutput>>>(ok_js_value);
+            match a {
+                Ok(a) => Ok(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(
+                        AppError {
+                            status:1, 
+                            message: "Falha ao buscar disposição de cortes".to_owned(), 
+                            timestamp: 1
+                        }
+                    )
+                }
+            }
+        },
+        Err(err_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<AppError>(err_js_value);
+            match a {
+                Ok(a) => Err(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(
+                        AppError {
+                            status:1, 
+                            message: "Falha ao buscar disposição de cortes".to_owned(), 
+                            timestamp: 1
+                        }
+                    )
+                }
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AdjustCutDispositionArgs<> {
+    sheet_index: usize,
+    adjustment: LayoutAdjustment,
+}
+
+pub async fn adjust_cut_disposition(sheet_index: usize, adjustment: LayoutAdjustment) -> Result<AdjustedCutDisposition, AppError> { 
+    let value = invoke("adjust_cut_disposition", to_value(&AdjustCutDispositionArgs { sheet_index, adjustment }).unwrap()).await;
+    match value {
+        Ok(ok_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<AdjustedCutDisposition>(ok_js_value);
+            match a {
+                Ok(a) => Ok(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(
+                        AppError {
+                            status:1, 
+                            message: "Falha ao ajustar disposição de cortes".to_owned(), 
+                            timestamp: 1
+                        }
+                    )
+                }
+            }
+        },
+        Err(err_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<AppError>(err_js_value);
+            match a {
+                Ok(a) => Err(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(
+                        AppError {
+                            status:1, 
+                            message: "Falha ao ajustar disposição de cortes".to_owned(), 
+                            timestamp: 1
+                        }
+                    )
+                }
+            }
+        }
+    }
+}
+
+pub async fn cancel_organize_cut_disposition() -> Result<(), AppError> { 
+    let value = invoke("cancel_organize_cut_disposition", to_value(&NoArgs { }).unwrap()).await;
+    match value {
+        Ok(_) => Ok(()),
+        Err(err_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<AppError>(err_js_value);
+            match a {
+                Ok(a) => Err(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(
+                        AppError {
+                            status:1, 
+                            message: "Falha ao cancelar disposição de cortes".to_owned(), 
+                            timestamp: 1
+                        }
+                    )
+                }
+            }
+        }
+    }
+}
+
+// Stops listening to the organize progress when dropped
+pub struct OrganizeProgressListener {
+    _handler: Closure<dyn FnMut(JsValue)>,
+    unlisten: Function,
+}
+
+impl Drop for OrganizeProgressListener {
+    fn drop(&mut self) {
+        let _ = self.unlisten.call0(&JsValue::NULL);
+    }
+}
+
+pub async fn listen_organize_progress(mut on_progress: impl FnMut(OrganizeProgress) + 'static) -> Option<OrganizeProgressListener> {
+    let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+        let organize_progress = Reflect::get(&event, &JsValue::from_str("payload"))
+            .ok()
+            .and_then(|payload| serde_wasm_bindgen::from_value::<OrganizeProgress>(payload).ok());
+        if let Some(organize_progress) = organize_progress {
+            on_progress(organize_progress);
+        }
+    });
+    match listen("organize-progress", &handler).await {
+        Ok(unlisten) => Some(OrganizeProgressListener { _handler: handler, unlisten: Function::from(unlisten) }),
+        Err(_) => {
+            log("Falha ao acompanhar disposição de cortes");
+            None
+        },
+    }
+}
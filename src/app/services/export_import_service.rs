@@ -1,7 +1,7 @@
 use serde::{Serialize, Deserialize};
 use serde_wasm_bindgen::to_value;
 
-use crate::app::{models::app_error::AppError, invoke, log};
+use crate::app::{models::{app_error::AppError, cut_disposition::DispositionViolation}, invoke, log};
 
 
 #[derive(Serialize, Deserialize)]
@@ -53,11 +53,12 @@ pub async fn export_disposition(
 pub struct NoArgs {
 }
 
-pub async fn import_disposition() -> Result<String, AppError> { 
+// the violations of the layout in the file, it is only imported without them
+pub async fn import_disposition() -> Result<Vec<DispositionViolation>, AppError> { 
     let value = invoke("import_disposition", to_value(&NoArgs {}).unwrap()).await;
     match value {
         Ok(ok_js_value) => {
-            let a = serde_wasm_bindgen::from_value::<String>(ok_js_value);
+            let a = serde_wasm_bindgen::from_value::<Vec<DispositionViolation>>(ok_js_value);
             match a {
                 Ok(a) => Ok(a),
                 Err(error) => {
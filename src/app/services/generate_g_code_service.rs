@@ -1,19 +1,30 @@
 use serde::{Serialize, Deserialize};
 use serde_wasm_bindgen::to_value;
 
-use crate::app::{models::app_error::AppError, invoke, log};
+use crate::app::{models::{app_error::AppError, cutting_lines::CutStrategy}, invoke, log};
 
 #[derive(Serialize, Deserialize)]
+#[allow(non_snake_case)]
 pub struct GenerateGCodeArgs {
     fileName: String, 
     pullTextile: bool,
+    oneFilePerSheet: bool,
+    trimSelvage: bool,
+    skipFabricBorderLines: bool,
+    skipWasteLines: bool,
+    cutStrategy: CutStrategy,
 }
 
 pub async fn generate_g_code_file(
     file_name: String, 
-    pull_textile: bool
+    pull_textile: bool,
+    one_file_per_sheet: bool,
+    trim_selvage: bool,
+    skip_fabric_border_lines: bool,
+    skip_waste_lines: bool,
+    cut_strategy: CutStrategy
 ) -> Result<String, AppError> { 
-    let value = invoke("generate_g_code", to_value(&GenerateGCodeArgs {fileName: file_name, pullTextile: pull_textile}).unwrap()).await;
+    let value = invoke("generate_g_code", to_value(&GenerateGCodeArgs {fileName: file_name, pullTextile: pull_textile, oneFilePerSheet: one_file_per_sheet, trimSelvage: trim_selvage, skipFabricBorderLines: skip_fabric_border_lines, skipWasteLines: skip_waste_lines, cutStrategy: cut_strategy}).unwrap()).await;
     match value {
         Ok(ok_js_value) => {
             let a = serde_wasm_bindgen::from_value::<String>(ok_js_value);
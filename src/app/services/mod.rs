@@ -4,3 +4,4 @@ pub mod cutting_table_service;
 pub mod fabric_service;
 pub mod generate_g_code_service;
 pub mod export_import_service;
+pub mod setting_service;
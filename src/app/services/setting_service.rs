@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use crate::app::{
+    invoke, log,
+    models::{
+        app_error::AppError,
+        setting::Setting,
+    },
+};
+
+#[derive(Serialize, Deserialize)]
+struct SettingNoArgs {}
+
+pub async fn get_setting() -> Result<Setting, AppError> {
+    let value = invoke("get_setting", to_value(&SettingNoArgs {}).unwrap()).await;
+    match value {
+        Ok(ok_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<Setting>(ok_js_value);
+            match a {
+                Ok(a) => Ok(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(AppError {
+                        status: 1,
+                        message: "Falha ao buscar configurações".to_owned(),
+                        timestamp: 1,
+                    })
+                }
+            }
+        }
+        Err(err_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<AppError>(err_js_value);
+            match a {
+                Ok(a) => Err(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(AppError {
+                        status: 1,
+                        message: "Falha ao buscar configurações".to_owned(),
+                        timestamp: 1,
+                    })
+                }
+            }
+        }
+    }
+}
+
+// the labels go without the unit when it can not be read
+pub async fn get_length_unit_symbol() -> String {
+    let value = invoke("get_length_unit_symbol", to_value(&SettingNoArgs {}).unwrap()).await;
+    match value {
+        Ok(ok_js_value) => serde_wasm_bindgen::from_value::<String>(ok_js_value).unwrap_or_default(),
+        Err(_err_js_value) => String::new(),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SettingUpdateArgs {
+    setting: Setting,
+}
+
+pub async fn update_setting(setting: Setting) -> Result<Setting, AppError> {
+    let value = invoke("update_setting", to_value(&SettingUpdateArgs { setting }).unwrap()).await;
+    match value {
+        Ok(ok_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<Setting>(ok_js_value);
+            match a {
+                Ok(a) => Ok(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(AppError {
+                        status: 1,
+                        message: "Falha ao salvar configurações".to_owned(),
+                        timestamp: 1,
+                    })
+                }
+            }
+        }
+        Err(err_js_value) => {
+            let a = serde_wasm_bindgen::from_value::<AppError>(err_js_value);
+            match a {
+                Ok(a) => Err(a),
+                Err(error) => {
+                    log(error.to_string().as_str());
+                    Err(AppError {
+                        status: 1,
+                        message: "Falha ao salvar configurações".to_owned(),
+                        timestamp: 1,
+                    })
+                }
+            }
+        }
+    }
+}